@@ -341,7 +341,7 @@ async fn configuration_examples() -> Result<()> {
     info!("Created config with custom settings");
     info!("  Base URL: {}", config.base_url);
     info!("  Timeout: {:?}", config.timeout);
-    info!("  Max retries: {}", config.max_retries);
+    info!("  Max retries: {}", config.backoff.max_retries);
 
     // Example 2: Configuration from environment
     std::env::set_var("LLM_ROUTER_BASE_URL", "http://test-server:3000");
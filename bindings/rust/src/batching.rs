@@ -0,0 +1,269 @@
+//! Continuous token-budget batching for `BatchInferenceRequest`.
+//!
+//! `Client::batch_inference` sends a fixed list of requests as one HTTP
+//! call. `BatchScheduler` instead lets callers enqueue requests one at a
+//! time as they arrive and groups them by a token budget rather than raw
+//! concurrency, the way continuous-batching inference servers do: a
+//! background task repeatedly pulls as many waiting [`Entry`]s as fit under
+//! `max_batch_total_tokens` and flushes them as one `BatchInferenceRequest`.
+
+use crate::{
+    error::{LLMRouterError, Result},
+    models::{BatchInferenceRequest, InferenceRequest, InferenceResponse},
+    protocols::http::HttpClient,
+};
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::time::timeout;
+use tracing::{debug, warn};
+
+/// How long to hold an under-filled batch open for more arrivals before
+/// flushing it anyway, so a slow trickle of requests can't stall forever.
+const IDLE_FLUSH_DELAY: Duration = Duration::from_millis(5);
+
+/// Estimate the token cost of a request: prompt length (~4 bytes/token)
+/// plus however many tokens it's allowed to generate.
+fn estimate_tokens(request: &InferenceRequest) -> u32 {
+    let prompt_tokens = (request.prompt.len() / 4).max(1) as u32;
+    let max_tokens = request.options.as_ref().and_then(|o| o.max_tokens).unwrap_or(256);
+    prompt_tokens + max_tokens
+}
+
+/// A request waiting in the batching queue, tagged with when it arrived and
+/// the channel its eventual response is delivered on.
+struct Entry {
+    request: InferenceRequest,
+    #[allow(dead_code)]
+    enqueued_at: Instant,
+    response_tx: oneshot::Sender<Result<InferenceResponse>>,
+}
+
+/// FIFO queue of entries waiting to be admitted into a batch.
+#[derive(Default)]
+struct Queue {
+    entries: VecDeque<Entry>,
+}
+
+impl Queue {
+    /// Pull as many waiting entries as fit under `max_batch_total_tokens`.
+    ///
+    /// Always admits at least one entry: a request whose own token estimate
+    /// already exceeds the budget gets a batch of one rather than stalling
+    /// the queue behind it forever.
+    fn admit(&mut self, max_batch_total_tokens: u32) -> Vec<Entry> {
+        let mut batch = Vec::new();
+        let mut used = 0u32;
+
+        while let Some(front) = self.entries.front() {
+            let cost = estimate_tokens(&front.request);
+            if !batch.is_empty() && used + cost > max_batch_total_tokens {
+                break;
+            }
+
+            let entry = self.entries.pop_front().expect("front just checked");
+            used += cost;
+            batch.push(entry);
+
+            if used >= max_batch_total_tokens {
+                break;
+            }
+        }
+
+        batch
+    }
+}
+
+/// Per-batch statistics accumulated over a scheduler's lifetime, read via
+/// `BatchScheduler::stats()`. They are not copied into
+/// `BatchInferenceResponse::{batches_formed, average_fill}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchingStats {
+    /// Number of batches flushed so far
+    pub batches_formed: u32,
+    /// Running average of `batch_tokens / max_batch_total_tokens` across
+    /// every flushed batch
+    pub average_fill: f64,
+}
+
+impl BatchingStats {
+    fn record(&mut self, batch_tokens: u32, max_batch_total_tokens: u32) {
+        let fill = if max_batch_total_tokens == 0 {
+            0.0
+        } else {
+            (batch_tokens as f64 / max_batch_total_tokens as f64).min(1.0)
+        };
+        let n = self.batches_formed as f64;
+        self.average_fill = (self.average_fill * n + fill) / (n + 1.0);
+        self.batches_formed += 1;
+    }
+}
+
+/// Groups enqueued `InferenceRequest`s into token-budgeted batches and
+/// submits each one to `HttpClient::batch_inference` as it's formed.
+pub struct BatchScheduler {
+    enqueue: mpsc::UnboundedSender<Entry>,
+    stats: Arc<Mutex<BatchingStats>>,
+}
+
+impl BatchScheduler {
+    /// Spawn the background task that owns the queue for this scheduler's
+    /// lifetime. On each iteration it waits for at least one entry, drains
+    /// anything else already waiting, then either flushes the batch formed
+    /// so far or holds it a little longer for more arrivals, based on
+    /// whether the queued tokens already clear `waiting_served_ratio` of
+    /// the budget.
+    pub fn spawn(http_client: Arc<HttpClient>, max_batch_total_tokens: u32, waiting_served_ratio: f32) -> Self {
+        let (enqueue, mut receive) = mpsc::unbounded_channel::<Entry>();
+        let stats = Arc::new(Mutex::new(BatchingStats::default()));
+        let task_stats = stats.clone();
+
+        tokio::spawn(async move {
+            let mut queue = Queue::default();
+
+            loop {
+                if queue.entries.is_empty() {
+                    match receive.recv().await {
+                        Some(entry) => queue.entries.push_back(entry),
+                        None => break,
+                    }
+                }
+
+                while let Ok(entry) = receive.try_recv() {
+                    queue.entries.push_back(entry);
+                }
+
+                let waiting_tokens: u32 = queue.entries.iter().map(|e| estimate_tokens(&e.request)).sum();
+                let fill_ratio = (waiting_tokens as f32 / max_batch_total_tokens.max(1) as f32).min(1.0);
+                if fill_ratio < waiting_served_ratio {
+                    // Not enough queued up yet to justify flushing; hold the
+                    // batch open briefly for more arrivals before giving up
+                    // and sending an under-filled one.
+                    match timeout(IDLE_FLUSH_DELAY, receive.recv()).await {
+                        Ok(Some(entry)) => {
+                            queue.entries.push_back(entry);
+                            continue;
+                        }
+                        Ok(None) if queue.entries.is_empty() => break,
+                        Ok(None) | Err(_) => {}
+                    }
+                }
+
+                let batch = queue.admit(max_batch_total_tokens);
+                if batch.is_empty() {
+                    continue;
+                }
+
+                let batch_tokens: u32 = batch.iter().map(|e| estimate_tokens(&e.request)).sum();
+                task_stats.lock().await.record(batch_tokens, max_batch_total_tokens);
+
+                let (requests, senders): (Vec<_>, Vec<_>) =
+                    batch.into_iter().map(|e| (e.request, e.response_tx)).unzip();
+
+                debug!("Flushing continuous batch of {} request(s), ~{} tokens", requests.len(), batch_tokens);
+
+                let batch_request = BatchInferenceRequest::new(requests).fail_fast(false);
+                match http_client.batch_inference(batch_request).await {
+                    Ok(response) => {
+                        for (sender, resp) in senders.into_iter().zip(response.responses) {
+                            let _ = sender.send(Ok(resp));
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Continuous batch flush failed: {}", e);
+                        let message = e.to_string();
+                        for sender in senders {
+                            let _ = sender.send(Err(LLMRouterError::other(message.clone(), None::<LLMRouterError>)));
+                        }
+                    }
+                }
+            }
+        });
+
+        Self { enqueue, stats }
+    }
+
+    /// Enqueue `request` and await its response once it's been admitted
+    /// into a batch and that batch has been flushed.
+    pub async fn submit(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let (response_tx, response_rx) = oneshot::channel();
+        let entry = Entry {
+            request,
+            enqueued_at: Instant::now(),
+            response_tx,
+        };
+
+        self.enqueue
+            .send(entry)
+            .map_err(|_| LLMRouterError::streaming("Batch scheduler is no longer running"))?;
+
+        response_rx
+            .await
+            .map_err(|_| LLMRouterError::streaming("Batch scheduler dropped the response channel"))?
+    }
+
+    /// Snapshot of batching statistics accumulated so far
+    pub async fn stats(&self) -> BatchingStats {
+        *self.stats.lock().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::InferenceOptions;
+
+    fn request(prompt: &str, max_tokens: u32) -> InferenceRequest {
+        InferenceRequest::new(prompt).options(InferenceOptions::new().max_tokens(max_tokens))
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        let request = request("1234", 100);
+        assert_eq!(estimate_tokens(&request), 1 + 100);
+    }
+
+    #[tokio::test]
+    async fn test_admit_respects_token_budget() {
+        let mut queue = Queue::default();
+        for _ in 0..3 {
+            let (tx, _rx) = oneshot::channel();
+            queue.entries.push_back(Entry {
+                request: request("hello", 50),
+                enqueued_at: Instant::now(),
+                response_tx: tx,
+            });
+        }
+
+        let batch = queue.admit(80);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(queue.entries.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_admit_always_makes_progress_on_oversized_entry() {
+        let mut queue = Queue::default();
+        let (tx, _rx) = oneshot::channel();
+        queue.entries.push_back(Entry {
+            request: request("hello", 1000),
+            enqueued_at: Instant::now(),
+            response_tx: tx,
+        });
+
+        let batch = queue.admit(10);
+        assert_eq!(batch.len(), 1);
+        assert!(queue.entries.is_empty());
+    }
+
+    #[test]
+    fn test_batching_stats_average_fill() {
+        let mut stats = BatchingStats::default();
+        stats.record(50, 100);
+        stats.record(100, 100);
+
+        assert_eq!(stats.batches_formed, 2);
+        assert!((stats.average_fill - 0.75).abs() < f64::EPSILON);
+    }
+}
@@ -1,33 +1,73 @@
 //! Main client implementation for LLM Router
 
 use crate::{
+    batching::BatchScheduler,
     config::RouterConfig,
+    endpoint_pool::EndpointPool,
     error::{LLMRouterError, Result},
+    health::HealthMonitor,
     models::*,
     protocols::http::HttpClient,
+    providers::{build_provider, Provider},
+    utils::retry::{reconnect_backoff_delay, retry_stream, RetryConfig},
 };
 
+use std::collections::HashMap;
+
+#[cfg(any(feature = "grpc", feature = "websocket"))]
+use crate::protocols::ConnectionState;
+
 #[cfg(feature = "grpc")]
 use crate::protocols::grpc::GrpcClient;
 
 #[cfg(feature = "websocket")]
-use crate::protocols::websocket::WebSocketClient;
+use crate::protocols::websocket::{EventSubscription, WebSocketClient};
 
+use futures::stream::BoxStream;
 use futures::Stream;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 /// Main LLM Router client with support for multiple protocols
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Client {
     config: Arc<RouterConfig>,
     http_client: Arc<HttpClient>,
+    /// Chat-completion backend selected by `config.provider`; `inference`,
+    /// `stream_inference`, `chat_completion`, and `list_models` dispatch
+    /// through this instead of `http_client` directly, so they work the same
+    /// whether `config.provider` is the native dialect or an OpenAI-compatible
+    /// server.
+    provider: Arc<dyn Provider>,
     #[cfg(feature = "grpc")]
     grpc_client: Arc<RwLock<Option<GrpcClient>>>,
     #[cfg(feature = "websocket")]
     websocket_client: Arc<RwLock<Option<WebSocketClient>>>,
     session_id: Arc<RwLock<Option<String>>>,
+    /// Lazily spawned on the first `batch_inference_continuous` call, keyed
+    /// to that call's `max_batch_total_tokens`/`waiting_served_ratio`
+    batch_scheduler: Arc<RwLock<Option<Arc<BatchScheduler>>>>,
+    /// Tracks Healthy/Degraded/Unhealthy across consecutive `inference()`
+    /// outcomes; subscribe via `health_updates()`
+    health: Arc<HealthMonitor>,
+    /// Set when `config.endpoint_pool.endpoints` is non-empty; `inference()`
+    /// routes through it instead of `provider` so it can fail over across
+    /// the pool
+    endpoint_pool: Option<Arc<EndpointPool>>,
+    /// Set by `close()`/`shutdown()` so `Drop` doesn't spawn a redundant
+    /// best-effort close and so both are safe to call more than once.
+    closed: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("config", &self.config)
+            .field("provider", &self.provider.name())
+            .finish()
+    }
 }
 
 impl Client {
@@ -37,15 +77,27 @@ impl Client {
         
         let config = Arc::new(config);
         let http_client = Arc::new(HttpClient::new(config.clone()).await?);
-        
+        let provider = build_provider(&config, http_client.clone());
+
+        let endpoint_pool = if config.endpoint_pool.endpoints.is_empty() {
+            None
+        } else {
+            Some(EndpointPool::spawn(&config).await?)
+        };
+
         let client = Self {
             config: config.clone(),
             http_client,
+            provider,
             #[cfg(feature = "grpc")]
             grpc_client: Arc::new(RwLock::new(None)),
             #[cfg(feature = "websocket")]
             websocket_client: Arc::new(RwLock::new(None)),
             session_id: Arc::new(RwLock::new(None)),
+            batch_scheduler: Arc::new(RwLock::new(None)),
+            health: Arc::new(HealthMonitor::default()),
+            endpoint_pool,
+            closed: Arc::new(AtomicBool::new(false)),
         };
 
         info!("LLM Router client created with base URL: {}", client.config.base_url);
@@ -107,8 +159,8 @@ impl Client {
 
     /// List available models
     pub async fn list_models(&self, include_unloaded: bool) -> Result<Vec<ModelInfo>> {
-        debug!("Listing models (include_unloaded: {})", include_unloaded);
-        self.http_client.list_models(include_unloaded).await
+        debug!("Listing models (include_unloaded: {}) via {} provider", include_unloaded, self.provider.name());
+        self.provider.list_models(include_unloaded).await
     }
 
     /// Get information about a specific model
@@ -129,15 +181,48 @@ impl Client {
         self.http_client.unload_model(model_id, force).await
     }
 
-    /// Perform inference
+    /// Perform inference. Routes through `endpoint_pool` (selecting and
+    /// failing over across the configured endpoints) when one is set up,
+    /// otherwise dispatches through `provider` against the single
+    /// `base_url` as before.
     pub async fn inference(&self, mut request: InferenceRequest) -> Result<InferenceResponse> {
+        request.validate()?;
+
         // Add session ID if available
         if request.session_id.is_none() {
             request.session_id = self.get_session_id().await;
         }
 
-        debug!("Running inference with model: {:?}", request.model_id);
-        self.http_client.inference(request).await
+        let result = if let Some(pool) = &self.endpoint_pool {
+            debug!("Running inference with model: {:?} via endpoint pool", request.model_id);
+            pool.inference(&request).await
+        } else {
+            debug!("Running inference with model: {:?} via {} provider", request.model_id, self.provider.name());
+            self.provider.inference(request).await
+        };
+
+        match &result {
+            Ok(_) => self.health.record_success(),
+            Err(_) => self.health.record_failure(),
+        }
+        result
+    }
+
+    /// Number of endpoints in the pool, or `None` if `endpoint_pool` isn't
+    /// configured (the default: `Client` talks to `base_url` alone)
+    pub fn endpoint_count(&self) -> Option<usize> {
+        self.endpoint_pool.as_ref().map(|pool| pool.len())
+    }
+
+    /// Subscribe to live Healthy/Degraded/Unhealthy transitions driven by
+    /// consecutive `inference()` outcomes
+    pub fn health_updates(&self) -> tokio::sync::watch::Receiver<HealthStatus> {
+        self.health.subscribe()
+    }
+
+    /// Whether the client's health is currently `HealthStatus::Healthy`
+    pub fn is_healthy(&self) -> bool {
+        self.health.is_healthy()
     }
 
     /// Quick inference with minimal setup
@@ -161,6 +246,8 @@ impl Client {
         &self,
         mut request: InferenceRequest,
     ) -> Result<impl Stream<Item = Result<StreamingResponse>>> {
+        request.validate()?;
+
         // Ensure streaming is enabled
         if let Some(ref mut options) = request.options {
             options.stream = Some(true);
@@ -173,73 +260,299 @@ impl Client {
             request.session_id = self.get_session_id().await;
         }
 
-        debug!("Starting streaming inference with model: {:?}", request.model_id);
-        self.http_client.stream_inference(request).await
+        debug!(
+            "Starting streaming inference with model: {:?} via {} provider",
+            request.model_id,
+            self.provider.name()
+        );
+        self.provider.stream_inference(request).await
+    }
+
+    /// Like [`Client::stream_inference`], but transparently reconnects
+    /// through [`retry_stream`] if the stream yields a retryable error
+    /// partway through, instead of ending the generation on a transient
+    /// socket drop. Each reconnect re-issues `request` via a fresh
+    /// `stream_inference` call, with `request.metadata["resume_from_token"]`
+    /// set to the count of `StreamingResponse` chunks already delivered so a
+    /// server that understands the key can skip re-sending them. The final
+    /// error, once `config` says to stop retrying, is surfaced like any
+    /// other stream item.
+    pub async fn stream_inference_with_retry(
+        &self,
+        request: InferenceRequest,
+        config: RetryConfig,
+    ) -> Result<impl Stream<Item = Result<StreamingResponse>>> {
+        request.validate()?;
+
+        let client = self.clone();
+        Ok(retry_stream(
+            move |tokens_delivered| {
+                let client = client.clone();
+                let mut request = request.clone();
+                if tokens_delivered > 0 {
+                    request
+                        .metadata
+                        .get_or_insert_with(HashMap::new)
+                        .insert("resume_from_token".to_string(), serde_json::json!(tokens_delivered));
+                }
+                async move {
+                    client
+                        .stream_inference(request)
+                        .await
+                        .map(|stream| Box::pin(stream) as BoxStream<'static, Result<StreamingResponse>>)
+                }
+            },
+            config,
+        ))
     }
 
     /// Perform batch inference
     pub async fn batch_inference(&self, request: BatchInferenceRequest) -> Result<BatchInferenceResponse> {
+        request.validate(&self.config.validation)?;
         info!("Running batch inference with {} requests", request.requests.len());
         self.http_client.batch_inference(request).await
     }
 
-    /// Chat completion interface
-    pub async fn chat_completion(
+    /// Submit `request` for continuous, token-budget batching instead of
+    /// fanning it out independently: it joins a shared queue, and a
+    /// background `BatchScheduler` groups it with other waiting requests
+    /// into a batch under `max_batch_total_tokens`, flushing once
+    /// `waiting_served_ratio` of that budget is queued (an under-filled
+    /// batch is flushed after a short idle wait). The scheduler is spawned on first use and
+    /// reused for later calls, so only the first call's budget/ratio take
+    /// effect.
+    pub async fn batch_inference_continuous(
         &self,
-        messages: Vec<ChatMessage>,
-        model_id: Option<String>,
-        options: Option<InferenceOptions>,
+        request: InferenceRequest,
+        max_batch_total_tokens: u32,
+        waiting_served_ratio: f32,
     ) -> Result<InferenceResponse> {
-        // Convert messages to a single prompt (simplified approach)
-        let prompt = messages
-            .iter()
-            .map(|msg| format!("{}: {}", msg.role, msg.content))
-            .collect::<Vec<_>>()
-            .join("\n");
+        let scheduler = {
+            let mut guard = self.batch_scheduler.write().await;
+            if guard.is_none() {
+                info!(
+                    "Spawning continuous batch scheduler (max_batch_total_tokens: {}, waiting_served_ratio: {})",
+                    max_batch_total_tokens, waiting_served_ratio
+                );
+                *guard = Some(Arc::new(BatchScheduler::spawn(
+                    self.http_client.clone(),
+                    max_batch_total_tokens,
+                    waiting_served_ratio,
+                )));
+            }
+            guard.as_ref().unwrap().clone()
+        };
 
-        let mut request = InferenceRequest::new(prompt);
-        if let Some(model_id) = model_id {
-            request = request.model_id(model_id);
-        }
+        scheduler.submit(request).await
+    }
+
+    /// Run one prompt across several models concurrently and return each
+    /// model's response side-by-side, so callers can A/B compare quality and
+    /// speed before deciding which model to route to. Reuses the batch
+    /// inference path, keyed by `model_id` instead of distinct prompts, with
+    /// `fail_fast` disabled so one model failing doesn't abort the others.
+    pub async fn arena(
+        &self,
+        prompt: impl Into<String>,
+        model_ids: Vec<String>,
+        options: Option<InferenceOptions>,
+    ) -> Result<ArenaResponse> {
+        let mut request = ArenaRequest::new(prompt, model_ids);
         if let Some(options) = options {
             request = request.options(options);
         }
 
-        self.inference(request).await
+        info!("Running arena comparison across {} models", request.model_ids.len());
+
+        let requests = request
+            .model_ids
+            .iter()
+            .map(|model_id| {
+                let mut inference_request = InferenceRequest::new(request.prompt.clone()).model_id(model_id.clone());
+                if let Some(ref options) = request.options {
+                    inference_request = inference_request.options(options.clone());
+                }
+                inference_request
+            })
+            .collect::<Vec<_>>();
+
+        let batch_request = BatchInferenceRequest::new(requests)
+            .max_concurrent(request.max_concurrent.unwrap_or(request.model_ids.len() as u32))
+            .fail_fast(false);
+
+        let batch_response = self.batch_inference(batch_request).await?;
+
+        let results = request
+            .model_ids
+            .into_iter()
+            .zip(batch_response.responses)
+            .map(|(model_id, response)| ArenaResult {
+                latency_ms: response.metrics.as_ref().and_then(|m| m.latency_ms),
+                tokens_generated: response.metrics.as_ref().and_then(|m| m.tokens_generated),
+                model_id,
+                response,
+            })
+            .collect();
+
+        Ok(ArenaResponse {
+            results,
+            total_time_ms: batch_response.total_time_ms,
+        })
+    }
+
+    /// Run inference multiplexed over the cached `websocket_client()`
+    /// connection instead of opening a new HTTP request: one socket carries
+    /// many concurrent `inference()` calls correlated by request id, so none
+    /// of them suffer head-of-line blocking behind each other.
+    #[cfg(feature = "websocket")]
+    pub async fn ws_inference(&self, mut request: InferenceRequest) -> Result<InferenceResponse> {
+        request.validate()?;
+        if request.session_id.is_none() {
+            request.session_id = self.get_session_id().await;
+        }
+
+        let ws_client = self.websocket_client().await?;
+        let result = ws_client.ws_unary_inference(request).await;
+        match &result {
+            Ok(_) => self.health.record_success(),
+            Err(_) => self.health.record_failure(),
+        }
+        result
+    }
+
+    /// Subscribe to server-pushed events (model load/unload, health
+    /// transitions, metric updates) matching `filter`, instead of polling
+    /// `get_status()`/`get_metrics()`. The returned `EventSubscription`
+    /// shares the cached `websocket_client()` connection with any other
+    /// subscriptions and in-flight `ws_inference` calls; dropping it sends
+    /// an unsubscribe frame and frees its routing entry.
+    #[cfg(feature = "websocket")]
+    pub async fn subscribe(&self, filter: SubscriptionFilter) -> Result<EventSubscription> {
+        let ws_client = self.websocket_client().await?;
+        ws_client.subscribe(filter).await
+    }
+
+    /// Embed one or more strings into vectors. Dispatches through
+    /// `config.provider`, same as `inference`/`chat_completion`.
+    pub async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        debug!("Running embeddings via {} provider", self.provider.name());
+        self.provider.embeddings(request).await
+    }
+
+    /// Chat completion interface. Dispatches through `config.provider` —
+    /// the native router flattens `messages` into a single prompt, while an
+    /// OpenAI-compatible provider sends them as-is to `/v1/chat/completions`.
+    pub async fn chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model_id: Option<String>,
+        options: Option<InferenceOptions>,
+    ) -> Result<InferenceResponse> {
+        debug!("Running chat completion via {} provider", self.provider.name());
+        self.provider.chat_completion(messages, model_id, options).await
     }
 
-    /// Get gRPC client (if enabled)
+    /// Get gRPC client (if enabled), dialing it with `config.reconnect`'s
+    /// backoff policy on first use.
+    ///
+    /// `GrpcClient` itself doesn't track a live/dead state the way
+    /// `WebSocketClient` does (there's no long-lived stream to watch), so
+    /// this only covers the "never dialed yet" case today; a dropped gRPC
+    /// channel surfaces as a normal per-call error rather than triggering a
+    /// supervised reconnect.
     #[cfg(feature = "grpc")]
     pub async fn grpc_client(&self) -> Result<GrpcClient> {
         let mut client_guard = self.grpc_client.write().await;
-        
+
         if client_guard.is_none() {
             info!("Initializing gRPC client");
-            let grpc_client = GrpcClient::new(self.config.clone()).await?;
+            let grpc_client = Self::dial_with_backoff(
+                || GrpcClient::new(self.config.clone()),
+                &self.config.reconnect,
+            )
+            .await?;
             *client_guard = Some(grpc_client);
         }
-        
+
         Ok(client_guard.as_ref().unwrap().clone())
     }
 
-    /// Get WebSocket client (if enabled)
+    /// Get WebSocket client (if enabled), transparently re-dialing with
+    /// `config.reconnect`'s backoff policy whenever the cached connection's
+    /// reader/writer tasks have flagged it `Failed`.
     #[cfg(feature = "websocket")]
     pub async fn websocket_client(&self) -> Result<WebSocketClient> {
         let mut client_guard = self.websocket_client.write().await;
-        
-        if client_guard.is_none() {
-            info!("Initializing WebSocket client");
-            let ws_client = WebSocketClient::new(self.config.clone()).await?;
+
+        let needs_dial = match client_guard.as_ref() {
+            Some(existing) => existing.state() == ConnectionState::Failed,
+            None => true,
+        };
+
+        if needs_dial {
+            info!("(Re)initializing WebSocket client");
+            let ws_client = Self::dial_with_backoff(
+                || WebSocketClient::new(self.config.clone()),
+                &self.config.reconnect,
+            )
+            .await?;
             *client_guard = Some(ws_client);
         }
-        
+
         Ok(client_guard.as_ref().unwrap().clone())
     }
 
-    /// Close all connections
+    /// Current state of the cached WebSocket connection, or `None` before
+    /// the first `websocket_client()` call has dialed one.
+    #[cfg(feature = "websocket")]
+    pub async fn websocket_connection_state(&self) -> Option<ConnectionState> {
+        self.websocket_client.read().await.as_ref().map(|c| c.state())
+    }
+
+    /// Dial a fresh protocol client, retrying a failed attempt up to
+    /// `policy.max_retries` times with exponential backoff before giving up
+    /// and returning the last error to the caller.
+    #[cfg(any(feature = "grpc", feature = "websocket"))]
+    async fn dial_with_backoff<F, Fut, T>(mut dial: F, policy: &crate::config::ReconnectConfig) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let mut attempt = 0u32;
+
+        loop {
+            match dial().await {
+                Ok(client) => return Ok(client),
+                Err(err) if attempt < policy.max_retries => {
+                    attempt += 1;
+                    let delay = reconnect_backoff_delay(policy, attempt);
+                    warn!(
+                        "Connect attempt {}/{} failed: {}; retrying in {:?}",
+                        attempt, policy.max_retries, err, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Close all connections: tears down the endpoint pool's prober and any
+    /// persistent gRPC/WebSocket connection, then the underlying
+    /// `http_client`. Idempotent; safe to call more than once (a second
+    /// call is a no-op) and it's what both `shutdown()` and `Drop`'s
+    /// best-effort cleanup call under the hood.
     pub async fn close(&self) -> Result<()> {
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
         info!("Closing LLM Router client connections");
 
+        if let Some(pool) = &self.endpoint_pool {
+            pool.shutdown();
+        }
+
         #[cfg(feature = "grpc")]
         {
             if let Some(grpc_client) = self.grpc_client.write().await.take() {
@@ -258,14 +571,39 @@ impl Client {
         info!("All connections closed");
         Ok(())
     }
+
+    /// Shut the client down, awaiting full teardown of the endpoint pool's
+    /// prober and any persistent gRPC/WebSocket connection before
+    /// returning. Consumes `self` so no further calls can race the
+    /// in-progress shutdown; prefer this over relying on `Drop`, which only
+    /// manages a best-effort `close()` for callers who forget.
+    pub async fn shutdown(self) -> Result<()> {
+        self.close().await
+    }
 }
 
-// Implement Drop to ensure cleanup
+// `close()` can't run synchronously, so a forgotten `close()`/`shutdown()`
+// call is handed off to a detached best-effort task instead of merely
+// logging a warning, as long as a runtime is available to spawn it on.
 impl Drop for Client {
     fn drop(&mut self) {
-        // Note: This is a best-effort cleanup since we can't await in Drop
-        // Users should call close() explicitly for proper cleanup
-        warn!("Client dropped - connections may not be properly closed");
+        if self.closed.load(Ordering::SeqCst) {
+            return;
+        }
+
+        // Other clones still share these connections; only the last handle closes them.
+        if Arc::strong_count(&self.session_id) > 1 {
+            return;
+        }
+
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let client = self.clone();
+            handle.spawn(async move {
+                let _ = client.close().await;
+            });
+        } else {
+            warn!("Client dropped outside a Tokio runtime - connections may not be properly closed");
+        }
     }
 }
 
@@ -305,4 +643,79 @@ mod tests {
         assert_eq!(request.model_id, Some("test-model".to_string()));
         assert_eq!(request.options.unwrap().max_tokens, Some(100));
     }
+
+    #[tokio::test]
+    async fn test_endpoint_count_is_none_without_pool_config() {
+        let config = RouterConfig::new("http://localhost:3000");
+        let client = Client::new(config).await.unwrap();
+
+        assert!(client.endpoint_count().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_marks_the_client_closed() {
+        let config = RouterConfig::new("http://localhost:3000");
+        let client = Client::new(config).await.unwrap();
+        let closed = client.closed.clone();
+
+        assert!(!closed.load(Ordering::SeqCst));
+        client.shutdown().await.unwrap();
+        assert!(closed.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_close_is_idempotent() {
+        let config = RouterConfig::new("http://localhost:3000");
+        let client = Client::new(config).await.unwrap();
+
+        client.close().await.unwrap();
+        client.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_drop_spawns_a_detached_close_when_not_already_closed() {
+        let config = RouterConfig::new("http://localhost:3000");
+        let client = Client::new(config).await.unwrap();
+        let closed = client.closed.clone();
+
+        drop(client);
+        // The Drop impl spawns close() on the current runtime rather than
+        // awaiting it inline, so give the spawned task a turn to run.
+        tokio::task::yield_now().await;
+
+        assert!(closed.load(Ordering::SeqCst));
+    }
+
+    #[cfg(feature = "websocket")]
+    #[tokio::test]
+    async fn test_dial_with_backoff_retries_then_succeeds() {
+        use crate::config::ReconnectConfig;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let attempts = AtomicU32::new(0);
+        let policy = ReconnectConfig {
+            max_retries: 3,
+            base_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_millis(5),
+            jitter: false,
+        };
+
+        let result: Result<&str> = Client::dial_with_backoff(
+            || {
+                let count = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if count < 2 {
+                        Err(LLMRouterError::websocket("not ready yet"))
+                    } else {
+                        Ok("connected")
+                    }
+                }
+            },
+            &policy,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), "connected");
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 //! Configuration for the LLM Router client
 
 use crate::error::{LLMRouterError, Result};
+use crate::NAME;
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::time::Duration;
 use url::Url;
 
@@ -16,10 +18,8 @@ pub struct RouterConfig {
     pub websocket_url: Option<String>,
     /// Request timeout
     pub timeout: Duration,
-    /// Maximum number of retries
-    pub max_retries: u32,
-    /// Base retry delay
-    pub retry_delay: Duration,
+    /// Retry/backoff policy for failed requests
+    pub backoff: BackoffConfig,
     /// API key for authentication
     pub api_key: Option<String>,
     /// User agent string
@@ -30,6 +30,218 @@ pub struct RouterConfig {
     pub rate_limit: RateLimitConfig,
     /// TLS settings
     pub tls: TlsConfig,
+    /// Compression settings
+    pub compression: CompressionConfig,
+    /// Extra header names (case-insensitive) to mask as `<masked>` in
+    /// `HttpClient`'s `Debug` output, beyond the built-in sensitive set
+    /// (`authorization`, `x-api-key`, `api-key`).
+    pub sensitive_headers: std::collections::HashSet<String>,
+    /// Static DNS/connect overrides, bypassing system resolution for the
+    /// listed hosts (blue/green fleets, pinning to a specific backend
+    /// instance, split-horizon deployments).
+    pub connect_to: Vec<ConnectOverride>,
+    /// Chat-completion backend `Client` dispatches through. Defaults to the
+    /// native router dialect; set to `ProviderConfig::OpenAiCompatible` to
+    /// target an OpenAI-compatible server instead.
+    pub provider: ProviderConfig,
+    /// Distributed tracing settings: OTLP export and W3C `traceparent`
+    /// propagation across outgoing requests.
+    pub tracing: TracingConfig,
+    /// JWT bearer auth: when set, `Client` mints and auto-refreshes a signed
+    /// token instead of sending `api_key` as a static bearer token. Set via
+    /// `RouterConfig::jwt_auth`.
+    pub jwt_auth: Option<JwtAuthConfig>,
+    /// Bounds enforced by `InferenceRequest::validate`/
+    /// `BatchInferenceRequest::validate` before a request reaches a backend
+    pub validation: ValidationConfig,
+    /// Backoff policy `Client` uses when transparently re-dialing a dropped
+    /// gRPC or WebSocket connection. Set via `RouterConfig::reconnect`.
+    pub reconnect: ReconnectConfig,
+    /// Multi-endpoint failover/load-balancing settings. Empty `endpoints`
+    /// (the default) keeps `Client` hardwired to `base_url` alone; set via
+    /// `RouterConfig::endpoint_pool`.
+    pub endpoint_pool: EndpointPoolConfig,
+}
+
+/// Bounds enforced by `InferenceRequest::validate`/`BatchInferenceRequest::validate`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ValidationConfig {
+    /// Maximum number of requests a single `BatchInferenceRequest` may carry
+    pub max_client_batch_size: usize,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_client_batch_size: 4,
+        }
+    }
+}
+
+/// Backoff policy for re-dialing a gRPC or WebSocket client whose connection
+/// was dropped: exponential growth (`base_delay * 2^(attempt - 1)` for the
+/// 1-based attempt number) clamped to
+/// `max_delay`, with optional full jitter to keep a fleet of clients that
+/// dropped together from hammering the server in lockstep on reconnect.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReconnectConfig {
+    /// Maximum number of reconnect attempts before giving up and surfacing
+    /// the last dial error to the caller
+    pub max_retries: u32,
+    /// Delay before the first reconnect attempt
+    pub base_delay: Duration,
+    /// Upper bound on any computed reconnect delay
+    pub max_delay: Duration,
+    /// Draw each delay uniformly from `[0, computed_delay]` instead of using
+    /// the computed delay directly
+    pub jitter: bool,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+/// Policy [`crate::endpoint_pool::EndpointPool`] uses to pick among
+/// currently-healthy endpoints for a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointSelectionPolicy {
+    /// Cycle through healthy endpoints in turn.
+    RoundRobin,
+    /// Prefer the healthy endpoint with the fewest in-flight requests.
+    LeastOutstanding,
+    /// Prefer the healthy endpoint with the lowest recent-latency EWMA.
+    LatencyWeighted,
+}
+
+impl Default for EndpointSelectionPolicy {
+    fn default() -> Self {
+        Self::RoundRobin
+    }
+}
+
+/// Multi-endpoint failover/load-balancing settings, consumed by
+/// `Client`/[`crate::endpoint_pool::EndpointPool`]. `base_url` is always
+/// pooled as the first endpoint; `endpoints` lists any additional router
+/// instances to spread requests and fail over across.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointPoolConfig {
+    /// Additional router URLs beyond `base_url` to pool requests across
+    pub endpoints: Vec<String>,
+    /// How to pick among currently-healthy endpoints
+    pub policy: EndpointSelectionPolicy,
+    /// Consecutive request failures before an endpoint is marked unhealthy
+    /// and skipped until a background probe succeeds against it
+    pub unhealthy_after_failures: u32,
+    /// Pin a given `session_id` to whichever endpoint it first lands on,
+    /// rather than re-selecting per request, as long as that endpoint stays
+    /// healthy
+    pub session_affinity: bool,
+}
+
+impl Default for EndpointPoolConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            policy: EndpointSelectionPolicy::default(),
+            unhealthy_after_failures: 3,
+            session_affinity: false,
+        }
+    }
+}
+
+/// JWT bearer-auth settings, set via `RouterConfig::jwt_auth`. A signed,
+/// short-lived token is minted from `claims` and re-minted automatically
+/// `refresh_skew_secs` before it expires, for gateways that require signed
+/// credentials rather than a long-lived `api_key`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JwtAuthConfig {
+    /// Signing key bytes: an HMAC secret for `HS256`/`HS384`/`HS512`, or a
+    /// PEM-encoded RSA private key for the `RS*` algorithms
+    pub signing_key: Vec<u8>,
+    /// Signing algorithm
+    pub algorithm: jsonwebtoken::Algorithm,
+    /// Custom claims merged into every minted token, alongside the `exp`,
+    /// `iat`, and (if set) `iss` this module adds
+    pub claims: serde_json::Map<String, serde_json::Value>,
+    /// `iss` claim; omitted from minted tokens when unset
+    pub issuer: Option<String>,
+    /// Token lifetime before expiry, in seconds
+    pub expires_in_secs: i64,
+    /// Re-mint the token this many seconds before its real expiry, so an
+    /// in-flight request doesn't race a token that's about to expire
+    pub refresh_skew_secs: i64,
+}
+
+/// Distributed tracing configuration, consumed by `init_tracing_with_config`.
+/// Every outgoing `Client` request opens a span under `service_name` and
+/// injects a W3C `traceparent` header; when `otlp_endpoint` is set those
+/// spans are also exported to an OTLP collector.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TracingConfig {
+    /// OTLP gRPC collector endpoint, e.g. `http://localhost:4317`. Spans
+    /// stay local-only (no export, `traceparent` still injected) when unset.
+    pub otlp_endpoint: Option<String>,
+    /// Fraction of traces to sample, from 0.0 (none) to 1.0 (all)
+    pub sampler_ratio: f64,
+    /// `service.name` resource attribute reported to the collector
+    pub service_name: String,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            sampler_ratio: 1.0,
+            service_name: NAME.to_string(),
+        }
+    }
+}
+
+/// Which chat-completion backend a [`crate::client::Client`] talks to,
+/// selected via [`RouterConfig::provider`]. Tagged by `type` so it
+/// round-trips through the same TOML/JSON/YAML config files
+/// [`RouterConfig::load`] reads.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    /// The native LLM-Runner-Router HTTP dialect (the default).
+    Native,
+    /// A server speaking the OpenAI `/v1/chat/completions` + `/v1/models`
+    /// dialect — a local llama.cpp/vLLM/Ollama server or a hosted endpoint.
+    OpenAiCompatible {
+        /// Base URL of the OpenAI-compatible server, e.g. `https://api.openai.com`.
+        base_url: String,
+        /// Bearer token sent as `Authorization: Bearer <api_key>`, if required.
+        api_key: Option<String>,
+        /// Model name sent as `model` when a call doesn't specify one.
+        default_model: String,
+    },
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        ProviderConfig::Native
+    }
+}
+
+/// A single `host:port -> addrs` override applied at connect time instead of
+/// resolving `host` through system DNS.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectOverride {
+    /// Hostname to override, as it appears in the request URL
+    pub host: String,
+    /// Port to override; only requests to this `(host, port)` pair are affected
+    pub port: u16,
+    /// Candidate addresses to connect to instead of resolving `host`
+    pub addrs: Vec<SocketAddr>,
 }
 
 /// Connection pool configuration
@@ -69,6 +281,309 @@ pub struct TlsConfig {
     pub client_key_path: Option<String>,
 }
 
+impl TlsConfig {
+    /// Build a `rustls::ClientConfig` from this policy, for transports
+    /// (`protocols::http`, `protocols::http_blocking`, `protocols::websocket`)
+    /// that need a concrete TLS client rather than just these paths.
+    ///
+    /// Starts from the platform's native root store, merges in `ca_cert_path`
+    /// when set, and enables mutual-TLS client authentication when both
+    /// `client_cert_path` and `client_key_path` are set. When `verify_ssl` is
+    /// `false`, installs a certificate verifier that accepts anything —
+    /// for local/self-signed dev servers only, never in production.
+    pub fn build_client_config(&self) -> Result<rustls::ClientConfig> {
+        let mut roots = rustls::RootCertStore::empty();
+
+        for cert in rustls_native_certs::load_native_certs().map_err(|e| {
+            LLMRouterError::configuration(format!("Failed to load native root certificates: {}", e))
+        })? {
+            // A handful of platform roots are routinely malformed from
+            // rustls's point of view; skip them instead of failing the load.
+            let _ = roots.add(&rustls::Certificate(cert.0));
+        }
+
+        if let Some(ca_path) = &self.ca_cert_path {
+            for cert in Self::read_certs(ca_path)? {
+                roots.add(&cert).map_err(|e| {
+                    LLMRouterError::configuration(format!("Invalid CA certificate in {}: {}", ca_path, e))
+                })?;
+            }
+        }
+
+        let builder = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots);
+
+        let mut client_config = match (&self.client_cert_path, &self.client_key_path) {
+            (Some(cert_path), Some(key_path)) => {
+                let cert_chain = Self::read_certs(cert_path)?;
+                let key = Self::read_private_key(key_path)?;
+                builder.with_client_auth_cert(cert_chain, key).map_err(|e| {
+                    LLMRouterError::configuration(format!("Invalid client certificate/key pair: {}", e))
+                })?
+            }
+            (None, None) => builder.with_no_client_auth(),
+            _ => {
+                return Err(LLMRouterError::configuration(
+                    "client_cert_path and client_key_path must both be set to enable mutual TLS",
+                ))
+            }
+        };
+
+        if !self.verify_ssl {
+            client_config
+                .dangerous()
+                .set_certificate_verifier(std::sync::Arc::new(danger::NoCertificateVerification));
+        }
+
+        Ok(client_config)
+    }
+
+    /// Parse every PEM certificate out of `path`
+    fn read_certs(path: &str) -> Result<Vec<rustls::Certificate>> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| LLMRouterError::configuration(format!("Failed to open certificate file {}: {}", path, e)))?;
+        let mut reader = std::io::BufReader::new(file);
+        let der_certs = rustls_pemfile::certs(&mut reader).map_err(|e| {
+            LLMRouterError::configuration(format!("Failed to parse PEM certificates in {}: {}", path, e))
+        })?;
+
+        if der_certs.is_empty() {
+            return Err(LLMRouterError::configuration(format!("No certificates found in {}", path)));
+        }
+
+        Ok(der_certs.into_iter().map(rustls::Certificate).collect())
+    }
+
+    /// Parse a private key out of `path`, trying PKCS#8 first and falling
+    /// back to PKCS#1 (RSA) encoding
+    fn read_private_key(path: &str) -> Result<rustls::PrivateKey> {
+        let open = || {
+            std::fs::File::open(path).map_err(|e| {
+                LLMRouterError::configuration(format!("Failed to open private key file {}: {}", path, e))
+            })
+        };
+
+        let mut reader = std::io::BufReader::new(open()?);
+        let pkcs8_keys = rustls_pemfile::pkcs8_private_keys(&mut reader).map_err(|e| {
+            LLMRouterError::configuration(format!("Failed to parse PKCS#8 private key in {}: {}", path, e))
+        })?;
+        if let Some(key) = pkcs8_keys.into_iter().next() {
+            return Ok(rustls::PrivateKey(key));
+        }
+
+        // The PKCS#8 pass above consumed the reader; re-open for the RSA fallback.
+        let mut reader = std::io::BufReader::new(open()?);
+        let rsa_keys = rustls_pemfile::rsa_private_keys(&mut reader).map_err(|e| {
+            LLMRouterError::configuration(format!("Failed to parse RSA private key in {}: {}", path, e))
+        })?;
+
+        rsa_keys
+            .into_iter()
+            .next()
+            .map(rustls::PrivateKey)
+            .ok_or_else(|| LLMRouterError::configuration(format!("No private key found in {}", path)))
+    }
+}
+
+/// Certificate verification that accepts any server certificate, installed
+/// only when `TlsConfig::verify_ssl` is explicitly `false`
+mod danger {
+    use rustls::client::{ServerCertVerified, ServerCertVerifier};
+
+    pub(super) struct NoCertificateVerification;
+
+    impl ServerCertVerifier for NoCertificateVerification {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &rustls::Certificate,
+            _intermediates: &[rustls::Certificate],
+            _server_name: &rustls::ServerName,
+            _scts: &mut dyn Iterator<Item = &[u8]>,
+            _ocsp_response: &[u8],
+            _now: std::time::SystemTime,
+        ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+    }
+}
+
+/// Retry/backoff policy for `utils::retry`'s decorrelated-jitter formula:
+/// each retry's delay is drawn from
+/// `[base_delay, min(max_backoff, prev_delay * multiplier)]` so a burst of
+/// clients retrying the same failure don't wake up in lockstep.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackoffConfig {
+    /// Base delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound on any computed retry delay
+    pub max_backoff: Duration,
+    /// Maximum number of retry attempts
+    pub max_retries: u32,
+    /// Growth factor applied to the previous delay's upper bound each retry
+    pub multiplier: f64,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            max_retries: 3,
+            multiplier: 3.0,
+        }
+    }
+}
+
+impl BackoffConfig {
+    /// Create a new backoff policy with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the base delay before the first retry
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the upper bound on any computed retry delay
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set the maximum number of retry attempts
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the growth factor applied to the previous delay's upper bound
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+}
+
+/// Compression configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// Negotiate and transparently decode gzip response bodies
+    pub decode_gzip: bool,
+    /// Negotiate and transparently decode brotli response bodies
+    pub decode_brotli: bool,
+    /// Gzip-compress POST bodies larger than `request_compression_threshold_bytes`,
+    /// setting `Content-Encoding: gzip`
+    pub compress_requests: bool,
+    /// Minimum JSON body size, in bytes, before `compress_requests` applies
+    pub request_compression_threshold_bytes: usize,
+}
+
+/// Per-request overrides for `RouterConfig`'s timeout/retry policy, attached
+/// to an individual inference or streaming call. Any field left `None` falls
+/// back to the corresponding global `RouterConfig` value via [`merge_with`](RequestConfig::merge_with).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestConfig {
+    /// Overrides `RouterConfig::timeout` for this request only
+    pub timeout: Option<Duration>,
+    /// Overrides `BackoffConfig::max_retries` for this request only
+    pub max_retries: Option<u32>,
+    /// Overrides `BackoffConfig::base_delay` for this request only
+    pub retry_delay: Option<Duration>,
+    /// Whether this request is safe to retry automatically. Set to `false`
+    /// for a request whose side effects must not be repeated; it then gets
+    /// zero retries instead of inheriting the global retry policy.
+    pub idempotent: bool,
+    /// Blunt kill switch for retries, independent of `idempotent`: an
+    /// idempotent read can still set this `false` to fail fast (e.g. a
+    /// cheap health check that the caller would rather poll again than have
+    /// this one call retry internally), whereas `idempotent` is about
+    /// whether retrying is *safe*, not whether it's *wanted*. Either one
+    /// being set to disable retries forces zero retries -- see
+    /// [`RequestConfig::merge_with`].
+    pub retry_enabled: bool,
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            max_retries: None,
+            retry_delay: None,
+            idempotent: true,
+            retry_enabled: true,
+        }
+    }
+}
+
+impl RequestConfig {
+    /// Create a new request override with no fields set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the request timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Override the maximum number of retries
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Override the base retry delay
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = Some(retry_delay);
+        self
+    }
+
+    /// Mark whether this request is safe to retry automatically
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// Enable or disable retries for this request regardless of
+    /// `idempotent`; see the field doc on [`RequestConfig::retry_enabled`]
+    pub fn retry_enabled(mut self, retry_enabled: bool) -> Self {
+        self.retry_enabled = retry_enabled;
+        self
+    }
+
+    /// Resolve this override against `base`, filling any unset field from the
+    /// global config. A non-idempotent request, or one with `retry_enabled`
+    /// set to `false`, resolves to zero retries regardless of `max_retries`,
+    /// so it fails fast instead of being retried.
+    pub fn merge_with(&self, base: &RouterConfig) -> ResolvedRequestConfig {
+        ResolvedRequestConfig {
+            timeout: self.timeout.unwrap_or(base.timeout),
+            max_retries: if self.idempotent && self.retry_enabled {
+                self.max_retries.unwrap_or(base.backoff.max_retries)
+            } else {
+                0
+            },
+            retry_delay: self.retry_delay.unwrap_or(base.backoff.base_delay),
+            max_backoff: base.backoff.max_backoff,
+            multiplier: base.backoff.multiplier,
+        }
+    }
+}
+
+/// Fully-resolved timeout/retry policy for a single request, after merging a
+/// `RequestConfig` override onto the global `RouterConfig`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedRequestConfig {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+}
+
 impl Default for RouterConfig {
     fn default() -> Self {
         Self {
@@ -76,13 +591,21 @@ impl Default for RouterConfig {
             grpc_url: Some("http://localhost:50051".to_string()),
             websocket_url: None, // Will be derived from base_url
             timeout: Duration::from_secs(30),
-            max_retries: 3,
-            retry_delay: Duration::from_secs(1),
+            backoff: BackoffConfig::default(),
             api_key: None,
             user_agent: format!("llm-runner-router-rust/{}", env!("CARGO_PKG_VERSION")),
             connection_pool: ConnectionPoolConfig::default(),
             rate_limit: RateLimitConfig::default(),
             tls: TlsConfig::default(),
+            compression: CompressionConfig::default(),
+            sensitive_headers: std::collections::HashSet::new(),
+            connect_to: Vec::new(),
+            provider: ProviderConfig::default(),
+            tracing: TracingConfig::default(),
+            jwt_auth: None,
+            validation: ValidationConfig::default(),
+            reconnect: ReconnectConfig::default(),
+            endpoint_pool: EndpointPoolConfig::default(),
         }
     }
 }
@@ -119,6 +642,120 @@ impl Default for TlsConfig {
     }
 }
 
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            decode_gzip: true,
+            decode_brotli: true,
+            compress_requests: false,
+            request_compression_threshold_bytes: 16 * 1024,
+        }
+    }
+}
+
+/// Serialization format of an on-disk config file, picked from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFileFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+/// Deserialize `content` as `T`, dispatching on `format`
+fn parse_config_content<T: for<'de> Deserialize<'de>>(
+    content: &str,
+    format: ConfigFileFormat,
+) -> Result<T> {
+    match format {
+        ConfigFileFormat::Toml => toml::from_str(content)
+            .map_err(|e| LLMRouterError::configuration(format!("Failed to parse TOML config file: {}", e))),
+        ConfigFileFormat::Json => serde_json::from_str(content)
+            .map_err(|e| LLMRouterError::configuration(format!("Failed to parse JSON config file: {}", e))),
+        ConfigFileFormat::Yaml => serde_yaml::from_str(content)
+            .map_err(|e| LLMRouterError::configuration(format!("Failed to parse YAML config file: {}", e))),
+    }
+}
+
+/// A config layer where every field is optional, so merging it over a
+/// `RouterConfig` via [`RouterConfig::merge`] only replaces fields it
+/// actually sets. Used to compose a base config file with environment
+/// variable overrides in [`RouterConfig::load`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialRouterConfig {
+    pub base_url: Option<String>,
+    pub grpc_url: Option<String>,
+    pub websocket_url: Option<String>,
+    pub timeout: Option<Duration>,
+    pub backoff: Option<BackoffConfig>,
+    pub api_key: Option<String>,
+    pub user_agent: Option<String>,
+    pub connection_pool: Option<ConnectionPoolConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub tls: Option<TlsConfig>,
+    pub compression: Option<CompressionConfig>,
+    pub sensitive_headers: Option<std::collections::HashSet<String>>,
+    pub connect_to: Option<Vec<ConnectOverride>>,
+    pub provider: Option<ProviderConfig>,
+    pub tracing: Option<TracingConfig>,
+    pub jwt_auth: Option<JwtAuthConfig>,
+}
+
+impl PartialRouterConfig {
+    /// Build a partial config from the same `LLM_ROUTER_*` environment
+    /// variables `RouterConfig::from_env` reads, leaving unset fields `None`.
+    pub fn from_env() -> Self {
+        let mut partial = Self::default();
+
+        if let Ok(base_url) = std::env::var("LLM_ROUTER_BASE_URL") {
+            partial.base_url = Some(base_url);
+        }
+
+        if let Ok(grpc_url) = std::env::var("LLM_ROUTER_GRPC_URL") {
+            partial.grpc_url = Some(grpc_url);
+        }
+
+        if let Ok(ws_url) = std::env::var("LLM_ROUTER_WEBSOCKET_URL") {
+            partial.websocket_url = Some(ws_url);
+        }
+
+        if let Ok(api_key) = std::env::var("LLM_ROUTER_API_KEY") {
+            partial.api_key = Some(api_key);
+        }
+
+        if let Ok(timeout_str) = std::env::var("LLM_ROUTER_TIMEOUT") {
+            if let Ok(timeout_secs) = timeout_str.parse::<u64>() {
+                partial.timeout = Some(Duration::from_secs(timeout_secs));
+            }
+        }
+
+        // These two env vars only touch one field of their sub-config, but
+        // `merge` replaces `backoff`/`tls` wholesale — if a lower layer also
+        // set other fields on the same sub-config, they're reset to default
+        // here rather than preserved.
+        if let Ok(retries_str) = std::env::var("LLM_ROUTER_MAX_RETRIES") {
+            if let Ok(retries) = retries_str.parse::<u32>() {
+                let mut backoff = BackoffConfig::default();
+                backoff.max_retries = retries;
+                partial.backoff = Some(backoff);
+            }
+        }
+
+        if let Ok(verify_ssl_str) = std::env::var("LLM_ROUTER_VERIFY_SSL") {
+            let mut tls = TlsConfig::default();
+            tls.verify_ssl = verify_ssl_str.to_lowercase() != "false";
+            partial.tls = Some(tls);
+        }
+
+        if let Ok(otlp_endpoint) = std::env::var("LLM_ROUTER_OTLP_ENDPOINT") {
+            let mut tracing = TracingConfig::default();
+            tracing.otlp_endpoint = Some(otlp_endpoint);
+            partial.tracing = Some(tracing);
+        }
+
+        partial
+    }
+}
+
 impl RouterConfig {
     /// Create a new configuration with the given base URL
     pub fn new(base_url: impl Into<String>) -> Self {
@@ -154,13 +791,25 @@ impl RouterConfig {
 
     /// Set the maximum number of retries
     pub fn max_retries(mut self, max_retries: u32) -> Self {
-        self.max_retries = max_retries;
+        self.backoff.max_retries = max_retries;
         self
     }
 
-    /// Set the retry delay
+    /// Set the base retry delay
     pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
-        self.retry_delay = retry_delay;
+        self.backoff.base_delay = retry_delay;
+        self
+    }
+
+    /// Set the decorrelated-jitter retry backoff cap
+    pub fn retry_backoff_cap(mut self, retry_backoff_cap: Duration) -> Self {
+        self.backoff.max_backoff = retry_backoff_cap;
+        self
+    }
+
+    /// Set the full retry/backoff policy at once
+    pub fn backoff(mut self, config: BackoffConfig) -> Self {
+        self.backoff = config;
         self
     }
 
@@ -194,6 +843,124 @@ impl RouterConfig {
         self
     }
 
+    /// Set compression configuration
+    pub fn compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = config;
+        self
+    }
+
+    /// Mark an additional header name as sensitive, so `HttpClient`'s `Debug`
+    /// impl masks its value instead of printing it.
+    pub fn mark_header_secret(mut self, header_name: impl Into<String>) -> Self {
+        self.sensitive_headers.insert(header_name.into());
+        self
+    }
+
+    /// Select the chat-completion backend `Client` dispatches through
+    pub fn provider(mut self, provider: ProviderConfig) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    /// Set distributed tracing configuration
+    pub fn tracing(mut self, config: TracingConfig) -> Self {
+        self.tracing = config;
+        self
+    }
+
+    /// Set request validation configuration
+    pub fn validation(mut self, config: ValidationConfig) -> Self {
+        self.validation = config;
+        self
+    }
+
+    /// Set the reconnect backoff policy `Client` uses to re-dial a dropped
+    /// gRPC or WebSocket connection
+    pub fn reconnect(mut self, config: ReconnectConfig) -> Self {
+        self.reconnect = config;
+        self
+    }
+
+    /// Set the multi-endpoint failover/load-balancing policy. A non-empty
+    /// `endpoints` list makes `Client` pool `base_url` with those endpoints
+    /// instead of talking to `base_url` alone.
+    pub fn endpoint_pool(mut self, config: EndpointPoolConfig) -> Self {
+        self.endpoint_pool = config;
+        self
+    }
+
+    /// Enable JWT bearer auth: `Client` will sign and attach a short-lived
+    /// `HS256` token built from `claims`, auto-refreshing it before expiry,
+    /// instead of sending `api_key` as a static bearer token. Chain
+    /// `jwt_algorithm`/`jwt_issuer`/`jwt_expires_in`/`jwt_refresh_skew` to
+    /// override the defaults.
+    pub fn jwt_auth(mut self, signing_key: impl Into<Vec<u8>>, claims: serde_json::Map<String, serde_json::Value>) -> Self {
+        self.jwt_auth = Some(JwtAuthConfig {
+            signing_key: signing_key.into(),
+            algorithm: jsonwebtoken::Algorithm::HS256,
+            claims,
+            issuer: None,
+            expires_in_secs: 300,
+            refresh_skew_secs: 30,
+        });
+        self
+    }
+
+    /// Override the JWT signing algorithm set by `jwt_auth` (default `HS256`)
+    pub fn jwt_algorithm(mut self, algorithm: jsonwebtoken::Algorithm) -> Self {
+        if let Some(ref mut jwt_auth) = self.jwt_auth {
+            jwt_auth.algorithm = algorithm;
+        }
+        self
+    }
+
+    /// Set the `iss` claim minted tokens carry
+    pub fn jwt_issuer(mut self, issuer: impl Into<String>) -> Self {
+        if let Some(ref mut jwt_auth) = self.jwt_auth {
+            jwt_auth.issuer = Some(issuer.into());
+        }
+        self
+    }
+
+    /// Override the JWT token lifetime set by `jwt_auth` (default 300s)
+    pub fn jwt_expires_in(mut self, expires_in_secs: i64) -> Self {
+        if let Some(ref mut jwt_auth) = self.jwt_auth {
+            jwt_auth.expires_in_secs = expires_in_secs;
+        }
+        self
+    }
+
+    /// Override how early a token is re-minted before its real expiry
+    /// (default 30s)
+    pub fn jwt_refresh_skew(mut self, refresh_skew_secs: i64) -> Self {
+        if let Some(ref mut jwt_auth) = self.jwt_auth {
+            jwt_auth.refresh_skew_secs = refresh_skew_secs;
+        }
+        self
+    }
+
+    /// Pin `host:port` to `addrs` instead of resolving `host` through system
+    /// DNS. Can be called multiple times to override several hosts.
+    pub fn connect_to(
+        mut self,
+        host: impl Into<String>,
+        port: u16,
+        addrs: Vec<SocketAddr>,
+    ) -> Self {
+        self.connect_to.push(ConnectOverride {
+            host: host.into(),
+            port,
+            addrs,
+        });
+        self
+    }
+
+    /// The baseline per-request policy derived from this config's global
+    /// timeout/retry settings, with no per-request overrides applied.
+    pub fn default_request_config(&self) -> ResolvedRequestConfig {
+        RequestConfig::default().merge_with(self)
+    }
+
     /// Validate the configuration
     pub fn validate(&self) -> Result<()> {
         // Validate base URL
@@ -227,6 +994,21 @@ impl RouterConfig {
             ));
         }
 
+        // Validate DNS/connect overrides
+        for override_entry in &self.connect_to {
+            if override_entry.host.is_empty() {
+                return Err(LLMRouterError::configuration(
+                    "connect_to override host must not be empty",
+                ));
+            }
+            if override_entry.addrs.is_empty() {
+                return Err(LLMRouterError::configuration(format!(
+                    "connect_to override for {}:{} must list at least one address",
+                    override_entry.host, override_entry.port
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -256,13 +1038,18 @@ impl RouterConfig {
     /// Get authentication headers
     pub fn get_auth_headers(&self) -> Vec<(String, String)> {
         let mut headers = Vec::new();
-        
-        if let Some(ref api_key) = self.api_key {
-            headers.push(("Authorization".to_string(), format!("Bearer {}", api_key)));
+
+        // JWT auth, when configured, supplies a per-request `Authorization`
+        // header of its own (minted/refreshed by `JwtTokenMinter`) instead of
+        // this static one, so skip it here to avoid sending both.
+        if self.jwt_auth.is_none() {
+            if let Some(ref api_key) = self.api_key {
+                headers.push(("Authorization".to_string(), format!("Bearer {}", api_key)));
+            }
         }
-        
+
         headers.push(("User-Agent".to_string(), self.user_agent.clone()));
-        
+
         headers
     }
 
@@ -294,7 +1081,7 @@ impl RouterConfig {
 
         if let Ok(retries_str) = std::env::var("LLM_ROUTER_MAX_RETRIES") {
             if let Ok(retries) = retries_str.parse::<u32>() {
-                config.max_retries = retries;
+                config.backoff.max_retries = retries;
             }
         }
 
@@ -302,22 +1089,115 @@ impl RouterConfig {
             config.tls.verify_ssl = verify_ssl_str.to_lowercase() != "false";
         }
 
+        if let Ok(otlp_endpoint) = std::env::var("LLM_ROUTER_OTLP_ENDPOINT") {
+            config.tracing.otlp_endpoint = Some(otlp_endpoint);
+        }
+
         config.validate()?;
         Ok(config)
     }
 
     /// Load configuration from a file
+    ///
+    /// Format is picked from the file extension: `.toml`, `.json`, or
+    /// `.yaml`/`.yml`. Files without a recognized extension are parsed as TOML.
     pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
         let content = std::fs::read_to_string(path)
             .map_err(|e| LLMRouterError::configuration(format!("Failed to read config file: {}", e)))?;
-        
-        let config: RouterConfig = toml::from_str(&content)
-            .map_err(|e| LLMRouterError::configuration(format!("Failed to parse config file: {}", e)))?;
-        
+
+        let config: RouterConfig = parse_config_content(&content, Self::format_of(path))?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Build a config by layering, in increasing precedence: defaults, an
+    /// optional config file, and environment variables. Apply any further
+    /// per-environment overrides to the returned `RouterConfig` with its
+    /// builder methods (e.g. `.api_key(...)`).
+    pub fn load(file_path: Option<impl AsRef<std::path::Path>>) -> Result<Self> {
+        let mut config = Self::default();
+
+        if let Some(path) = file_path {
+            let path = path.as_ref();
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| LLMRouterError::configuration(format!("Failed to read config file: {}", e)))?;
+            let partial: PartialRouterConfig = parse_config_content(&content, Self::format_of(path))?;
+            config = config.merge(partial);
+        }
+
+        config = config.merge(PartialRouterConfig::from_env());
+
         config.validate()?;
         Ok(config)
     }
 
+    /// Overwrite only the fields `other` actually sets, leaving everything
+    /// else untouched — the building block `load()` uses to layer a config
+    /// file and environment variables over the defaults.
+    pub fn merge(mut self, other: PartialRouterConfig) -> Self {
+        if let Some(base_url) = other.base_url {
+            self.base_url = base_url;
+        }
+        if let Some(grpc_url) = other.grpc_url {
+            self.grpc_url = Some(grpc_url);
+        }
+        if let Some(websocket_url) = other.websocket_url {
+            self.websocket_url = Some(websocket_url);
+        }
+        if let Some(timeout) = other.timeout {
+            self.timeout = timeout;
+        }
+        if let Some(backoff) = other.backoff {
+            self.backoff = backoff;
+        }
+        if let Some(api_key) = other.api_key {
+            self.api_key = Some(api_key);
+        }
+        if let Some(user_agent) = other.user_agent {
+            self.user_agent = user_agent;
+        }
+        if let Some(connection_pool) = other.connection_pool {
+            self.connection_pool = connection_pool;
+        }
+        if let Some(rate_limit) = other.rate_limit {
+            self.rate_limit = rate_limit;
+        }
+        if let Some(tls) = other.tls {
+            self.tls = tls;
+        }
+        if let Some(compression) = other.compression {
+            self.compression = compression;
+        }
+        if let Some(sensitive_headers) = other.sensitive_headers {
+            self.sensitive_headers = sensitive_headers;
+        }
+        if let Some(connect_to) = other.connect_to {
+            self.connect_to = connect_to;
+        }
+        if let Some(provider) = other.provider {
+            self.provider = provider;
+        }
+        if let Some(tracing) = other.tracing {
+            self.tracing = tracing;
+        }
+        if let Some(jwt_auth) = other.jwt_auth {
+            self.jwt_auth = Some(jwt_auth);
+        }
+        self
+    }
+
+    /// Config file format, picked from `path`'s extension; defaults to TOML
+    /// for extensionless or unrecognized paths.
+    fn format_of(path: &std::path::Path) -> ConfigFileFormat {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => ConfigFileFormat::Json,
+            Some("yaml") | Some("yml") => ConfigFileFormat::Yaml,
+            _ => ConfigFileFormat::Toml,
+        }
+    }
+
     /// Save configuration to a file
     pub fn save_to_file(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
         let content = toml::to_string_pretty(self)
@@ -339,7 +1219,7 @@ mod tests {
         let config = RouterConfig::default();
         assert_eq!(config.base_url, "http://localhost:3000");
         assert_eq!(config.timeout, Duration::from_secs(30));
-        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.backoff.max_retries, 3);
     }
 
     #[test]
@@ -351,7 +1231,7 @@ mod tests {
 
         assert_eq!(config.base_url, "http://example.com:8080");
         assert_eq!(config.timeout, Duration::from_secs(60));
-        assert_eq!(config.max_retries, 5);
+        assert_eq!(config.backoff.max_retries, 5);
         assert_eq!(config.api_key, Some("test-key".to_string()));
     }
 
@@ -373,6 +1253,31 @@ mod tests {
         assert!(config.is_tls_enabled());
     }
 
+    #[test]
+    fn test_tls_config_builds_client_config_with_defaults() {
+        let tls = TlsConfig::default();
+        assert!(tls.build_client_config().is_ok());
+    }
+
+    #[test]
+    fn test_tls_config_rejects_missing_ca_file() {
+        let tls = TlsConfig {
+            ca_cert_path: Some("/nonexistent/ca.pem".to_string()),
+            ..TlsConfig::default()
+        };
+        assert!(tls.build_client_config().is_err());
+    }
+
+    #[test]
+    fn test_tls_config_rejects_incomplete_mutual_tls() {
+        let tls = TlsConfig {
+            client_cert_path: Some("/nonexistent/client.pem".to_string()),
+            client_key_path: None,
+            ..TlsConfig::default()
+        };
+        assert!(tls.build_client_config().is_err());
+    }
+
     #[test]
     fn test_auth_headers() {
         let config = RouterConfig::new("http://example.com").api_key("test-key");
@@ -382,6 +1287,147 @@ mod tests {
         assert!(headers.iter().any(|(k, _)| k == "User-Agent"));
     }
 
+    #[test]
+    fn test_merge_only_overwrites_set_fields() {
+        let base = RouterConfig::new("http://example.com").api_key("base-key");
+
+        let mut partial = PartialRouterConfig::default();
+        partial.base_url = Some("http://overridden.example.com".to_string());
+
+        let merged = base.merge(partial);
+
+        assert_eq!(merged.base_url, "http://overridden.example.com");
+        assert_eq!(merged.api_key, Some("base-key".to_string()));
+    }
+
+    #[test]
+    fn test_load_layers_file_then_env_over_defaults() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "llm_router_test_config_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, r#"base_url = "http://from-file.example.com""#).unwrap();
+
+        std::env::set_var("LLM_ROUTER_API_KEY", "from-env-key");
+        let config = RouterConfig::load(Some(&path)).unwrap();
+        std::env::remove_var("LLM_ROUTER_API_KEY");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.base_url, "http://from-file.example.com");
+        assert_eq!(config.api_key, Some("from-env-key".to_string()));
+    }
+
+    #[test]
+    fn test_format_of_picks_format_from_extension() {
+        assert_eq!(
+            RouterConfig::format_of(std::path::Path::new("router.json")),
+            ConfigFileFormat::Json
+        );
+        assert_eq!(
+            RouterConfig::format_of(std::path::Path::new("router.yaml")),
+            ConfigFileFormat::Yaml
+        );
+        assert_eq!(
+            RouterConfig::format_of(std::path::Path::new("router.toml")),
+            ConfigFileFormat::Toml
+        );
+        assert_eq!(
+            RouterConfig::format_of(std::path::Path::new("router.conf")),
+            ConfigFileFormat::Toml
+        );
+    }
+
+    #[test]
+    fn test_connect_to_override_builder() {
+        let addr: std::net::SocketAddr = "127.0.0.1:8443".parse().unwrap();
+        let config = RouterConfig::new("https://router.internal")
+            .connect_to("router.internal", 443, vec![addr]);
+
+        assert_eq!(config.connect_to.len(), 1);
+        assert_eq!(config.connect_to[0].host, "router.internal");
+        assert_eq!(config.connect_to[0].port, 443);
+        assert_eq!(config.connect_to[0].addrs, vec![addr]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_connect_to_override_requires_at_least_one_addr() {
+        let config = RouterConfig::new("https://router.internal")
+            .connect_to("router.internal", 443, vec![]);
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_mark_header_secret() {
+        let config = RouterConfig::new("http://example.com")
+            .mark_header_secret("X-Tenant-Token");
+
+        assert!(config.sensitive_headers.contains("X-Tenant-Token"));
+    }
+
+    #[test]
+    fn test_request_config_merge_fills_unset_fields() {
+        let base = RouterConfig::new("http://example.com")
+            .timeout(Duration::from_secs(30))
+            .max_retries(3);
+
+        let resolved = RequestConfig::new().timeout(Duration::from_secs(5)).merge_with(&base);
+        assert_eq!(resolved.timeout, Duration::from_secs(5));
+        assert_eq!(resolved.max_retries, 3);
+        assert_eq!(resolved.retry_delay, base.backoff.base_delay);
+    }
+
+    #[test]
+    fn test_request_config_merge_inherits_backoff_policy() {
+        let base = RouterConfig::new("http://example.com").backoff(
+            BackoffConfig::new()
+                .max_backoff(Duration::from_secs(45))
+                .multiplier(2.5),
+        );
+
+        let resolved = RequestConfig::new().merge_with(&base);
+        assert_eq!(resolved.max_backoff, Duration::from_secs(45));
+        assert_eq!(resolved.multiplier, 2.5);
+    }
+
+    #[test]
+    fn test_backoff_config_builder() {
+        let backoff = BackoffConfig::new()
+            .base_delay(Duration::from_millis(50))
+            .max_backoff(Duration::from_secs(10))
+            .max_retries(4)
+            .multiplier(2.0);
+
+        assert_eq!(backoff.base_delay, Duration::from_millis(50));
+        assert_eq!(backoff.max_backoff, Duration::from_secs(10));
+        assert_eq!(backoff.max_retries, 4);
+        assert_eq!(backoff.multiplier, 2.0);
+    }
+
+    #[test]
+    fn test_request_config_non_idempotent_forces_zero_retries() {
+        let base = RouterConfig::new("http://example.com").max_retries(5);
+        let resolved = RequestConfig::new()
+            .max_retries(5)
+            .idempotent(false)
+            .merge_with(&base);
+
+        assert_eq!(resolved.max_retries, 0);
+    }
+
+    #[test]
+    fn test_request_config_retry_enabled_false_forces_zero_retries() {
+        let base = RouterConfig::new("http://example.com").max_retries(5);
+        let resolved = RequestConfig::new()
+            .max_retries(5)
+            .retry_enabled(false)
+            .merge_with(&base);
+
+        assert_eq!(resolved.max_retries, 0);
+    }
+
     #[test]
     fn test_config_validation() {
         // Valid config
@@ -397,4 +1443,40 @@ mod tests {
         config.timeout = Duration::from_secs(0);
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_reconnect_builder_overrides_default() {
+        let config = RouterConfig::new("http://example.com").reconnect(ReconnectConfig {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            jitter: false,
+        });
+
+        assert_eq!(config.reconnect.max_retries, 10);
+        assert!(!config.reconnect.jitter);
+    }
+
+    #[test]
+    fn test_endpoint_pool_defaults_to_no_extra_endpoints() {
+        let config = RouterConfig::default();
+        assert!(config.endpoint_pool.endpoints.is_empty());
+        assert_eq!(config.endpoint_pool.policy, EndpointSelectionPolicy::RoundRobin);
+        assert_eq!(config.endpoint_pool.unhealthy_after_failures, 3);
+        assert!(!config.endpoint_pool.session_affinity);
+    }
+
+    #[test]
+    fn test_endpoint_pool_builder_overrides_default() {
+        let config = RouterConfig::new("http://example.com").endpoint_pool(EndpointPoolConfig {
+            endpoints: vec!["http://peer-1:3000".to_string(), "http://peer-2:3000".to_string()],
+            policy: EndpointSelectionPolicy::LeastOutstanding,
+            unhealthy_after_failures: 5,
+            session_affinity: true,
+        });
+
+        assert_eq!(config.endpoint_pool.endpoints.len(), 2);
+        assert_eq!(config.endpoint_pool.policy, EndpointSelectionPolicy::LeastOutstanding);
+        assert!(config.endpoint_pool.session_affinity);
+    }
 }
\ No newline at end of file
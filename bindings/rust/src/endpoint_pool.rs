@@ -0,0 +1,355 @@
+//! Multi-endpoint failover and load-balancing across a pool of router URLs.
+//!
+//! `Client` is otherwise hardwired to one `HttpClient` talking to
+//! `config.base_url`. When `config.endpoint_pool.endpoints` lists additional
+//! URLs, `EndpointPool` builds one `HttpClient` per endpoint (`base_url`
+//! included) and picks one per request via a selectable
+//! [`EndpointSelectionPolicy`], tracking health from request outcomes and a
+//! background prober so `Client::inference` automatically fails over to a
+//! healthy peer instead of surfacing a single endpoint's errors.
+
+use crate::{
+    config::{EndpointSelectionPolicy, RouterConfig},
+    error::{LLMRouterError, Result},
+    models::InferenceRequest,
+    protocols::http::HttpClient,
+};
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// How often the background prober re-checks endpoints currently marked
+/// unhealthy.
+const PROBE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Smoothing factor for the per-endpoint latency EWMA used by
+/// `EndpointSelectionPolicy::LatencyWeighted`: each sample contributes 20%
+/// of the new estimate.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// One pooled endpoint and the health/latency state used to route around it.
+struct EndpointState {
+    url: String,
+    http_client: Arc<HttpClient>,
+    healthy: AtomicBool,
+    consecutive_failures: AtomicU32,
+    outstanding: AtomicU32,
+    latency_ewma_ms: Mutex<f64>,
+}
+
+impl EndpointState {
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::SeqCst)
+    }
+}
+
+/// Pool of `HttpClient`s for `config.base_url` plus any configured extra
+/// endpoints, with routing, health tracking, and session affinity.
+pub struct EndpointPool {
+    endpoints: Vec<Arc<EndpointState>>,
+    policy: EndpointSelectionPolicy,
+    unhealthy_after_failures: u32,
+    session_affinity: bool,
+    affinity: Mutex<HashMap<String, usize>>,
+    round_robin: AtomicUsize,
+    /// Cancelled by `shutdown()` so the background prober exits instead of
+    /// sleeping forever on a pool nothing references anymore.
+    cancel: CancellationToken,
+}
+
+impl EndpointPool {
+    /// Build one `HttpClient` per endpoint and spawn the background prober
+    /// that re-checks unhealthy endpoints every [`PROBE_INTERVAL`].
+    pub async fn spawn(config: &Arc<RouterConfig>) -> Result<Arc<Self>> {
+        let mut urls = vec![config.base_url.clone()];
+        urls.extend(config.endpoint_pool.endpoints.iter().cloned());
+
+        let mut endpoints = Vec::with_capacity(urls.len());
+        for url in urls {
+            let mut endpoint_config = (**config).clone();
+            endpoint_config.base_url = url.clone();
+            let http_client = Arc::new(HttpClient::new(Arc::new(endpoint_config)).await?);
+            endpoints.push(Arc::new(EndpointState {
+                url,
+                http_client,
+                healthy: AtomicBool::new(true),
+                consecutive_failures: AtomicU32::new(0),
+                outstanding: AtomicU32::new(0),
+                latency_ewma_ms: Mutex::new(0.0),
+            }));
+        }
+
+        let pool = Arc::new(Self {
+            endpoints,
+            policy: config.endpoint_pool.policy,
+            unhealthy_after_failures: config.endpoint_pool.unhealthy_after_failures,
+            session_affinity: config.endpoint_pool.session_affinity,
+            affinity: Mutex::new(HashMap::new()),
+            round_robin: AtomicUsize::new(0),
+            cancel: CancellationToken::new(),
+        });
+
+        tokio::spawn(run_prober(pool.clone()));
+
+        Ok(pool)
+    }
+
+    /// Number of endpoints in the pool (`base_url` plus any configured
+    /// extras).
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Stop the background prober. Idempotent; safe to call even if the
+    /// pool is about to be dropped anyway.
+    pub fn shutdown(&self) {
+        self.cancel.cancel();
+    }
+
+    /// Run `request` against the pool, selecting an endpoint per `policy`
+    /// (or the session's pinned endpoint, under affinity) and failing over
+    /// on error, up to `endpoints.len()` attempts in all. Each attempt
+    /// re-runs the selection, so an endpoint that failed but is still
+    /// marked healthy can be picked again.
+    pub async fn inference(
+        &self,
+        request: &InferenceRequest,
+    ) -> Result<crate::models::InferenceResponse> {
+        let mut last_error = None;
+
+        for _ in 0..self.endpoints.len() {
+            let endpoint = self.select(request.session_id.as_deref()).await;
+            endpoint.outstanding.fetch_add(1, Ordering::SeqCst);
+            let started = Instant::now();
+            let result = endpoint.http_client.inference(request.clone()).await;
+            endpoint.outstanding.fetch_sub(1, Ordering::SeqCst);
+
+            match result {
+                Ok(response) => {
+                    self.record_success(&endpoint, started.elapsed()).await;
+                    return Ok(response);
+                }
+                Err(err) => {
+                    self.record_failure(&endpoint);
+                    warn!("Endpoint {} inference failed, failing over: {}", endpoint.url, err);
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            LLMRouterError::network("Endpoint pool is empty", None::<std::io::Error>)
+        }))
+    }
+
+    /// Pick the endpoint for `session_id`: its pinned endpoint if affinity
+    /// is enabled and that endpoint is still healthy, otherwise the next
+    /// pick per `policy`.
+    async fn select(&self, session_id: Option<&str>) -> Arc<EndpointState> {
+        if self.session_affinity {
+            if let Some(session_id) = session_id {
+                let mut affinity = self.affinity.lock().await;
+                if let Some(&index) = affinity.get(session_id) {
+                    if self.endpoints[index].is_healthy() {
+                        return self.endpoints[index].clone();
+                    }
+                }
+                let endpoint = self.pick();
+                let index = self.index_of(&endpoint);
+                affinity.insert(session_id.to_string(), index);
+                return endpoint;
+            }
+        }
+
+        self.pick()
+    }
+
+    fn index_of(&self, endpoint: &Arc<EndpointState>) -> usize {
+        self.endpoints
+            .iter()
+            .position(|candidate| Arc::ptr_eq(candidate, endpoint))
+            .unwrap_or(0)
+    }
+
+    /// Pick the next endpoint per `policy` among currently-healthy
+    /// endpoints, falling back to the full set if every endpoint looks
+    /// unhealthy so the pool still attempts the request rather than
+    /// failing outright.
+    fn pick(&self) -> Arc<EndpointState> {
+        let mut candidates: Vec<Arc<EndpointState>> =
+            self.endpoints.iter().filter(|e| e.is_healthy()).cloned().collect();
+        if candidates.is_empty() {
+            candidates = self.endpoints.clone();
+        }
+
+        match self.policy {
+            EndpointSelectionPolicy::RoundRobin => {
+                let index = self.round_robin.fetch_add(1, Ordering::SeqCst) % candidates.len();
+                candidates[index].clone()
+            }
+            EndpointSelectionPolicy::LeastOutstanding => candidates
+                .into_iter()
+                .min_by_key(|e| e.outstanding.load(Ordering::SeqCst))
+                .expect("candidates is never empty"),
+            EndpointSelectionPolicy::LatencyWeighted => {
+                // A contended latency lock is read as 0 (most-preferred)
+                // rather than awaited, so selection never blocks the hot
+                // path on another request's in-flight update.
+                candidates
+                    .into_iter()
+                    .min_by(|a, b| {
+                        let latency_a = a.latency_ewma_ms.try_lock().map(|g| *g).unwrap_or(0.0);
+                        let latency_b = b.latency_ewma_ms.try_lock().map(|g| *g).unwrap_or(0.0);
+                        latency_a.total_cmp(&latency_b)
+                    })
+                    .expect("candidates is never empty")
+            }
+        }
+    }
+
+    async fn record_success(&self, endpoint: &Arc<EndpointState>, latency: Duration) {
+        endpoint.consecutive_failures.store(0, Ordering::SeqCst);
+        if !endpoint.healthy.swap(true, Ordering::SeqCst) {
+            info!("Endpoint {} recovered", endpoint.url);
+        }
+
+        let sample_ms = latency.as_secs_f64() * 1000.0;
+        let mut ewma = endpoint.latency_ewma_ms.lock().await;
+        *ewma = if *ewma == 0.0 {
+            sample_ms
+        } else {
+            (1.0 - LATENCY_EWMA_ALPHA) * *ewma + LATENCY_EWMA_ALPHA * sample_ms
+        };
+    }
+
+    fn record_failure(&self, endpoint: &Arc<EndpointState>) {
+        let failures = endpoint.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
+        if failures >= self.unhealthy_after_failures && endpoint.healthy.swap(false, Ordering::SeqCst) {
+            warn!("Endpoint {} marked unhealthy after {} consecutive failures", endpoint.url, failures);
+        }
+    }
+}
+
+/// Periodically re-`health_check` every endpoint currently marked unhealthy,
+/// putting it back in rotation the moment a probe succeeds.
+async fn run_prober(pool: Arc<EndpointPool>) {
+    loop {
+        tokio::select! {
+            _ = pool.cancel.cancelled() => break,
+            _ = tokio::time::sleep(PROBE_INTERVAL) => {}
+        }
+
+        for endpoint in &pool.endpoints {
+            if endpoint.is_healthy() {
+                continue;
+            }
+
+            match endpoint.http_client.health_check().await {
+                Ok(_) => {
+                    endpoint.consecutive_failures.store(0, Ordering::SeqCst);
+                    endpoint.healthy.store(true, Ordering::SeqCst);
+                    info!("Probe succeeded; endpoint {} back in rotation", endpoint.url);
+                }
+                Err(e) => debug!("Probe failed for endpoint {}: {}", endpoint.url, e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{EndpointPoolConfig, RouterConfig};
+
+    async fn state(url: &str, healthy: bool) -> Arc<EndpointState> {
+        let http_client = HttpClient::new(Arc::new(RouterConfig::new(url)))
+            .await
+            .expect("HttpClient::new doesn't touch the network");
+
+        Arc::new(EndpointState {
+            url: url.to_string(),
+            http_client: Arc::new(http_client),
+            healthy: AtomicBool::new(healthy),
+            consecutive_failures: AtomicU32::new(0),
+            outstanding: AtomicU32::new(0),
+            latency_ewma_ms: Mutex::new(0.0),
+        })
+    }
+
+    fn pool(policy: EndpointSelectionPolicy, endpoints: Vec<Arc<EndpointState>>) -> EndpointPool {
+        EndpointPool {
+            endpoints,
+            policy,
+            unhealthy_after_failures: 3,
+            session_affinity: false,
+            affinity: Mutex::new(HashMap::new()),
+            round_robin: AtomicUsize::new(0),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_pool_config_default_has_no_extra_endpoints() {
+        let config = EndpointPoolConfig::default();
+        assert!(config.endpoints.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_record_failure_marks_unhealthy_after_threshold() {
+        let endpoint = state("http://a", true).await;
+        let pool = pool(EndpointSelectionPolicy::RoundRobin, vec![endpoint.clone()]);
+
+        pool.record_failure(&endpoint);
+        pool.record_failure(&endpoint);
+        assert!(endpoint.is_healthy());
+
+        pool.record_failure(&endpoint);
+        assert!(!endpoint.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_pick_skips_unhealthy_endpoints() {
+        let healthy = state("http://healthy", true).await;
+        let unhealthy = state("http://unhealthy", false).await;
+        let pool = pool(EndpointSelectionPolicy::RoundRobin, vec![unhealthy, healthy.clone()]);
+
+        for _ in 0..4 {
+            assert_eq!(pool.pick().url, healthy.url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pick_falls_back_to_full_set_when_all_unhealthy() {
+        let a = state("http://a", false).await;
+        let b = state("http://b", false).await;
+        let pool = pool(EndpointSelectionPolicy::RoundRobin, vec![a, b]);
+
+        // Neither endpoint is healthy, but `pick` must still return one.
+        let picked = pool.pick();
+        assert!(picked.url == "http://a" || picked.url == "http://b");
+    }
+
+    #[tokio::test]
+    async fn test_pick_least_outstanding_prefers_idle_endpoint() {
+        let busy = state("http://busy", true).await;
+        busy.outstanding.store(5, Ordering::SeqCst);
+        let idle = state("http://idle", true).await;
+
+        let pool = pool(EndpointSelectionPolicy::LeastOutstanding, vec![busy, idle.clone()]);
+        assert_eq!(pool.pick().url, idle.url);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_the_prober_token() {
+        let pool = pool(EndpointSelectionPolicy::RoundRobin, vec![]);
+        assert!(!pool.cancel.is_cancelled());
+
+        pool.shutdown();
+        assert!(pool.cancel.is_cancelled());
+    }
+}
@@ -31,6 +31,17 @@ pub enum LLMRouterError {
         retry_after: Option<std::time::Duration>,
     },
 
+    /// A `429`/`503` response carrying structured rate-limit metadata
+    /// (`Retry-After` and/or the `X-RateLimit-*` trio), as opposed to the
+    /// free-form [`LLMRouterError::RateLimit`].
+    #[error("Rate limited: retry after {retry_after:?}")]
+    RateLimited {
+        retry_after: Option<std::time::Duration>,
+        limit: Option<u32>,
+        remaining: Option<u32>,
+        reset: Option<u64>,
+    },
+
     /// Authentication/authorization errors
     #[error("Authentication error: {message}")]
     Authentication { message: String },
@@ -95,6 +106,33 @@ pub enum LLMRouterError {
         #[source]
         source: Option<Box<dyn std::error::Error + Send + Sync>>,
     },
+
+    /// The request-level retry budget in `retry_with_backoff` ran out
+    /// without a success. Wraps the error from the final attempt so
+    /// callers/logs see both how many attempts were made and why the last
+    /// one failed, rather than just the last error on its own.
+    #[error("Retry budget exhausted after {attempts} attempt(s): {source}")]
+    RetryExhausted {
+        attempts: u32,
+        #[source]
+        source: Box<LLMRouterError>,
+    },
+
+    /// `retry_with_config_observed` ran out of attempts. Unlike
+    /// `RetryExhausted`, which keeps only the last attempt's error, this
+    /// keeps every error the loop saw (oldest first) so the message reports
+    /// the full chain instead of just the final symptom.
+    #[error("Retry exhausted after {attempts} attempt(s); errors: {}", format_error_chain(&errors))]
+    RetryExhaustedChain {
+        attempts: u32,
+        errors: Vec<LLMRouterError>,
+    },
+}
+
+/// Render `errors` as a `"; "`-separated list for `RetryExhaustedChain`'s
+/// `Display` impl.
+fn format_error_chain(errors: &[LLMRouterError]) -> String {
+    errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")
 }
 
 impl LLMRouterError {
@@ -128,6 +166,21 @@ impl LLMRouterError {
         }
     }
 
+    /// Create a structured rate-limited error from server-provided metadata
+    pub fn rate_limited(
+        retry_after: Option<std::time::Duration>,
+        limit: Option<u32>,
+        remaining: Option<u32>,
+        reset: Option<u64>,
+    ) -> Self {
+        Self::RateLimited {
+            retry_after,
+            limit,
+            remaining,
+            reset,
+        }
+    }
+
     /// Create an authentication error
     pub fn authentication(message: impl Into<String>) -> Self {
         Self::Authentication {
@@ -218,12 +271,54 @@ impl LLMRouterError {
         }
     }
 
+    /// Wrap the final attempt's error with the number of attempts
+    /// `retry_with_backoff` made before giving up
+    pub fn retry_exhausted(attempts: u32, source: Self) -> Self {
+        Self::RetryExhausted {
+            attempts,
+            source: Box::new(source),
+        }
+    }
+
+    /// The error from the last retry attempt, if this is a `RetryExhausted`
+    pub fn last_attempt_error(&self) -> Option<&LLMRouterError> {
+        match self {
+            Self::RetryExhausted { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+
+    /// Number of attempts made before giving up, if this is a `RetryExhausted`
+    /// or `RetryExhaustedChain`
+    pub fn attempts(&self) -> Option<u32> {
+        match self {
+            Self::RetryExhausted { attempts, .. } => Some(*attempts),
+            Self::RetryExhaustedChain { attempts, .. } => Some(*attempts),
+            _ => None,
+        }
+    }
+
+    /// Wrap every attempt's error (oldest first) with the number of
+    /// attempts `retry_with_config_observed` made before giving up
+    pub fn retry_exhausted_chain(attempts: u32, errors: Vec<LLMRouterError>) -> Self {
+        Self::RetryExhaustedChain { attempts, errors }
+    }
+
+    /// The full error history, oldest first, if this is a `RetryExhaustedChain`
+    pub fn error_chain(&self) -> Option<&[LLMRouterError]> {
+        match self {
+            Self::RetryExhaustedChain { errors, .. } => Some(errors),
+            _ => None,
+        }
+    }
+
     /// Check if error is retryable
     pub fn is_retryable(&self) -> bool {
         match self {
             Self::Network { .. } => true,
             Self::Timeout { .. } => true,
             Self::RateLimit { .. } => true,
+            Self::RateLimited { .. } => true,
             Self::Http { status, .. } => match *status {
                 500..=599 => true, // Server errors are retryable
                 429 => true,       // Rate limit is retryable
@@ -237,6 +332,10 @@ impl LLMRouterError {
                     Some(14) | Some(4) | Some(8) | Some(2) // UNAVAILABLE, DEADLINE_EXCEEDED, RESOURCE_EXHAUSTED, UNKNOWN
                 )
             }
+            // The budget that produced this is already spent; retrying
+            // again would just repeat the same exhaustion.
+            Self::RetryExhausted { .. } => false,
+            Self::RetryExhaustedChain { .. } => false,
             _ => false,
         }
     }
@@ -245,6 +344,7 @@ impl LLMRouterError {
     pub fn retry_delay(&self) -> Option<std::time::Duration> {
         match self {
             Self::RateLimit { retry_after, .. } => *retry_after,
+            Self::RateLimited { retry_after, .. } => *retry_after,
             Self::Timeout { .. } => Some(std::time::Duration::from_secs(2)),
             Self::Network { .. } => Some(std::time::Duration::from_secs(1)),
             _ => None,
@@ -281,6 +381,18 @@ impl From<url::ParseError> for LLMRouterError {
     }
 }
 
+// Conversion from request-validation errors, naming the offending field
+impl From<crate::models::ValidationError> for LLMRouterError {
+    fn from(err: crate::models::ValidationError) -> Self {
+        let field = match &err {
+            crate::models::ValidationError::Empty { field } => Some(field.to_string()),
+            crate::models::ValidationError::OutOfRange { field, .. } => Some(field.to_string()),
+            crate::models::ValidationError::BatchTooLarge { .. } => None,
+        };
+        Self::validation(err.to_string(), field)
+    }
+}
+
 // Conversion from tokio-tungstenite errors
 #[cfg(feature = "websocket")]
 impl From<tokio_tungstenite::tungstenite::Error> for LLMRouterError {
@@ -319,6 +431,19 @@ mod tests {
         assert!(!LLMRouterError::validation("test", None).is_retryable());
     }
 
+    #[test]
+    fn test_rate_limited_error() {
+        let err = LLMRouterError::rate_limited(
+            Some(std::time::Duration::from_secs(5)),
+            Some(100),
+            Some(0),
+            Some(1_700_000_000),
+        );
+
+        assert!(err.is_retryable());
+        assert_eq!(err.retry_delay(), Some(std::time::Duration::from_secs(5)));
+    }
+
     #[test]
     fn test_retry_delay() {
         let rate_limit_err = LLMRouterError::rate_limit(
@@ -330,4 +455,34 @@ mod tests {
             Some(std::time::Duration::from_secs(60))
         );
     }
+
+    #[test]
+    fn test_retry_exhausted_wraps_attempts_and_last_error() {
+        let err = LLMRouterError::retry_exhausted(4, LLMRouterError::timeout("timed out", None));
+
+        assert_eq!(err.attempts(), Some(4));
+        assert!(matches!(err.last_attempt_error(), Some(LLMRouterError::Timeout { .. })));
+        assert!(!err.is_retryable());
+        assert_eq!(err.to_string(), "Retry budget exhausted after 4 attempt(s): Request timeout: timed out");
+    }
+
+    #[test]
+    fn test_retry_exhausted_chain_keeps_every_error() {
+        let err = LLMRouterError::retry_exhausted_chain(
+            3,
+            vec![
+                LLMRouterError::network("first", None::<reqwest::Error>),
+                LLMRouterError::network("second", None::<reqwest::Error>),
+                LLMRouterError::timeout("third", None),
+            ],
+        );
+
+        assert_eq!(err.attempts(), Some(3));
+        assert_eq!(err.error_chain().map(|errors| errors.len()), Some(3));
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "Retry exhausted after 3 attempt(s); errors: Network error: first; Network error: second; Request timeout: third"
+        );
+    }
 }
\ No newline at end of file
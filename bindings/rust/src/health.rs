@@ -0,0 +1,120 @@
+//! Live health tracking for the client's inference path.
+//!
+//! `HealthStatus` alone is just a value; `HealthMonitor` gives it runtime
+//! behavior via a `tokio::sync::watch` channel, so load balancers and
+//! routers can react to Healthy -> Degraded -> Unhealthy transitions as they
+//! happen instead of polling `get_status()`.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::models::HealthStatus;
+
+/// Consecutive failed generations after which `HealthMonitor` flips to
+/// `HealthStatus::Unhealthy`.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Tracks health as a `watch`-broadcast `HealthStatus`, flipping to
+/// `Unhealthy` after `failure_threshold` consecutive failed generations and
+/// back to `Healthy` on the next success.
+pub struct HealthMonitor {
+    tx: watch::Sender<HealthStatus>,
+    consecutive_failures: AtomicU32,
+    failure_threshold: u32,
+}
+
+impl HealthMonitor {
+    /// Create a monitor starting at `HealthStatus::Unknown`, flipping to
+    /// `Unhealthy` after `failure_threshold` consecutive failures
+    pub fn new(failure_threshold: u32) -> Self {
+        let (tx, _rx) = watch::channel(HealthStatus::Unknown);
+        Self {
+            tx,
+            consecutive_failures: AtomicU32::new(0),
+            failure_threshold,
+        }
+    }
+
+    /// Subscribe to live health transitions
+    pub fn subscribe(&self) -> watch::Receiver<HealthStatus> {
+        self.tx.subscribe()
+    }
+
+    /// Current health status
+    pub fn status(&self) -> HealthStatus {
+        self.tx.borrow().clone()
+    }
+
+    /// Whether the current status is `HealthStatus::Healthy`
+    pub fn is_healthy(&self) -> bool {
+        matches!(self.status(), HealthStatus::Healthy)
+    }
+
+    /// Set the status, only sending on the watch channel if it actually
+    /// changed so subscribers aren't woken for no-op updates
+    pub fn set(&self, status: HealthStatus) {
+        if *self.tx.borrow() != status {
+            info!("Health status transition: {:?} -> {:?}", *self.tx.borrow(), status);
+            let _ = self.tx.send(status);
+        }
+    }
+
+    /// Record a successful generation: resets the failure streak and
+    /// recovers to `Healthy`
+    pub fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+        self.set(HealthStatus::Healthy);
+    }
+
+    /// Record a failed generation: `Degraded` until `failure_threshold`
+    /// consecutive failures have been seen, then `Unhealthy`
+    pub fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
+        if failures >= self.failure_threshold {
+            warn!("{} consecutive inference failures, marking Unhealthy", failures);
+            self.set(HealthStatus::Unhealthy);
+        } else {
+            self.set(HealthStatus::Degraded);
+        }
+    }
+}
+
+impl Default for HealthMonitor {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_is_a_noop_on_unchanged_status() {
+        let monitor = HealthMonitor::default();
+        let mut rx = monitor.subscribe();
+        monitor.set(HealthStatus::Unknown);
+        assert!(rx.has_changed().unwrap() == false);
+
+        monitor.set(HealthStatus::Healthy);
+        assert!(rx.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_failure_threshold_flips_to_unhealthy() {
+        let monitor = HealthMonitor::new(2);
+        monitor.record_success();
+        assert!(monitor.is_healthy());
+
+        monitor.record_failure();
+        assert_eq!(monitor.status(), HealthStatus::Degraded);
+
+        monitor.record_failure();
+        assert_eq!(monitor.status(), HealthStatus::Unhealthy);
+
+        monitor.record_success();
+        assert!(monitor.is_healthy());
+    }
+}
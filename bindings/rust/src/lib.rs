@@ -6,13 +6,15 @@
 //! ## Features
 //!
 //! - 🚀 **Async/Await Support** - Built with Tokio for high-performance async I/O
+//! - 🧵 **Blocking Client** - Optional `blocking` feature for sync CLIs, scripts, and FFI hosts
 //! - 🔒 **Type Safety** - Strong typing with comprehensive error handling
 //! - 🌐 **Multiple Protocols** - HTTP REST, gRPC, and WebSocket support
 //! - 🛡️ **Error Handling** - Comprehensive error types with context
 //! - 🔄 **Retry Logic** - Built-in retries with exponential backoff
-//! - 🎛️ **Rate Limiting** - Client-side rate limiting with governor
+//! - 🎛️ **Rate Limiting** - Client-side token-bucket rate limiting
 //! - 📊 **Streaming** - Real-time token streaming with futures
-//! - 🧪 **Testing** - Extensive test suite with mocks
+//! - 🧪 **Testing** - Extensive test suite with mocks, plus an optional `testing`
+//!   feature exposing `MockRouter` for downstream integration tests
 //! - 🔗 **FFI Support** - Node.js bindings for JavaScript integration
 //!
 //! ## Quick Start
@@ -32,25 +34,49 @@
 //! }
 //! ```
 
+pub mod batching;
 pub mod client;
 pub mod config;
+pub mod endpoint_pool;
 pub mod error;
+pub mod health;
 pub mod models;
 pub mod protocols;
+pub mod providers;
 pub mod utils;
 
 #[cfg(feature = "napi-binding")]
 pub mod node_bindings;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 // Re-export main types
+pub use batching::{BatchScheduler, BatchingStats};
 pub use client::Client;
+pub use endpoint_pool::EndpointPool;
+pub use health::HealthMonitor;
 pub use config::RouterConfig;
 pub use error::{LLMRouterError, Result};
 pub use models::{
     InferenceRequest, InferenceResponse, InferenceOptions, LoadModelRequest,
     LoadModelResponse, ModelInfo, StreamingResponse, BatchInferenceRequest,
     BatchInferenceResponse, ChatMessage, HealthStatus, SystemMetrics,
+    ArenaRequest, ArenaResponse, ArenaResult,
+    EmbeddingInput, EmbeddingRequest, EmbeddingResponse, EmbeddingUsage,
+    ToolCall, ToolDefinition,
+    ChatCompletionRequest, ChatCompletionResponse, ChatCompletionChunk,
+    ChatCompletionChunkChoice, ChatCompletionDelta, Choice, Usage,
+    ValidationError, Event, EventCondition, EventType, SubscriptionFilter,
 };
+pub use providers::{NativeProvider, OpenAiCompatibleProvider, Provider};
+pub use config::{EndpointPoolConfig, EndpointSelectionPolicy, JwtAuthConfig, ReconnectConfig, TracingConfig, ValidationConfig};
+pub use protocols::ConnectionState;
+#[cfg(feature = "websocket")]
+pub use protocols::websocket::EventSubscription;
+
+#[cfg(feature = "blocking")]
+pub use protocols::http_blocking::BlockingHttpClient;
 
 #[cfg(feature = "grpc")]
 pub use protocols::grpc::GrpcClient;
@@ -67,4 +93,12 @@ pub fn init_tracing() {
     tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .init();
+}
+
+/// Initialize tracing per `config`, additionally wiring an OTLP exporter and
+/// W3C `traceparent` propagation when `config.otlp_endpoint` is set. Prefer
+/// this over `init_tracing` when running against a router deployment with
+/// distributed tracing enabled.
+pub fn init_tracing_with_config(config: &TracingConfig) -> Result<()> {
+    utils::tracing::init_tracing_with_config(config)
 }
\ No newline at end of file
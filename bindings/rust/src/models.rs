@@ -3,6 +3,23 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+/// Why `InferenceRequest::validate`/`BatchInferenceRequest::validate`
+/// rejected a request, naming the offending field rather than silently
+/// clamping it the way the builder methods do
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ValidationError {
+    /// A required field was empty
+    #[error("{field} must not be empty")]
+    Empty { field: &'static str },
+    /// A field was outside its documented range
+    #[error("{field} must be between {min} and {max}, got {value}")]
+    OutOfRange { field: &'static str, value: f32, min: f32, max: f32 },
+    /// `BatchInferenceRequest::requests` exceeded `ValidationConfig::max_client_batch_size`
+    #[error("batch of {size} requests exceeds max_client_batch_size of {max}")]
+    BatchTooLarge { size: usize, max: usize },
+}
 
 /// Health status enumeration
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -111,6 +128,32 @@ impl InferenceOptions {
         }
         self
     }
+
+    /// Reject sampling parameters outside their documented ranges instead
+    /// of silently clamping them the way `temperature`/`top_p` do
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(value) = self.temperature {
+            if !(0.0..=2.0).contains(&value) {
+                return Err(ValidationError::OutOfRange { field: "temperature", value, min: 0.0, max: 2.0 });
+            }
+        }
+        if let Some(value) = self.top_p {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(ValidationError::OutOfRange { field: "top_p", value, min: 0.0, max: 1.0 });
+            }
+        }
+        if let Some(value) = self.frequency_penalty {
+            if !(-2.0..=2.0).contains(&value) {
+                return Err(ValidationError::OutOfRange { field: "frequency_penalty", value, min: -2.0, max: 2.0 });
+            }
+        }
+        if let Some(value) = self.presence_penalty {
+            if !(-2.0..=2.0).contains(&value) {
+                return Err(ValidationError::OutOfRange { field: "presence_penalty", value, min: -2.0, max: 2.0 });
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Inference metrics
@@ -128,6 +171,26 @@ pub struct InferenceMetrics {
     pub processing_time: Option<u64>,
     /// Queue time in milliseconds
     pub queue_time: Option<u64>,
+    /// Tokens consumed by the prompt
+    pub prompt_tokens: Option<u32>,
+    /// Tokens generated in the completion
+    pub completion_tokens: Option<u32>,
+    /// `prompt_tokens + completion_tokens`
+    pub total_tokens: Option<u32>,
+    /// Estimated cost in USD, set via `InferenceMetrics::with_pricing`
+    pub estimated_cost_usd: Option<f64>,
+}
+
+impl InferenceMetrics {
+    /// Compute `estimated_cost_usd` from per-1K-token prices for prompt vs
+    /// completion tokens, using `prompt_tokens`/`completion_tokens` already
+    /// recorded on this value
+    pub fn with_pricing(mut self, prompt_price_per_1k: f64, completion_price_per_1k: f64) -> Self {
+        let prompt_cost = self.prompt_tokens.unwrap_or(0) as f64 / 1000.0 * prompt_price_per_1k;
+        let completion_cost = self.completion_tokens.unwrap_or(0) as f64 / 1000.0 * completion_price_per_1k;
+        self.estimated_cost_usd = Some(prompt_cost + completion_cost);
+        self
+    }
 }
 
 /// Information about a model
@@ -199,10 +262,44 @@ pub struct SystemMetrics {
     pub load_average: Option<f64>,
 }
 
+/// A tool/function the model may call, advertised via `InferenceRequest::tools`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    /// Tool name, referenced by `ToolCall::name` when the model invokes it
+    pub name: String,
+    /// Human-readable description of what the tool does
+    pub description: String,
+    /// JSON Schema describing the tool's parameters
+    pub parameters: serde_json::Value,
+}
+
+impl ToolDefinition {
+    /// Create a new tool definition
+    pub fn new(name: impl Into<String>, description: impl Into<String>, parameters: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            parameters,
+        }
+    }
+}
+
+/// A single tool invocation requested by the model, carried on
+/// `ChatMessage::tool_calls` / `InferenceResponse::tool_calls`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// Call ID, echoed back via `ChatMessage::tool_call_id` when returning the result
+    pub id: String,
+    /// Name of the tool being called
+    pub name: String,
+    /// Arguments the model wants to call the tool with, as a JSON string
+    pub arguments: String,
+}
+
 /// Chat message
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChatMessage {
-    /// Message role (user, assistant, system)
+    /// Message role (user, assistant, system, tool)
     pub role: String,
     /// Message content
     pub content: String,
@@ -210,6 +307,10 @@ pub struct ChatMessage {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     /// Timestamp
     pub timestamp: Option<DateTime<Utc>>,
+    /// Tool calls the assistant requested in this message
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// For a `role: "tool"` message, the `ToolCall::id` this is the result of
+    pub tool_call_id: Option<String>,
 }
 
 impl ChatMessage {
@@ -220,6 +321,8 @@ impl ChatMessage {
             content: content.into(),
             metadata: None,
             timestamp: Some(Utc::now()),
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
@@ -230,6 +333,8 @@ impl ChatMessage {
             content: content.into(),
             metadata: None,
             timestamp: Some(Utc::now()),
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
@@ -240,8 +345,29 @@ impl ChatMessage {
             content: content.into(),
             metadata: None,
             timestamp: Some(Utc::now()),
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
+
+    /// Create a `role: "tool"` message carrying the result of `tool_call_id`
+    /// back to the model
+    pub fn tool(content: impl Into<String>, tool_call_id: impl Into<String>) -> Self {
+        Self {
+            role: "tool".to_string(),
+            content: content.into(),
+            metadata: None,
+            timestamp: Some(Utc::now()),
+            tool_calls: None,
+            tool_call_id: Some(tool_call_id.into()),
+        }
+    }
+
+    /// Attach tool calls requested by the assistant to this message
+    pub fn tool_calls(mut self, tool_calls: Vec<ToolCall>) -> Self {
+        self.tool_calls = Some(tool_calls);
+        self
+    }
 }
 
 /// Request for inference
@@ -257,6 +383,10 @@ pub struct InferenceRequest {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     /// Session ID for tracking
     pub session_id: Option<String>,
+    /// Tools the model may call
+    pub tools: Option<Vec<ToolDefinition>>,
+    /// Tool-calling policy: "auto", "none", or a specific tool name
+    pub tool_choice: Option<String>,
 }
 
 impl InferenceRequest {
@@ -268,6 +398,8 @@ impl InferenceRequest {
             options: None,
             metadata: None,
             session_id: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -289,6 +421,18 @@ impl InferenceRequest {
         self
     }
 
+    /// Set the tools the model may call
+    pub fn tools(mut self, tools: Vec<ToolDefinition>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    /// Set the tool-calling policy ("auto", "none", or a specific tool name)
+    pub fn tool_choice(mut self, tool_choice: impl Into<String>) -> Self {
+        self.tool_choice = Some(tool_choice.into());
+        self
+    }
+
     /// Add metadata
     pub fn metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
         if let Some(ref mut metadata) = self.metadata {
@@ -300,6 +444,18 @@ impl InferenceRequest {
         }
         self
     }
+
+    /// Reject an empty prompt or out-of-range `options`, returning a
+    /// descriptive error instead of letting a bad request reach a backend
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.prompt.trim().is_empty() {
+            return Err(ValidationError::Empty { field: "prompt" });
+        }
+        if let Some(ref options) = self.options {
+            options.validate()?;
+        }
+        Ok(())
+    }
 }
 
 /// Response from inference
@@ -317,6 +473,10 @@ pub struct InferenceResponse {
     pub error: Option<String>,
     /// Additional metadata
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Tool calls the model requested instead of (or alongside) final text
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// Why generation stopped: "stop", "length", "tool_calls", etc.
+    pub finish_reason: Option<String>,
 }
 
 /// Streaming response chunk
@@ -415,6 +575,13 @@ pub struct BatchInferenceRequest {
     pub fail_fast: bool,
     /// Request priority
     pub priority: Option<String>,
+    /// Token budget for continuous batching via `BatchScheduler`: the most
+    /// estimated tokens (prompt length plus `max_tokens`) a single formed
+    /// batch may contain
+    pub max_batch_total_tokens: Option<u32>,
+    /// Fraction of the token budget that must already be queued before
+    /// `BatchScheduler` flushes a batch instead of waiting for more requests
+    pub waiting_served_ratio: Option<f32>,
 }
 
 impl BatchInferenceRequest {
@@ -426,6 +593,8 @@ impl BatchInferenceRequest {
             timeout_ms: Some(30000),
             fail_fast: false,
             priority: None,
+            max_batch_total_tokens: None,
+            waiting_served_ratio: None,
         }
     }
 
@@ -446,6 +615,34 @@ impl BatchInferenceRequest {
         self.fail_fast = fail_fast;
         self
     }
+
+    /// Set the token budget used by `BatchScheduler`'s continuous batching
+    pub fn max_batch_total_tokens(mut self, max_batch_total_tokens: u32) -> Self {
+        self.max_batch_total_tokens = Some(max_batch_total_tokens);
+        self
+    }
+
+    /// Set the fraction of the token budget that must be queued before
+    /// `BatchScheduler` flushes instead of waiting for more requests
+    pub fn waiting_served_ratio(mut self, waiting_served_ratio: f32) -> Self {
+        self.waiting_served_ratio = Some(waiting_served_ratio);
+        self
+    }
+
+    /// Reject a batch larger than `config.max_client_batch_size` or
+    /// containing any individually invalid request
+    pub fn validate(&self, config: &crate::config::ValidationConfig) -> Result<(), ValidationError> {
+        if self.requests.len() > config.max_client_batch_size {
+            return Err(ValidationError::BatchTooLarge {
+                size: self.requests.len(),
+                max: config.max_client_batch_size,
+            });
+        }
+        for request in &self.requests {
+            request.validate()?;
+        }
+        Ok(())
+    }
 }
 
 /// Response from batch inference
@@ -467,6 +664,449 @@ pub struct BatchInferenceResponse {
     pub success: bool,
     /// Error message if any
     pub error: Option<String>,
+    /// Number of continuous-batching batches `BatchScheduler` formed to
+    /// serve this request, if it went through continuous batching
+    pub batches_formed: Option<u32>,
+    /// Average `batch_tokens / max_batch_total_tokens` across those batches
+    pub average_fill: Option<f64>,
+    /// Token usage summed across every response's `metrics`, via
+    /// `BatchInferenceResponse::compute_usage`
+    pub usage: Option<Usage>,
+}
+
+impl BatchInferenceResponse {
+    /// Sum `prompt_tokens`/`completion_tokens` across every response's
+    /// metrics into one rollup `Usage`
+    pub fn compute_usage(&self) -> Usage {
+        let mut usage = Usage::default();
+        for response in &self.responses {
+            let Some(metrics) = &response.metrics else { continue };
+            let prompt_tokens = metrics.prompt_tokens.unwrap_or(0);
+            let completion_tokens = metrics.completion_tokens.unwrap_or(0);
+            usage.prompt_tokens += prompt_tokens;
+            usage.completion_tokens += completion_tokens;
+            usage.total_tokens += metrics.total_tokens.unwrap_or(prompt_tokens + completion_tokens);
+        }
+        usage
+    }
+}
+
+/// Request to run one prompt across several models for side-by-side
+/// comparison, via `Client::arena`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArenaRequest {
+    /// Prompt to run against every model
+    pub prompt: String,
+    /// Model IDs to compare
+    pub model_ids: Vec<String>,
+    /// Inference options applied to every model
+    pub options: Option<InferenceOptions>,
+    /// Maximum concurrent requests across models
+    pub max_concurrent: Option<u32>,
+}
+
+impl ArenaRequest {
+    /// Create a new arena request
+    pub fn new(prompt: impl Into<String>, model_ids: Vec<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            model_ids,
+            options: None,
+            max_concurrent: Some(5),
+        }
+    }
+
+    /// Set options
+    pub fn options(mut self, options: InferenceOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Set maximum concurrent requests
+    pub fn max_concurrent(mut self, max_concurrent: u32) -> Self {
+        self.max_concurrent = Some(max_concurrent);
+        self
+    }
+}
+
+/// One model's result within an `ArenaResponse`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArenaResult {
+    /// Model ID this result is for
+    pub model_id: String,
+    /// The model's inference response; `success: false` here means this
+    /// model failed without aborting the rest of the arena
+    pub response: InferenceResponse,
+    /// Latency for this model's response, in milliseconds
+    pub latency_ms: Option<u64>,
+    /// Tokens generated by this model
+    pub tokens_generated: Option<u32>,
+}
+
+/// Response from `Client::arena`: one `ArenaResult` per requested model, in
+/// the same order as `ArenaRequest::model_ids`, so callers can A/B compare
+/// quality and speed across models.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArenaResponse {
+    /// Per-model results, in request order
+    pub results: Vec<ArenaResult>,
+    /// Total wall-clock time for the arena run, in milliseconds
+    pub total_time_ms: Option<u64>,
+}
+
+/// Input text for `EmbeddingRequest`: a single string or a batch, mirroring
+/// how most embedding APIs accept either shape under one field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EmbeddingInput {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+/// Request to embed one or more strings into vectors, via `Client::embeddings`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingRequest {
+    /// Text to embed; a single string or a batch
+    pub input: EmbeddingInput,
+    /// Model to embed with; router default if omitted
+    pub model_id: Option<String>,
+}
+
+impl EmbeddingRequest {
+    /// Create a request to embed a single string
+    pub fn new(input: impl Into<String>) -> Self {
+        Self {
+            input: EmbeddingInput::Single(input.into()),
+            model_id: None,
+        }
+    }
+
+    /// Create a request to embed a batch of strings
+    pub fn batch(inputs: Vec<String>) -> Self {
+        Self {
+            input: EmbeddingInput::Batch(inputs),
+            model_id: None,
+        }
+    }
+
+    /// Set the model to embed with
+    pub fn model_id(mut self, model_id: impl Into<String>) -> Self {
+        self.model_id = Some(model_id.into());
+        self
+    }
+}
+
+/// Token usage reported alongside an `EmbeddingResponse`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct EmbeddingUsage {
+    /// Tokens consumed by the input text
+    pub prompt_tokens: u32,
+    /// Total tokens billed for the request
+    pub total_tokens: u32,
+}
+
+/// Response from `Client::embeddings`: one vector per input, in request order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingResponse {
+    /// Embedding vectors, one per input, in request order
+    pub vectors: Vec<Vec<f32>>,
+    /// Model that produced the embeddings
+    pub model: String,
+    /// Token usage for the request
+    pub usage: Option<EmbeddingUsage>,
+}
+
+/// Token usage reported alongside a `ChatCompletionResponse`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct Usage {
+    /// Tokens consumed by the prompt/messages
+    pub prompt_tokens: u32,
+    /// Tokens generated in the completion
+    pub completion_tokens: u32,
+    /// `prompt_tokens + completion_tokens`
+    pub total_tokens: u32,
+}
+
+/// One completion choice within a `ChatCompletionResponse`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Choice {
+    /// Index of this choice among `ChatCompletionResponse::choices`
+    pub index: u32,
+    /// The generated message
+    pub message: ChatMessage,
+    /// Why generation stopped: "stop", "length", "tool_calls", etc.
+    pub finish_reason: Option<String>,
+}
+
+/// OpenAI-compatible `/v1/chat/completions` request, for code that wants to
+/// speak the wire format directly rather than go through `Client::chat_completion`'s
+/// native dispatch. Converts to/from `InferenceRequest` via `From`/`Into`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+}
+
+impl From<InferenceRequest> for ChatCompletionRequest {
+    /// Flattens `InferenceOptions` onto the OpenAI request shape; the native
+    /// router's single `prompt` becomes one `user` message.
+    fn from(request: InferenceRequest) -> Self {
+        let options = request.options.unwrap_or_default();
+        Self {
+            model: request.model_id.unwrap_or_default(),
+            messages: vec![ChatMessage::user(request.prompt)],
+            temperature: options.temperature,
+            top_p: options.top_p,
+            max_tokens: options.max_tokens,
+            stop: options.stop_sequences,
+            stream: options.stream,
+            seed: options.seed,
+            frequency_penalty: options.frequency_penalty,
+            presence_penalty: options.presence_penalty,
+        }
+    }
+}
+
+impl From<ChatCompletionRequest> for InferenceRequest {
+    /// Collapses `messages` down to the last message's content as `prompt`,
+    /// since the native router has no concept of a message history.
+    fn from(request: ChatCompletionRequest) -> Self {
+        let prompt = request.messages.last().map(|msg| msg.content.clone()).unwrap_or_default();
+
+        let mut options = InferenceOptions::new();
+        options.temperature = request.temperature;
+        options.top_p = request.top_p;
+        options.max_tokens = request.max_tokens;
+        options.stop_sequences = request.stop;
+        options.stream = request.stream;
+        options.seed = request.seed;
+        options.frequency_penalty = request.frequency_penalty;
+        options.presence_penalty = request.presence_penalty;
+
+        let mut inference_request = InferenceRequest::new(prompt).options(options);
+        if !request.model.is_empty() {
+            inference_request = inference_request.model_id(request.model);
+        }
+        inference_request
+    }
+}
+
+/// OpenAI-compatible `/v1/chat/completions` response. Converts to/from
+/// `InferenceResponse` via `From`/`Into`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatCompletionResponse {
+    pub id: String,
+    pub object: String,
+    pub created: u64,
+    pub model: String,
+    pub system_fingerprint: Option<String>,
+    pub choices: Vec<Choice>,
+    pub usage: Usage,
+}
+
+impl From<ChatCompletionResponse> for InferenceResponse {
+    fn from(response: ChatCompletionResponse) -> Self {
+        let choice = response.choices.into_iter().next();
+
+        Self {
+            text: choice.as_ref().map(|c| c.message.content.clone()).unwrap_or_default(),
+            model_id: Some(response.model),
+            metrics: Some(InferenceMetrics {
+                latency_ms: None,
+                tokens_generated: Some(response.usage.completion_tokens),
+                tokens_per_second: None,
+                memory_used: None,
+                processing_time: None,
+                queue_time: None,
+                prompt_tokens: Some(response.usage.prompt_tokens),
+                completion_tokens: Some(response.usage.completion_tokens),
+                total_tokens: Some(response.usage.total_tokens),
+                estimated_cost_usd: None,
+            }),
+            success: true,
+            error: None,
+            metadata: None,
+            tool_calls: choice.as_ref().and_then(|c| c.message.tool_calls.clone()),
+            finish_reason: choice.and_then(|c| c.finish_reason),
+        }
+    }
+}
+
+impl From<InferenceResponse> for ChatCompletionResponse {
+    /// The native router has no request ID or fingerprint of its own, so
+    /// `id`/`system_fingerprint` are left empty and `created` is stamped at
+    /// conversion time.
+    fn from(response: InferenceResponse) -> Self {
+        let tokens = response.metrics.as_ref().and_then(|m| m.tokens_generated).unwrap_or(0);
+
+        Self {
+            id: String::new(),
+            object: "chat.completion".to_string(),
+            created: Utc::now().timestamp().max(0) as u64,
+            model: response.model_id.unwrap_or_default(),
+            system_fingerprint: None,
+            choices: vec![Choice {
+                index: 0,
+                message: ChatMessage {
+                    role: "assistant".to_string(),
+                    content: response.text,
+                    metadata: None,
+                    timestamp: None,
+                    tool_calls: response.tool_calls,
+                    tool_call_id: None,
+                },
+                finish_reason: response.finish_reason,
+            }],
+            usage: Usage {
+                prompt_tokens: 0,
+                completion_tokens: tokens,
+                total_tokens: tokens,
+            },
+        }
+    }
+}
+
+/// Delta content within one `ChatCompletionChunkChoice` of a streamed response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ChatCompletionDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// One choice within a `ChatCompletionChunk`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatCompletionChunkChoice {
+    pub index: u32,
+    pub delta: ChatCompletionDelta,
+    pub finish_reason: Option<String>,
+}
+
+/// Streaming variant of `ChatCompletionResponse`, one per SSE `data: ` event
+/// from a `/v1/chat/completions` request with `stream: true`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    pub id: String,
+    pub object: String,
+    pub created: u64,
+    pub model: String,
+    pub choices: Vec<ChatCompletionChunkChoice>,
+}
+
+impl From<ChatCompletionChunk> for StreamingResponse {
+    fn from(chunk: ChatCompletionChunk) -> Self {
+        let choice = chunk.choices.into_iter().next();
+
+        Self {
+            token: choice.as_ref().and_then(|c| c.delta.content.clone()).unwrap_or_default(),
+            is_complete: choice.as_ref().map(|c| c.finish_reason.is_some()).unwrap_or(false),
+            model_id: Some(chunk.model),
+            metrics: None,
+            error: None,
+        }
+    }
+}
+
+impl From<StreamingResponse> for ChatCompletionChunk {
+    fn from(response: StreamingResponse) -> Self {
+        Self {
+            id: String::new(),
+            object: "chat.completion.chunk".to_string(),
+            created: Utc::now().timestamp().max(0) as u64,
+            model: response.model_id.unwrap_or_default(),
+            choices: vec![ChatCompletionChunkChoice {
+                index: 0,
+                delta: ChatCompletionDelta {
+                    role: None,
+                    content: Some(response.token),
+                },
+                finish_reason: if response.is_complete { Some("stop".to_string()) } else { None },
+            }],
+        }
+    }
+}
+
+/// Server-side event kinds a `SubscriptionFilter` can match against, pushed
+/// to `Client::subscribe` streams in place of polling `get_status()`/
+/// `get_metrics()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    ModelLoad,
+    ModelUnload,
+    HealthTransition,
+    MetricUpdate,
+}
+
+/// One server-pushed event delivered to a `Client::subscribe` stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub event_type: EventType,
+    pub model_id: Option<String>,
+    pub latency_ms: Option<f64>,
+    pub timestamp: DateTime<Utc>,
+    /// Event-specific data the server attaches (e.g. updated `ModelMetrics`)
+    pub payload: Option<serde_json::Value>,
+}
+
+/// One condition a `SubscriptionFilter` asks the server to evaluate against
+/// every `Event` before pushing it down a subscription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "field", rename_all = "snake_case")]
+pub enum EventCondition {
+    EventType { eq: EventType },
+    ModelId { eq: String },
+    LatencyMs { gt: f64 },
+}
+
+/// Set of conditions `Client::subscribe` asks the server to evaluate against
+/// every `Event` — an AND of all entries, evaluated server-side so only
+/// matching events are pushed down the subscription
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubscriptionFilter {
+    pub conditions: Vec<EventCondition>,
+}
+
+impl SubscriptionFilter {
+    /// Start with no conditions; `Client::subscribe` pushes every event
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only deliver events of the given `event_type`
+    pub fn event_type(mut self, event_type: EventType) -> Self {
+        self.conditions.push(EventCondition::EventType { eq: event_type });
+        self
+    }
+
+    /// Only deliver events for the given `model_id`
+    pub fn model_id(mut self, model_id: impl Into<String>) -> Self {
+        self.conditions.push(EventCondition::ModelId { eq: model_id.into() });
+        self
+    }
+
+    /// Only deliver events whose `latency_ms` exceeds `threshold_ms`
+    pub fn latency_over(mut self, threshold_ms: f64) -> Self {
+        self.conditions.push(EventCondition::LatencyMs { gt: threshold_ms });
+        self
+    }
 }
 
 #[cfg(test)]
@@ -537,4 +1177,175 @@ mod tests {
         assert_eq!(batch.timeout_ms, Some(60000));
         assert_eq!(batch.fail_fast, true);
     }
+
+    #[test]
+    fn test_tool_calling_round_trip() {
+        let tool = ToolDefinition::new(
+            "get_weather",
+            "Look up the current weather for a city",
+            serde_json::json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+        );
+
+        let request = InferenceRequest::new("What's the weather in Paris?")
+            .tools(vec![tool])
+            .tool_choice("auto");
+
+        assert_eq!(request.tools.as_ref().unwrap().len(), 1);
+        assert_eq!(request.tool_choice, Some("auto".to_string()));
+
+        let call = ToolCall {
+            id: "call_1".to_string(),
+            name: "get_weather".to_string(),
+            arguments: "{\"city\": \"Paris\"}".to_string(),
+        };
+
+        let assistant_message = ChatMessage::assistant("").tool_calls(vec![call.clone()]);
+        assert_eq!(assistant_message.tool_calls, Some(vec![call]));
+
+        let tool_result = ChatMessage::tool("18C and sunny", "call_1");
+        assert_eq!(tool_result.role, "tool");
+        assert_eq!(tool_result.tool_call_id, Some("call_1".to_string()));
+    }
+
+    #[test]
+    fn test_inference_request_chat_completion_round_trip() {
+        let request = InferenceRequest::new("Hello there")
+            .model_id("llama-3")
+            .options(InferenceOptions::new().temperature(0.5).max_tokens(64));
+
+        let chat_request: ChatCompletionRequest = request.clone().into();
+        assert_eq!(chat_request.model, "llama-3");
+        assert_eq!(chat_request.messages.len(), 1);
+        assert_eq!(chat_request.messages[0].content, "Hello there");
+        assert_eq!(chat_request.temperature, Some(0.5));
+        assert_eq!(chat_request.max_tokens, Some(64));
+
+        let round_tripped: InferenceRequest = chat_request.into();
+        assert_eq!(round_tripped.prompt, request.prompt);
+        assert_eq!(round_tripped.model_id, request.model_id);
+    }
+
+    #[test]
+    fn test_inference_request_validate_rejects_empty_prompt() {
+        let request = InferenceRequest::new("   ");
+        assert!(matches!(request.validate(), Err(ValidationError::Empty { field: "prompt" })));
+    }
+
+    #[test]
+    fn test_inference_request_validate_rejects_out_of_range_temperature() {
+        let mut request = InferenceRequest::new("Hello");
+        request.options = Some(InferenceOptions { temperature: Some(5.0), ..InferenceOptions::new() });
+        assert!(matches!(request.validate(), Err(ValidationError::OutOfRange { field: "temperature", .. })));
+    }
+
+    #[test]
+    fn test_batch_inference_request_validate_enforces_max_client_batch_size() {
+        let requests = vec![InferenceRequest::new("a"), InferenceRequest::new("b")];
+        let batch = BatchInferenceRequest::new(requests);
+        let config = crate::config::ValidationConfig { max_client_batch_size: 1 };
+
+        assert!(matches!(batch.validate(&config), Err(ValidationError::BatchTooLarge { size: 2, max: 1 })));
+    }
+
+    #[test]
+    fn test_inference_metrics_with_pricing() {
+        let metrics = InferenceMetrics {
+            latency_ms: None,
+            tokens_generated: None,
+            tokens_per_second: None,
+            memory_used: None,
+            processing_time: None,
+            queue_time: None,
+            prompt_tokens: Some(1000),
+            completion_tokens: Some(500),
+            total_tokens: Some(1500),
+            estimated_cost_usd: None,
+        }
+        .with_pricing(0.01, 0.03);
+
+        assert_eq!(metrics.estimated_cost_usd, Some(0.01 + 0.015));
+    }
+
+    #[test]
+    fn test_batch_inference_response_compute_usage() {
+        let response = InferenceResponse {
+            text: "hi".to_string(),
+            model_id: None,
+            metrics: Some(InferenceMetrics {
+                latency_ms: None,
+                tokens_generated: None,
+                tokens_per_second: None,
+                memory_used: None,
+                processing_time: None,
+                queue_time: None,
+                prompt_tokens: Some(10),
+                completion_tokens: Some(5),
+                total_tokens: Some(15),
+                estimated_cost_usd: None,
+            }),
+            success: true,
+            error: None,
+            metadata: None,
+            tool_calls: None,
+            finish_reason: None,
+        };
+
+        let batch = BatchInferenceResponse {
+            responses: vec![response.clone(), response],
+            total_requests: 2,
+            successful_requests: 2,
+            failed_requests: 0,
+            total_time_ms: None,
+            average_latency_ms: None,
+            success: true,
+            error: None,
+            batches_formed: None,
+            average_fill: None,
+            usage: None,
+        };
+
+        let usage = batch.compute_usage();
+        assert_eq!(usage.prompt_tokens, 20);
+        assert_eq!(usage.completion_tokens, 10);
+        assert_eq!(usage.total_tokens, 30);
+    }
+
+    #[test]
+    fn test_chat_completion_response_to_inference_response() {
+        let chat_response = ChatCompletionResponse {
+            id: "chatcmpl-1".to_string(),
+            object: "chat.completion".to_string(),
+            created: 0,
+            model: "llama-3".to_string(),
+            system_fingerprint: None,
+            choices: vec![Choice {
+                index: 0,
+                message: ChatMessage::assistant("Hi!"),
+                finish_reason: Some("stop".to_string()),
+            }],
+            usage: Usage { prompt_tokens: 5, completion_tokens: 2, total_tokens: 7 },
+        };
+
+        let inference_response: InferenceResponse = chat_response.into();
+        assert_eq!(inference_response.text, "Hi!");
+        assert_eq!(inference_response.finish_reason, Some("stop".to_string()));
+        assert_eq!(inference_response.metrics.unwrap().tokens_generated, Some(2));
+    }
+
+    #[test]
+    fn test_subscription_filter_builder() {
+        let filter = SubscriptionFilter::new()
+            .event_type(EventType::ModelLoad)
+            .model_id("llama-3")
+            .latency_over(100.0);
+
+        assert_eq!(
+            filter.conditions,
+            vec![
+                EventCondition::EventType { eq: EventType::ModelLoad },
+                EventCondition::ModelId { eq: "llama-3".to_string() },
+                EventCondition::LatencyMs { gt: 100.0 },
+            ]
+        );
+    }
 }
\ No newline at end of file
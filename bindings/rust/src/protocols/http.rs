@@ -1,25 +1,79 @@
 //! HTTP client implementation for LLM Router
 
 use crate::{
-    config::RouterConfig,
+    config::{RequestConfig, ResolvedRequestConfig, RouterConfig},
     error::{LLMRouterError, Result},
     models::*,
-    utils::retry::retry_with_backoff,
+    utils::{jwt::JwtTokenMinter, rate_limit::RateLimiter, retry::retry_with_backoff, tracing::inject_traceparent},
 };
 
+use flate2::{write::GzEncoder, Compression};
 use futures::{Stream, TryStreamExt};
-use reqwest::{Client as ReqwestClient, ClientBuilder, Response};
+use reqwest::{multipart, Client as ReqwestClient, ClientBuilder, Response};
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, fmt, io::Write, path::Path, sync::Arc, time::Duration};
+use tokio::fs::File;
+use tokio::sync::RwLock;
 use tokio::time::timeout;
-use tracing::{debug, error, info, warn};
+use tokio_util::io::ReaderStream;
+use tracing::{debug, error, info, instrument, warn};
+use url::Url;
+
+/// Header names that are always masked in `HttpClient`'s `Debug` output,
+/// regardless of `RouterConfig::sensitive_headers`.
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "x-api-key", "api-key"];
+
+/// Timeout for `upload_model`, mirroring `batch_inference`'s longer-than-default
+/// allowance since model artifacts can be large.
+const UPLOAD_TIMEOUT_MS: u64 = 120_000;
+
+/// Generate a fresh idempotency key for a non-idempotent request. Called
+/// once per logical request, before retries begin, so every attempt of
+/// the same request carries the same key.
+fn generate_idempotency_key() -> String {
+    format!("{:016x}{:016x}", fastrand::u64(..), fastrand::u64(..))
+}
+
+/// Snapshot of the most recently observed `X-RateLimit-*` response headers,
+/// returned by `HttpClient::rate_limit_status()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimitStatus {
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+    pub reset: Option<u64>,
+}
 
 /// HTTP client for LLM Router REST API
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct HttpClient {
     client: ReqwestClient,
     config: Arc<RouterConfig>,
     base_url: String,
+    rate_limit_status: Arc<RwLock<Option<RateLimitStatus>>>,
+    rate_limiter: Arc<RateLimiter>,
+    jwt_minter: Option<Arc<JwtTokenMinter>>,
+}
+
+impl fmt::Debug for HttpClient {
+    /// Renders `config.get_auth_headers()` with sensitive values masked, so
+    /// logging a client (or a struct containing one) at debug level can't
+    /// leak API keys or bearer tokens.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let headers: Vec<(String, String)> = self.config.get_auth_headers()
+            .into_iter()
+            .map(|(name, value)| {
+                let is_sensitive = SENSITIVE_HEADERS.iter().any(|h| name.eq_ignore_ascii_case(h))
+                    || self.config.sensitive_headers.iter().any(|h| name.eq_ignore_ascii_case(h));
+                let value = if is_sensitive { "<masked>".to_string() } else { value };
+                (name, value)
+            })
+            .collect();
+
+        f.debug_struct("HttpClient")
+            .field("base_url", &self.base_url)
+            .field("headers", &headers)
+            .finish()
+    }
 }
 
 impl HttpClient {
@@ -49,11 +103,24 @@ impl HttpClient {
             .pool_idle_timeout(Some(config.connection_pool.idle_timeout))
             .pool_max_idle_per_host(config.connection_pool.max_idle_connections);
 
-        // Configure TLS
-        if !config.tls.verify_ssl {
-            client_builder = client_builder.danger_accept_invalid_certs(true);
+        // Configure TLS via a real rustls client built from `TlsConfig`, so
+        // `ca_cert_path`/mutual-TLS settings are honored (not just the
+        // verify_ssl toggle that `danger_accept_invalid_certs` covers alone).
+        let tls_config = config.tls.build_client_config()?;
+        client_builder = client_builder.use_preconfigured_tls(tls_config);
+
+        // Pin overridden hosts to their configured addresses instead of
+        // resolving them through system DNS.
+        for override_entry in &config.connect_to {
+            client_builder = client_builder.resolve_to_addrs(&override_entry.host, &override_entry.addrs);
         }
 
+        // Negotiate response compression; reqwest advertises the matching
+        // `Accept-Encoding` and decodes the body transparently.
+        client_builder = client_builder
+            .gzip(config.compression.decode_gzip)
+            .brotli(config.compression.decode_brotli);
+
         let client = client_builder
             .build()
             .map_err(|e| LLMRouterError::configuration(format!("Failed to create HTTP client: {}", e)))?;
@@ -62,61 +129,235 @@ impl HttpClient {
 
         info!("HTTP client initialized for: {}", base_url);
 
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+
+        let jwt_minter = config.jwt_auth.clone().map(|jwt_config| Arc::new(JwtTokenMinter::new(jwt_config)));
+
         Ok(Self {
             client,
             config,
             base_url,
+            rate_limit_status: Arc::new(RwLock::new(None)),
+            rate_limiter,
+            jwt_minter,
         })
     }
 
-    /// Make a GET request with retry logic
+    /// Mint (or reuse a cached) JWT bearer token and attach it to `headers`
+    /// as `Authorization: Bearer <token>`, when `RouterConfig::jwt_auth` is
+    /// set. A no-op otherwise, leaving the static `api_key` header (already
+    /// baked into the client's default headers) in place.
+    async fn apply_jwt_auth(&self, headers: &mut reqwest::header::HeaderMap) -> Result<()> {
+        if let Some(ref minter) = self.jwt_minter {
+            let token = minter.bearer_token().await?;
+            let value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|e| LLMRouterError::authentication(format!("Invalid JWT header value: {}", e)))?;
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+
+        Ok(())
+    }
+
+    /// The host to key the rate limiter's token bucket by, derived from
+    /// `base_url`. Falls back to the full base URL if it doesn't parse.
+    fn rate_limit_host(&self) -> String {
+        Url::parse(&self.base_url)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_string()))
+            .unwrap_or_else(|| self.base_url.clone())
+    }
+
+    /// Make a GET request with retry logic, under the global default
+    /// timeout/retry policy
     async fn get(&self, endpoint: &str, params: Option<&HashMap<String, String>>) -> Result<Value> {
+        self.get_with_config(endpoint, params, &self.config.default_request_config()).await
+    }
+
+    /// Make a GET request with retry logic, under a per-request policy
+    async fn get_with_config(
+        &self,
+        endpoint: &str,
+        params: Option<&HashMap<String, String>>,
+        request_config: &ResolvedRequestConfig,
+    ) -> Result<Value> {
         let url = format!("{}/api/v1/{}", self.base_url, endpoint.trim_start_matches('/'));
-        
-        retry_with_backoff(
+        let host = self.rate_limit_host();
+
+        let attempt = retry_with_backoff(
             || async {
+                self.rate_limiter.acquire(&host).await?;
+
                 let mut request = self.client.get(&url);
-                
+
                 if let Some(params) = params {
                     request = request.query(params);
                 }
-                
+
+                let mut traceparent = reqwest::header::HeaderMap::new();
+                inject_traceparent(&mut traceparent);
+                self.apply_jwt_auth(&mut traceparent).await?;
+                request = request.headers(traceparent);
+
                 let response = request.send().await?;
                 self.handle_response(response).await
             },
-            self.config.max_retries,
-            self.config.retry_delay,
-        ).await
+            request_config.max_retries,
+            request_config.retry_delay,
+            request_config.max_backoff,
+            request_config.multiplier,
+        );
+
+        timeout(request_config.timeout, attempt)
+            .await
+            .map_err(|_| LLMRouterError::timeout("Request timed out", Some(request_config.timeout)))?
     }
 
-    /// Make a POST request with retry logic
+    /// Make a POST request with retry logic, under the global default
+    /// timeout/retry policy
     async fn post(&self, endpoint: &str, body: &Value) -> Result<Value> {
+        self.post_with_config(endpoint, body, &self.config.default_request_config(), None).await
+    }
+
+    /// Make a POST request for a non-idempotent operation (one with a
+    /// server-side side effect that shouldn't fire twice), retried under
+    /// the global default policy with a single `Idempotency-Key` generated
+    /// once by the caller and resent unchanged on every attempt, so the
+    /// server can recognize and drop a duplicate caused by a retried
+    /// request whose first attempt actually succeeded.
+    async fn post_idempotent(&self, endpoint: &str, body: &Value, idempotency_key: &str) -> Result<Value> {
+        self.post_with_config(endpoint, body, &self.config.default_request_config(), Some(idempotency_key))
+            .await
+    }
+
+    /// Make a POST request with retry logic, under a per-request policy.
+    /// `idempotency_key`, when set, is sent as `Idempotency-Key` on every
+    /// attempt unchanged so the server can dedupe retried side effects.
+    async fn post_with_config(
+        &self,
+        endpoint: &str,
+        body: &Value,
+        request_config: &ResolvedRequestConfig,
+        idempotency_key: Option<&str>,
+    ) -> Result<Value> {
         let url = format!("{}/api/v1/{}", self.base_url, endpoint.trim_start_matches('/'));
-        
-        retry_with_backoff(
+        let json_bytes = serde_json::to_vec(body)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize request body", Some(e)))?;
+        let compressed = self.maybe_compress_body(&json_bytes)?;
+        let host = self.rate_limit_host();
+
+        let attempt = retry_with_backoff(
             || async {
-                let response = self.client
+                self.rate_limiter.acquire(&host).await?;
+
+                let mut request = self.client
                     .post(&url)
-                    .json(body)
-                    .send()
-                    .await?;
+                    .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+                let mut traceparent = reqwest::header::HeaderMap::new();
+                inject_traceparent(&mut traceparent);
+                self.apply_jwt_auth(&mut traceparent).await?;
+                request = request.headers(traceparent);
+
+                if let Some(key) = idempotency_key {
+                    request = request.header("Idempotency-Key", key);
+                }
+
+                request = match &compressed {
+                    Some(gzipped) => request
+                        .header(reqwest::header::CONTENT_ENCODING, "gzip")
+                        .body(gzipped.clone()),
+                    None => request.body(json_bytes.clone()),
+                };
+
+                let response = request.send().await?;
                 self.handle_response(response).await
             },
-            self.config.max_retries,
-            self.config.retry_delay,
-        ).await
+            request_config.max_retries,
+            request_config.retry_delay,
+            request_config.max_backoff,
+            request_config.multiplier,
+        );
+
+        timeout(request_config.timeout, attempt)
+            .await
+            .map_err(|_| LLMRouterError::timeout("Request timed out", Some(request_config.timeout)))?
+    }
+
+    /// Gzip-compress `body` when `compression.compress_requests` is enabled
+    /// and it's at least `request_compression_threshold_bytes` long.
+    fn maybe_compress_body(&self, body: &[u8]) -> Result<Option<Vec<u8>>> {
+        if !self.config.compression.compress_requests
+            || body.len() < self.config.compression.request_compression_threshold_bytes
+        {
+            return Ok(None);
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body)
+            .map_err(|e| LLMRouterError::serialization("Failed to gzip-compress request body", Some(e)))?;
+        let gzipped = encoder.finish()
+            .map_err(|e| LLMRouterError::serialization("Failed to finalize gzip-compressed body", Some(e)))?;
+
+        Ok(Some(gzipped))
+    }
+
+    /// Parse the `Retry-After` header, which servers may send as either a
+    /// number of seconds or an HTTP-date.
+    fn parse_retry_after(response: &Response) -> Option<Duration> {
+        let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let target = httpdate::parse_http_date(value).ok()?;
+        target.duration_since(std::time::SystemTime::now()).ok()
+    }
+
+    /// Parse the conventional `X-RateLimit-Limit` / `X-RateLimit-Remaining` /
+    /// `X-RateLimit-Reset` trio, if the server sent any of them.
+    fn parse_rate_limit_headers(response: &Response) -> RateLimitStatus {
+        let header_value = |name: &str| {
+            response.headers().get(name).and_then(|v| v.to_str().ok())
+        };
+
+        RateLimitStatus {
+            limit: header_value("x-ratelimit-limit").and_then(|v| v.parse().ok()),
+            remaining: header_value("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+            reset: header_value("x-ratelimit-reset").and_then(|v| v.parse().ok()),
+        }
+    }
+
+    /// Snapshot of the most recently observed `X-RateLimit-*` response
+    /// headers, or `None` if the server has never sent any.
+    pub async fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        *self.rate_limit_status.read().await
     }
 
     /// Handle HTTP response
     async fn handle_response(&self, response: Response) -> Result<Value> {
         let status = response.status();
-        
+        let retry_after = Self::parse_retry_after(&response);
+        let rate_limit = Self::parse_rate_limit_headers(&response);
+
+        if rate_limit.limit.is_some() || rate_limit.remaining.is_some() || rate_limit.reset.is_some() {
+            *self.rate_limit_status.write().await = Some(rate_limit);
+        }
+
         if status.is_success() {
             let body = response.text().await
                 .map_err(|e| LLMRouterError::network("Failed to read response body", Some(e)))?;
-            
+
             serde_json::from_str(&body)
                 .map_err(|e| LLMRouterError::serialization("Failed to parse JSON response", Some(e)))
+        } else if status.as_u16() == 429 || (status.as_u16() == 503 && retry_after.is_some()) {
+            warn!("Rate limited by server (status {}), retry after {:?}", status, retry_after);
+            Err(LLMRouterError::rate_limited(
+                retry_after,
+                rate_limit.limit,
+                rate_limit.remaining,
+                rate_limit.reset,
+            ))
         } else {
             let body = response.text().await.ok();
             let error_message = body
@@ -183,17 +424,66 @@ impl HttpClient {
     }
 
     /// Load a model
+    #[instrument(name = "llm_router.load_model", skip(self, request), fields(source = %request.source))]
     pub async fn load_model(&self, request: LoadModelRequest) -> Result<LoadModelResponse> {
         debug!("HTTP load model: {}", request.source);
         let body = serde_json::to_value(&request)
             .map_err(|e| LLMRouterError::serialization("Failed to serialize load request", Some(e)))?;
-        
-        let response = self.post("models/load", &body).await?;
+
+        let idempotency_key = generate_idempotency_key();
+        let response = self.post_idempotent("models/load", &body, &idempotency_key).await?;
         
         serde_json::from_value(response)
             .map_err(|e| LLMRouterError::serialization("Failed to parse load response", Some(e)))
     }
 
+    /// Upload a local model artifact (GGUF/safetensors) directly to the
+    /// server instead of asking it to fetch `metadata.source` itself. Streams
+    /// the file rather than buffering it fully in memory, and uses a longer,
+    /// batch-style timeout since artifacts can be large.
+    pub async fn upload_model(
+        &self,
+        metadata: LoadModelRequest,
+        path: impl AsRef<Path>,
+    ) -> Result<LoadModelResponse> {
+        let path = path.as_ref();
+        debug!("HTTP upload model: {}", path.display());
+
+        let metadata_json = serde_json::to_string(&metadata)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize upload metadata", Some(e)))?;
+
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "model.bin".to_string());
+
+        let file = File::open(path).await
+            .map_err(|e| LLMRouterError::other("Failed to open model artifact", Some(e)))?;
+        let stream = ReaderStream::new(file);
+
+        let file_part = multipart::Part::stream(reqwest::Body::wrap_stream(stream))
+            .file_name(file_name)
+            .mime_str("application/octet-stream")
+            .map_err(|e| LLMRouterError::configuration(format!("Invalid content type: {}", e)))?;
+
+        let form = multipart::Form::new()
+            .text("metadata", metadata_json)
+            .part("file", file_part);
+
+        let url = format!("{}/api/v1/models/upload", self.base_url);
+        let timeout_duration = Duration::from_millis(UPLOAD_TIMEOUT_MS);
+
+        self.rate_limiter.acquire(&self.rate_limit_host()).await?;
+        let response = timeout(timeout_duration, self.client.post(&url).multipart(form).send())
+            .await
+            .map_err(|_| LLMRouterError::timeout("Model upload timeout", Some(timeout_duration)))?
+            .map_err(|e| LLMRouterError::network("Failed to upload model", Some(e)))?;
+
+        let value = self.handle_response(response).await?;
+        serde_json::from_value(value)
+            .map_err(|e| LLMRouterError::serialization("Failed to parse upload response", Some(e)))
+    }
+
     /// Unload a model
     pub async fn unload_model(&self, model_id: &str, force: bool) -> Result<Value> {
         debug!("HTTP unload model: {} (force: {})", model_id, force);
@@ -201,72 +491,151 @@ impl HttpClient {
             "model_id": model_id,
             "force": force
         });
-        
-        self.post("models/unload", &body).await
+
+        let idempotency_key = generate_idempotency_key();
+        self.post_idempotent("models/unload", &body, &idempotency_key).await
     }
 
-    /// Perform inference
+    /// Perform inference, under the global default timeout/retry policy
     pub async fn inference(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        self.inference_with_config(request, &RequestConfig::default()).await
+    }
+
+    /// Perform inference, overriding timeout/retry policy for this call only
+    #[instrument(name = "llm_router.inference", skip(self, request, request_config), fields(model_id = ?request.model_id))]
+    pub async fn inference_with_config(
+        &self,
+        request: InferenceRequest,
+        request_config: &RequestConfig,
+    ) -> Result<InferenceResponse> {
         debug!("HTTP inference");
         let body = serde_json::to_value(&request)
             .map_err(|e| LLMRouterError::serialization("Failed to serialize inference request", Some(e)))?;
-        
-        let response = self.post("inference", &body).await?;
-        
+
+        let resolved = request_config.merge_with(&self.config);
+        let response = self.post_with_config("inference", &body, &resolved, None).await?;
+
         serde_json::from_value(response)
             .map_err(|e| LLMRouterError::serialization("Failed to parse inference response", Some(e)))
     }
 
-    /// Stream inference
+    /// Embed one or more strings into vectors via `/api/v1/embeddings`
+    #[instrument(name = "llm_router.embeddings", skip(self, request), fields(model_id = ?request.model_id))]
+    pub async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        debug!("HTTP embeddings");
+        let body = serde_json::to_value(&request)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize embeddings request", Some(e)))?;
+
+        let response = self.post("embeddings", &body).await?;
+
+        serde_json::from_value(response)
+            .map_err(|e| LLMRouterError::serialization("Failed to parse embeddings response", Some(e)))
+    }
+
+    /// Stream inference, under the global default timeout policy
     pub async fn stream_inference(
         &self,
         request: InferenceRequest,
+    ) -> Result<impl Stream<Item = Result<StreamingResponse>>> {
+        self.stream_inference_with_config(request, &RequestConfig::default()).await
+    }
+
+    /// Stream inference, overriding the connection timeout for this call only.
+    /// Connection establishment is retried per `request_config`'s backoff
+    /// policy; once the first token is forwarded, the stream is never retried.
+    #[instrument(name = "llm_router.stream_inference", skip(self, request, request_config), fields(model_id = ?request.model_id))]
+    pub async fn stream_inference_with_config(
+        &self,
+        request: InferenceRequest,
+        request_config: &RequestConfig,
     ) -> Result<impl Stream<Item = Result<StreamingResponse>>> {
         debug!("HTTP stream inference");
         let url = format!("{}/api/v1/inference/stream", self.base_url);
-        
+
         let body = serde_json::to_value(&request)
             .map_err(|e| LLMRouterError::serialization("Failed to serialize stream request", Some(e)))?;
 
-        let response = self.client
-            .post(&url)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| LLMRouterError::network("Failed to start stream", Some(e)))?;
+        let resolved = request_config.merge_with(&self.config);
+        let host = self.rate_limit_host();
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.ok();
-            return Err(LLMRouterError::http(
-                status.as_u16(),
-                "Streaming request failed".to_string(),
-                body,
-            ));
-        }
+        // Retries only cover connection establishment (up through the
+        // response status check, before any SSE bytes are read) -- once a
+        // single token has been forwarded to the caller, retrying would
+        // silently replay or duplicate output, so the stream itself is
+        // never retried past that point.
+        let response = retry_with_backoff(
+            || async {
+                self.rate_limiter.acquire(&host).await?;
+
+                let mut traceparent = reqwest::header::HeaderMap::new();
+                inject_traceparent(&mut traceparent);
+                self.apply_jwt_auth(&mut traceparent).await?;
+
+                let response = timeout(resolved.timeout, self.client.post(&url).headers(traceparent).json(&body).send())
+                    .await
+                    .map_err(|_| LLMRouterError::timeout("Failed to start stream", Some(resolved.timeout)))?
+                    .map_err(|e| LLMRouterError::network("Failed to start stream", Some(e)))?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let body = response.text().await.ok();
+                    return Err(LLMRouterError::http(
+                        status.as_u16(),
+                        "Streaming request failed".to_string(),
+                        body,
+                    ));
+                }
+
+                Ok(response)
+            },
+            resolved.max_retries,
+            resolved.retry_delay,
+            resolved.max_backoff,
+            resolved.multiplier,
+        )
+        .await?;
 
         let stream = response.bytes_stream().map_err(|e| {
             LLMRouterError::streaming(format!("Stream error: {}", e))
         });
 
-        Ok(stream.and_then(|chunk| async move {
-            let text = String::from_utf8(chunk.to_vec())
-                .map_err(|e| LLMRouterError::streaming(format!("Invalid UTF-8 in stream: {}", e)))?;
-
-            // Parse Server-Sent Events format
-            for line in text.lines() {
-                if let Some(data) = line.strip_prefix("data: ") {
-                    let chunk: StreamingResponse = serde_json::from_str(data)
-                        .map_err(|e| LLMRouterError::serialization("Failed to parse stream chunk", Some(e)))?;
-                    return Ok(chunk);
+        // Recorded as span events rather than log lines so a collector can
+        // correlate "time to first token" and stream completion with the
+        // rest of this request's trace.
+        let span = tracing::Span::current();
+        let first_token_seen = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        Ok(stream.and_then(move |chunk| {
+            let span = span.clone();
+            let first_token_seen = first_token_seen.clone();
+            async move {
+                let text = String::from_utf8(chunk.to_vec())
+                    .map_err(|e| LLMRouterError::streaming(format!("Invalid UTF-8 in stream: {}", e)))?;
+
+                // Parse Server-Sent Events format
+                for line in text.lines() {
+                    if let Some(data) = line.strip_prefix("data: ") {
+                        let chunk: StreamingResponse = serde_json::from_str(data)
+                            .map_err(|e| LLMRouterError::serialization("Failed to parse stream chunk", Some(e)))?;
+
+                        if !first_token_seen.swap(true, std::sync::atomic::Ordering::Relaxed) {
+                            span.in_scope(|| tracing::info!("first_token"));
+                        }
+                        if chunk.is_complete {
+                            span.in_scope(|| tracing::info!("stream_complete"));
+                        }
+
+                        return Ok(chunk);
+                    }
                 }
-            }
 
-            Err(LLMRouterError::streaming("No valid data in stream chunk"))
+                Err(LLMRouterError::streaming("No valid data in stream chunk"))
+            }
         }))
     }
 
     /// Batch inference
+    #[instrument(name = "llm_router.batch_inference", skip(self, request), fields(count = request.requests.len()))]
     pub async fn batch_inference(&self, request: BatchInferenceRequest) -> Result<BatchInferenceResponse> {
         debug!("HTTP batch inference with {} requests", request.requests.len());
         let body = serde_json::to_value(&request)
@@ -297,6 +666,7 @@ impl HttpClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::RateLimitConfig;
     use mockito::{mock, server_url};
 
     async fn test_client() -> HttpClient {
@@ -350,6 +720,94 @@ mod tests {
         assert!(response.success);
     }
 
+    #[tokio::test]
+    async fn test_load_model_sends_an_idempotency_key() {
+        let _m = mock("POST", "/api/v1/models/load")
+            .match_header("idempotency-key", mockito::Matcher::Regex("^[0-9a-f]{32}$".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"success": true, "message": "loaded"}"#)
+            .create();
+
+        let client = test_client().await;
+        let result = client.load_model(LoadModelRequest::new("test-model")).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_generate_idempotency_key_is_unique_per_call() {
+        let a = generate_idempotency_key();
+        let b = generate_idempotency_key();
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 32);
+    }
+
+    #[tokio::test]
+    async fn test_debug_masks_auth_headers() {
+        let config = Arc::new(RouterConfig::new(&server_url()).api_key("super-secret-key"));
+        let client = HttpClient::new(config).await.unwrap();
+
+        let rendered = format!("{:?}", client);
+        assert!(!rendered.contains("super-secret-key"));
+        assert!(rendered.contains("<masked>"));
+        assert!(rendered.contains("User-Agent"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_response() {
+        let _m = mock("GET", "/api/v1/health")
+            .with_status(429)
+            .with_header("content-type", "application/json")
+            .with_header("retry-after", "2")
+            .with_header("x-ratelimit-limit", "100")
+            .with_header("x-ratelimit-remaining", "0")
+            .with_header("x-ratelimit-reset", "1700000000")
+            .with_body(r#"{"error": "Too many requests"}"#)
+            .create();
+
+        let config = Arc::new(RouterConfig::new(&server_url()).max_retries(0));
+        let client = HttpClient::new(config).await.unwrap();
+        let result = client.health_check().await;
+
+        match result {
+            Err(LLMRouterError::RateLimited { retry_after, limit, remaining, reset }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(2)));
+                assert_eq!(limit, Some(100));
+                assert_eq!(remaining, Some(0));
+                assert_eq!(reset, Some(1_700_000_000));
+            }
+            other => panic!("Expected RateLimited error, got {:?}", other),
+        }
+
+        let status = client.rate_limit_status().await.unwrap();
+        assert_eq!(status.remaining, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_client_side_rate_limit_delays_requests() {
+        let _m = mock("GET", "/api/v1/health")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "healthy"}"#)
+            .create();
+
+        let config = Arc::new(
+            RouterConfig::new(&server_url()).rate_limit(RateLimitConfig {
+                requests_per_minute: 600,
+                burst_capacity: 1,
+                enabled: true,
+            }),
+        );
+        let client = HttpClient::new(config).await.unwrap();
+
+        client.health_check().await.unwrap();
+        let started = std::time::Instant::now();
+        client.health_check().await.unwrap();
+        // Burst capacity of 1 at 10 tokens/sec means the second call had to
+        // wait out roughly a 100ms deficit instead of firing immediately.
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
     #[tokio::test]
     async fn test_error_handling() {
         let _m = mock("GET", "/api/v1/health")
@@ -0,0 +1,545 @@
+//! Blocking (synchronous) HTTP client implementation for LLM Router
+//!
+//! Mirrors [`crate::protocols::http::HttpClient`]'s method surface for
+//! integrators (synchronous CLIs, scripts, FFI hosts) where spinning up a
+//! Tokio runtime is awkward. Built on `reqwest::blocking` rather than futures;
+//! there is no blocking equivalent of `http::HttpClient::stream_inference`'s
+//! `Stream` return type, so it returns a plain `Iterator` instead.
+
+use crate::{
+    config::RouterConfig,
+    error::{LLMRouterError, Result},
+    models::*,
+    utils::{rate_limit::RateLimiter, retry::retry_with_backoff_blocking},
+};
+
+use reqwest::blocking::{Client as ReqwestBlockingClient, ClientBuilder, Response};
+use serde_json::Value;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::sync::RwLock;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tracing::{debug, info, warn};
+use url::Url;
+
+use crate::protocols::http::RateLimitStatus;
+
+/// Header names that are always masked in `BlockingHttpClient`'s `Debug`
+/// output, regardless of `RouterConfig::sensitive_headers`.
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "x-api-key", "api-key"];
+
+/// Blocking HTTP client for LLM Router REST API
+#[derive(Clone)]
+pub struct BlockingHttpClient {
+    client: ReqwestBlockingClient,
+    config: Arc<RouterConfig>,
+    base_url: String,
+    rate_limit_status: Arc<RwLock<Option<RateLimitStatus>>>,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl fmt::Debug for BlockingHttpClient {
+    /// Mirrors `protocols::http::HttpClient`'s masked `Debug` impl: auth
+    /// header values are replaced with `<masked>` so logging this client
+    /// can't leak API keys or bearer tokens.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let headers: Vec<(String, String)> = self.config.get_auth_headers()
+            .into_iter()
+            .map(|(name, value)| {
+                let is_sensitive = SENSITIVE_HEADERS.iter().any(|h| name.eq_ignore_ascii_case(h))
+                    || self.config.sensitive_headers.iter().any(|h| name.eq_ignore_ascii_case(h));
+                let value = if is_sensitive { "<masked>".to_string() } else { value };
+                (name, value)
+            })
+            .collect();
+
+        f.debug_struct("BlockingHttpClient")
+            .field("base_url", &self.base_url)
+            .field("headers", &headers)
+            .finish()
+    }
+}
+
+impl BlockingHttpClient {
+    /// Create a new blocking HTTP client
+    pub fn new(config: Arc<RouterConfig>) -> Result<Self> {
+        let mut headers = reqwest::header::HeaderMap::new();
+
+        // Set default headers
+        headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+
+        // Add authentication headers
+        for (key, value) in config.get_auth_headers() {
+            let header_name = reqwest::header::HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| LLMRouterError::configuration(format!("Invalid header name: {}", e)))?;
+            let header_value = reqwest::header::HeaderValue::from_str(&value)
+                .map_err(|e| LLMRouterError::configuration(format!("Invalid header value: {}", e)))?;
+            headers.insert(header_name, header_value);
+        }
+
+        let mut client_builder = ClientBuilder::new()
+            .default_headers(headers)
+            .timeout(config.timeout)
+            .connect_timeout(config.connection_pool.connect_timeout)
+            .pool_idle_timeout(Some(config.connection_pool.idle_timeout))
+            .pool_max_idle_per_host(config.connection_pool.max_idle_connections);
+
+        // Configure TLS via a real rustls client built from `TlsConfig`, so
+        // `ca_cert_path`/mutual-TLS settings are honored (not just the
+        // verify_ssl toggle that `danger_accept_invalid_certs` covers alone).
+        let tls_config = config.tls.build_client_config()?;
+        client_builder = client_builder.use_preconfigured_tls(tls_config);
+
+        // Pin overridden hosts to their configured addresses instead of
+        // resolving them through system DNS.
+        for override_entry in &config.connect_to {
+            client_builder = client_builder.resolve_to_addrs(&override_entry.host, &override_entry.addrs);
+        }
+
+        let client = client_builder
+            .build()
+            .map_err(|e| LLMRouterError::configuration(format!("Failed to create HTTP client: {}", e)))?;
+
+        let base_url = config.base_url.trim_end_matches('/').to_string();
+
+        info!("Blocking HTTP client initialized for: {}", base_url);
+
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+
+        Ok(Self {
+            client,
+            config,
+            base_url,
+            rate_limit_status: Arc::new(RwLock::new(None)),
+            rate_limiter,
+        })
+    }
+
+    /// The host to key the rate limiter's token bucket by, derived from
+    /// `base_url`. Falls back to the full base URL if it doesn't parse.
+    fn rate_limit_host(&self) -> String {
+        Url::parse(&self.base_url)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_string()))
+            .unwrap_or_else(|| self.base_url.clone())
+    }
+
+    /// Make a GET request with retry logic
+    fn get(&self, endpoint: &str, params: Option<&HashMap<String, String>>) -> Result<Value> {
+        let url = format!("{}/api/v1/{}", self.base_url, endpoint.trim_start_matches('/'));
+
+        retry_with_backoff_blocking(
+            || {
+                self.rate_limiter.check(&self.rate_limit_host())?;
+
+                let mut request = self.client.get(&url);
+
+                if let Some(params) = params {
+                    request = request.query(params);
+                }
+
+                let response = request.send()?;
+                self.handle_response(response)
+            },
+            self.config.backoff.max_retries,
+            self.config.backoff.base_delay,
+            self.config.backoff.max_backoff,
+            self.config.backoff.multiplier,
+        )
+    }
+
+    /// Make a POST request with retry logic
+    fn post(&self, endpoint: &str, body: &Value) -> Result<Value> {
+        let url = format!("{}/api/v1/{}", self.base_url, endpoint.trim_start_matches('/'));
+
+        retry_with_backoff_blocking(
+            || {
+                self.rate_limiter.check(&self.rate_limit_host())?;
+
+                let response = self.client.post(&url).json(body).send()?;
+                self.handle_response(response)
+            },
+            self.config.backoff.max_retries,
+            self.config.backoff.base_delay,
+            self.config.backoff.max_backoff,
+            self.config.backoff.multiplier,
+        )
+    }
+
+    /// Parse the `Retry-After` header, which servers may send as either a
+    /// number of seconds or an HTTP-date.
+    fn parse_retry_after(response: &Response) -> Option<Duration> {
+        let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let target = httpdate::parse_http_date(value).ok()?;
+        target.duration_since(std::time::SystemTime::now()).ok()
+    }
+
+    /// Parse the conventional `X-RateLimit-Limit` / `X-RateLimit-Remaining` /
+    /// `X-RateLimit-Reset` trio, if the server sent any of them.
+    fn parse_rate_limit_headers(response: &Response) -> RateLimitStatus {
+        let header_value = |name: &str| {
+            response.headers().get(name).and_then(|v| v.to_str().ok())
+        };
+
+        RateLimitStatus {
+            limit: header_value("x-ratelimit-limit").and_then(|v| v.parse().ok()),
+            remaining: header_value("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+            reset: header_value("x-ratelimit-reset").and_then(|v| v.parse().ok()),
+        }
+    }
+
+    /// Snapshot of the most recently observed `X-RateLimit-*` response
+    /// headers, or `None` if the server has never sent any.
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        *self.rate_limit_status.read().unwrap()
+    }
+
+    /// Handle HTTP response
+    fn handle_response(&self, response: Response) -> Result<Value> {
+        let status = response.status();
+        let retry_after = Self::parse_retry_after(&response);
+        let rate_limit = Self::parse_rate_limit_headers(&response);
+
+        if rate_limit.limit.is_some() || rate_limit.remaining.is_some() || rate_limit.reset.is_some() {
+            *self.rate_limit_status.write().unwrap() = Some(rate_limit);
+        }
+
+        if status.is_success() {
+            let body = response.text()
+                .map_err(|e| LLMRouterError::network("Failed to read response body", Some(e)))?;
+
+            serde_json::from_str(&body)
+                .map_err(|e| LLMRouterError::serialization("Failed to parse JSON response", Some(e)))
+        } else if status.as_u16() == 429 || (status.as_u16() == 503 && retry_after.is_some()) {
+            warn!("Rate limited by server (status {}), retry after {:?}", status, retry_after);
+            Err(LLMRouterError::rate_limited(
+                retry_after,
+                rate_limit.limit,
+                rate_limit.remaining,
+                rate_limit.reset,
+            ))
+        } else {
+            let body = response.text().ok();
+            let error_message = body
+                .as_ref()
+                .and_then(|b| serde_json::from_str::<Value>(b).ok())
+                .and_then(|v| v.get("error").and_then(|e| e.as_str()))
+                .unwrap_or("Request failed");
+
+            Err(LLMRouterError::http(
+                status.as_u16(),
+                error_message.to_string(),
+                body,
+            ))
+        }
+    }
+
+    /// Health check
+    pub fn health_check(&self) -> Result<Value> {
+        debug!("Blocking HTTP health check");
+        self.get("health", None)
+    }
+
+    /// Get system status
+    pub fn get_status(&self) -> Result<Value> {
+        debug!("Blocking HTTP get status");
+        self.get("status", None)
+    }
+
+    /// Get system metrics
+    pub fn get_metrics(&self) -> Result<Value> {
+        debug!("Blocking HTTP get metrics");
+        self.get("metrics", None)
+    }
+
+    /// List models
+    pub fn list_models(&self, include_unloaded: bool) -> Result<Vec<ModelInfo>> {
+        debug!("Blocking HTTP list models");
+        let mut params = HashMap::new();
+        params.insert("include_unloaded".to_string(), include_unloaded.to_string());
+
+        let response = self.get("models", Some(&params))?;
+        let models = response
+            .get("models")
+            .and_then(|m| m.as_array())
+            .ok_or_else(|| LLMRouterError::serialization("Invalid models response format", None))?;
+
+        models
+            .iter()
+            .map(|model| {
+                serde_json::from_value(model.clone())
+                    .map_err(|e| LLMRouterError::serialization("Failed to parse model info", Some(e)))
+            })
+            .collect()
+    }
+
+    /// Get model information
+    pub fn get_model(&self, model_id: &str) -> Result<ModelInfo> {
+        debug!("Blocking HTTP get model: {}", model_id);
+        let endpoint = format!("models/{}", model_id);
+        let response = self.get(&endpoint, None)?;
+
+        serde_json::from_value(response)
+            .map_err(|e| LLMRouterError::serialization("Failed to parse model info", Some(e)))
+    }
+
+    /// Load a model
+    pub fn load_model(&self, request: LoadModelRequest) -> Result<LoadModelResponse> {
+        debug!("Blocking HTTP load model: {}", request.source);
+        let body = serde_json::to_value(&request)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize load request", Some(e)))?;
+
+        let response = self.post("models/load", &body)?;
+
+        serde_json::from_value(response)
+            .map_err(|e| LLMRouterError::serialization("Failed to parse load response", Some(e)))
+    }
+
+    /// Unload a model
+    pub fn unload_model(&self, model_id: &str, force: bool) -> Result<Value> {
+        debug!("Blocking HTTP unload model: {} (force: {})", model_id, force);
+        let body = serde_json::json!({
+            "model_id": model_id,
+            "force": force
+        });
+
+        self.post("models/unload", &body)
+    }
+
+    /// Perform inference
+    pub fn inference(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        debug!("Blocking HTTP inference");
+        let body = serde_json::to_value(&request)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize inference request", Some(e)))?;
+
+        let response = self.post("inference", &body)?;
+
+        serde_json::from_value(response)
+            .map_err(|e| LLMRouterError::serialization("Failed to parse inference response", Some(e)))
+    }
+
+    /// Stream inference tokens, reading SSE lines off a blocking byte reader.
+    pub fn stream_inference(
+        &self,
+        request: InferenceRequest,
+    ) -> Result<BlockingStreamingResponseIter> {
+        debug!("Blocking HTTP stream inference");
+        let url = format!("{}/api/v1/inference/stream", self.base_url);
+
+        let body = serde_json::to_value(&request)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize stream request", Some(e)))?;
+
+        self.rate_limiter.check(&self.rate_limit_host())?;
+        let response = self.client
+            .post(&url)
+            .json(&body)
+            .send()
+            .map_err(|e| LLMRouterError::network("Failed to start stream", Some(e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().ok();
+            return Err(LLMRouterError::http(
+                status.as_u16(),
+                "Streaming request failed".to_string(),
+                body,
+            ));
+        }
+
+        Ok(BlockingStreamingResponseIter {
+            reader: BufReader::new(response),
+        })
+    }
+
+    /// Batch inference
+    pub fn batch_inference(&self, request: BatchInferenceRequest) -> Result<BatchInferenceResponse> {
+        debug!("Blocking HTTP batch inference with {} requests", request.requests.len());
+        let body = serde_json::to_value(&request)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize batch request", Some(e)))?;
+
+        let url = format!("{}/api/v1/{}", self.base_url, "inference/batch");
+        let timeout_duration = Duration::from_millis(request.timeout_ms.unwrap_or(30000));
+
+        let response = retry_with_backoff_blocking(
+            || {
+                self.rate_limiter.check(&self.rate_limit_host())?;
+
+                let response = self.client
+                    .post(&url)
+                    .timeout(timeout_duration)
+                    .json(&body)
+                    .send()?;
+                self.handle_response(response)
+            },
+            self.config.backoff.max_retries,
+            self.config.backoff.base_delay,
+            self.config.backoff.max_backoff,
+            self.config.backoff.multiplier,
+        )?;
+
+        serde_json::from_value(response)
+            .map_err(|e| LLMRouterError::serialization("Failed to parse batch response", Some(e)))
+    }
+
+    /// Close the client
+    pub fn close(&self) -> Result<()> {
+        debug!("Closing blocking HTTP client");
+        // reqwest::blocking::Client doesn't need explicit cleanup
+        Ok(())
+    }
+}
+
+/// Iterator over SSE-framed `StreamingResponse`s read off a blocking response body.
+///
+/// Reads line-by-line, skipping everything but `data: ...` frames, yielding
+/// one item per frame and ending once the underlying reader is exhausted.
+pub struct BlockingStreamingResponseIter {
+    reader: BufReader<Response>,
+}
+
+impl Iterator for BlockingStreamingResponseIter {
+    type Item = Result<StreamingResponse>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    let trimmed = line.trim_end_matches(['\r', '\n']);
+                    if let Some(data) = trimmed.strip_prefix("data: ") {
+                        return Some(
+                            serde_json::from_str(data)
+                                .map_err(|e| LLMRouterError::serialization("Failed to parse stream chunk", Some(e))),
+                        );
+                    }
+                    // Blank lines and other SSE fields (event:, id:) are ignored.
+                }
+                Err(e) => {
+                    return Some(Err(LLMRouterError::streaming(format!("Stream error: {}", e))));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, server_url};
+
+    fn test_client() -> BlockingHttpClient {
+        let config = Arc::new(RouterConfig::new(&server_url()));
+        BlockingHttpClient::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_health_check() {
+        let _m = mock("GET", "/api/v1/health")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "healthy"}"#)
+            .create();
+
+        let client = test_client();
+        let result = client.health_check();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_inference() {
+        let _m = mock("POST", "/api/v1/inference")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"text": "Hello, world!", "success": true}"#)
+            .create();
+
+        let client = test_client();
+        let request = InferenceRequest::new("Hello");
+        let result = client.inference(request);
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.text, "Hello, world!");
+        assert!(response.success);
+    }
+
+    #[test]
+    fn test_rate_limited_response() {
+        let _m = mock("GET", "/api/v1/health")
+            .with_status(429)
+            .with_header("content-type", "application/json")
+            .with_header("retry-after", "2")
+            .with_header("x-ratelimit-remaining", "0")
+            .with_body(r#"{"error": "Too many requests"}"#)
+            .create();
+
+        let config = Arc::new(RouterConfig::new(&server_url()).max_retries(0));
+        let client = BlockingHttpClient::new(config).unwrap();
+        let result = client.health_check();
+
+        match result {
+            Err(LLMRouterError::RateLimited { retry_after, remaining, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(2)));
+                assert_eq!(remaining, Some(0));
+            }
+            other => panic!("Expected RateLimited error, got {:?}", other),
+        }
+
+        assert_eq!(client.rate_limit_status().unwrap().remaining, Some(0));
+    }
+
+    #[test]
+    fn test_client_side_rate_limit_fails_fast() {
+        let _m = mock("GET", "/api/v1/health")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "healthy"}"#)
+            .create();
+
+        let config = Arc::new(
+            RouterConfig::new(&server_url())
+                .max_retries(0)
+                .rate_limit(crate::config::RateLimitConfig {
+                    requests_per_minute: 60,
+                    burst_capacity: 1,
+                    enabled: true,
+                }),
+        );
+        let client = BlockingHttpClient::new(config).unwrap();
+
+        client.health_check().unwrap();
+        match client.health_check() {
+            Err(LLMRouterError::RateLimit { retry_after, .. }) => assert!(retry_after.is_some()),
+            other => panic!("Expected client-side RateLimit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_error_handling() {
+        let _m = mock("GET", "/api/v1/health")
+            .with_status(500)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error": "Internal server error"}"#)
+            .create();
+
+        let client = test_client();
+        let result = client.health_check();
+        assert!(result.is_err());
+
+        if let Err(LLMRouterError::Http { status, message, .. }) = result {
+            assert_eq!(status, 500);
+            assert_eq!(message, "Internal server error");
+        } else {
+            panic!("Expected HTTP error");
+        }
+    }
+}
@@ -2,8 +2,25 @@
 
 pub mod http;
 
+#[cfg(feature = "blocking")]
+pub mod http_blocking;
+
 #[cfg(feature = "grpc")]
 pub mod grpc;
 
 #[cfg(feature = "websocket")]
-pub mod websocket;
\ No newline at end of file
+pub mod websocket;
+
+/// Connection lifecycle of a persistent protocol client (gRPC, WebSocket),
+/// as tracked by the client itself and surfaced through `Client`'s
+/// reconnect-on-demand accessors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The underlying transport is up and serving requests.
+    Connected,
+    /// The transport dropped and a reconnect attempt is in flight.
+    Reconnecting,
+    /// The transport dropped (the socket errored or closed); the cached
+    /// client will be re-dialed from scratch on the next access.
+    Failed,
+}
\ No newline at end of file
@@ -0,0 +1,600 @@
+//! Persistent WebSocket transport for LLM Router client
+//!
+//! `stream_inference` over `protocols::http` opens a fresh POST (and a fresh
+//! TCP/TLS handshake) per call and parses Server-Sent Events off the
+//! response body. `WebSocketClient` instead holds a single long-lived
+//! connection to `/api/v1/ws` and multiplexes concurrent requests over it:
+//! each outgoing request gets a monotonically increasing correlation id, and
+//! a background task routes inbound `{id, payload}` frames back to the right
+//! caller — streaming requests via an `mpsc` channel kept open until a
+//! terminal frame arrives, and unary `inference()` calls via a `oneshot`
+//! resolved exactly once — so hundreds of in-flight requests can share one
+//! socket without head-of-line blocking.
+//!
+//! The reader/writer tasks flip a shared [`ConnectionState`](crate::protocols::ConnectionState)
+//! to `Failed` the moment the socket errors or closes; `Client::websocket_client`
+//! watches that state to decide when the cached client needs re-dialing
+//! rather than handing out a dead connection.
+
+use crate::{
+    config::RouterConfig,
+    error::{LLMRouterError, Result},
+    models::*,
+    protocols::ConnectionState,
+};
+
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot, watch, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async_tls_with_config, Connector, MaybeTlsStream, WebSocketStream};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, warn};
+
+/// Number of consecutive chunks to forward for one correlation id before
+/// yielding to the scheduler, so a single large response can't starve the
+/// other streams sharing this connection.
+const FAIRNESS_BATCH: usize = 8;
+
+/// Number of terminated correlation ids to accumulate before sweeping
+/// closed-channel entries out of the routing table.
+const GC_THRESHOLD: usize = 256;
+
+/// Envelope exchanged over the socket: every outbound request and inbound
+/// chunk is tagged with the correlation id that ties it back to a caller.
+#[derive(Debug, Serialize, Deserialize)]
+struct WsEnvelope<T> {
+    id: u64,
+    payload: T,
+}
+
+/// Outbound subscribe request: distinct from `WsEnvelope` (a `filter`, not a
+/// `payload`) so the server can tell a subscription apart from an inference
+/// call sharing the same correlation-id space.
+#[derive(Debug, Serialize)]
+struct SubscribeEnvelope {
+    id: u64,
+    filter: SubscriptionFilter,
+}
+
+/// Outbound unsubscribe frame sent when an `EventSubscription` is dropped.
+#[derive(Debug, Serialize)]
+struct UnsubscribeEnvelope {
+    id: u64,
+    unsubscribe: bool,
+}
+
+type ChunkSender = mpsc::Sender<Result<StreamingResponse>>;
+type UnarySender = oneshot::Sender<Result<InferenceResponse>>;
+type EventSender = mpsc::Sender<Result<Event>>;
+type WsSink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+type WsSource = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+
+/// WebSocket client multiplexing concurrent streaming inference calls over
+/// a single long-lived connection.
+#[derive(Clone)]
+pub struct WebSocketClient {
+    #[allow(dead_code)]
+    config: Arc<RouterConfig>,
+    next_id: Arc<AtomicU64>,
+    routes: Arc<Mutex<HashMap<u64, ChunkSender>>>,
+    /// Pending unary (non-streaming) `inference()` calls multiplexed over
+    /// this same connection, keyed by correlation id like `routes` but
+    /// resolved exactly once via a `oneshot` rather than forwarded chunk by
+    /// chunk
+    unary_routes: Arc<Mutex<BTreeMap<u64, UnarySender>>>,
+    /// Live `Client::subscribe` subscriptions sharing this connection, keyed
+    /// by the id their subscribe frame was sent under; entries live until
+    /// the matching `EventSubscription` is dropped or the socket closes
+    subscriptions: Arc<Mutex<HashMap<u64, EventSender>>>,
+    outbound: mpsc::Sender<Message>,
+    /// Flipped to `Failed` by `run_reader`/`run_writer` when the socket
+    /// closes or errors, so `Client::websocket_client` knows to re-dial
+    /// instead of handing out a dead connection.
+    state: Arc<watch::Sender<ConnectionState>>,
+    /// Cancelled by `close()` so `run_reader`/`run_writer` stop waiting on
+    /// the socket and exit instead of outliving every clone of this client.
+    cancel: CancellationToken,
+}
+
+impl WebSocketClient {
+    /// Connect to `/api/v1/ws` and spawn the reader/writer tasks that own
+    /// the connection for its lifetime.
+    pub async fn new(config: Arc<RouterConfig>) -> Result<Self> {
+        let scheme_base = config.base_url
+            .replacen("http://", "ws://", 1)
+            .replacen("https://", "wss://", 1);
+        let ws_url = format!("{}/api/v1/ws", scheme_base.trim_end_matches('/'));
+
+        // `connect_async` picks a default TLS connector that can't see
+        // `ca_cert_path`/the mutual-TLS keypair, so build one from
+        // `TlsConfig` ourselves whenever the connection is secure.
+        let connector = if ws_url.starts_with("wss://") {
+            Some(Connector::Rustls(Arc::new(config.tls.build_client_config()?)))
+        } else {
+            None
+        };
+
+        debug!("Connecting WebSocket client to: {}", ws_url);
+        let (ws_stream, _) = connect_async_tls_with_config(&ws_url, None, false, connector).await?;
+        let (write, read) = ws_stream.split();
+
+        let routes: Arc<Mutex<HashMap<u64, ChunkSender>>> = Arc::new(Mutex::new(HashMap::new()));
+        let unary_routes: Arc<Mutex<BTreeMap<u64, UnarySender>>> = Arc::new(Mutex::new(BTreeMap::new()));
+        let subscriptions: Arc<Mutex<HashMap<u64, EventSender>>> = Arc::new(Mutex::new(HashMap::new()));
+        let (outbound_tx, outbound_rx) = mpsc::channel(64);
+        let (state_tx, _) = watch::channel(ConnectionState::Connected);
+        let state = Arc::new(state_tx);
+        let cancel = CancellationToken::new();
+
+        tokio::spawn(run_writer(write, outbound_rx, state.clone(), cancel.clone()));
+        tokio::spawn(run_reader(
+            read,
+            routes.clone(),
+            unary_routes.clone(),
+            subscriptions.clone(),
+            state.clone(),
+            cancel.clone(),
+        ));
+
+        Ok(Self {
+            config,
+            next_id: Arc::new(AtomicU64::new(0)),
+            routes,
+            unary_routes,
+            subscriptions,
+            outbound: outbound_tx,
+            state,
+            cancel,
+        })
+    }
+
+    /// Current connection state, as last observed by the reader/writer
+    /// tasks that own this socket.
+    pub fn state(&self) -> ConnectionState {
+        *self.state.borrow()
+    }
+
+    /// Run streaming inference over the shared WebSocket connection,
+    /// mirroring `protocols::http::HttpClient::stream_inference`'s API so
+    /// callers can switch transport transparently.
+    pub async fn ws_inference(
+        &self,
+        request: InferenceRequest,
+    ) -> Result<impl Stream<Item = Result<StreamingResponse>>> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = mpsc::channel(32);
+        self.routes.lock().await.insert(id, tx);
+
+        let envelope = WsEnvelope { id, payload: request };
+        let text = serde_json::to_string(&envelope)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize WS request", Some(e)))?;
+
+        self.outbound.send(Message::Text(text)).await.map_err(|_| {
+            LLMRouterError::websocket("WebSocket writer task is no longer running")
+        })?;
+
+        Ok(futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        }))
+    }
+
+    /// Run one non-streaming inference call over the shared WebSocket
+    /// connection, multiplexed by correlation id alongside any concurrent
+    /// `ws_inference` streams and other unary calls, so one socket can carry
+    /// hundreds of in-flight requests without head-of-line blocking.
+    pub async fn ws_unary_inference(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.unary_routes.lock().await.insert(id, tx);
+
+        let envelope = WsEnvelope { id, payload: request };
+        let text = serde_json::to_string(&envelope)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize WS request", Some(e)))?;
+
+        if let Err(e) = self.outbound.send(Message::Text(text)).await {
+            self.unary_routes.lock().await.remove(&id);
+            return Err(LLMRouterError::websocket(format!(
+                "WebSocket writer task is no longer running: {}",
+                e
+            )));
+        }
+
+        rx.await
+            .map_err(|_| LLMRouterError::websocket("Connection closed before a response for this request arrived"))?
+    }
+
+    /// Subscribe to server-pushed events (model load/unload, health
+    /// transitions, metric updates) matching `filter`, evaluated
+    /// server-side, instead of polling `get_status()`/`get_metrics()`.
+    /// Multiple subscriptions share this one connection, keyed by
+    /// subscription id; dropping the returned `EventSubscription` sends an
+    /// unsubscribe frame and frees its routing entry, the same way a
+    /// jsonrpsee/tendermint subscription tears down when its sink is
+    /// dropped.
+    pub async fn subscribe(&self, filter: SubscriptionFilter) -> Result<EventSubscription> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = mpsc::channel(32);
+        self.subscriptions.lock().await.insert(id, tx);
+
+        let envelope = SubscribeEnvelope { id, filter };
+        let text = serde_json::to_string(&envelope)
+            .map_err(|e| LLMRouterError::serialization("Failed to serialize subscribe request", Some(e)))?;
+
+        if let Err(e) = self.outbound.send(Message::Text(text)).await {
+            self.subscriptions.lock().await.remove(&id);
+            return Err(LLMRouterError::websocket(format!(
+                "WebSocket writer task is no longer running: {}",
+                e
+            )));
+        }
+
+        Ok(EventSubscription {
+            id,
+            rx,
+            outbound: self.outbound.clone(),
+            subscriptions: self.subscriptions.clone(),
+        })
+    }
+
+    /// Close the client: cancels `run_reader`/`run_writer` for this
+    /// connection so they stop waiting on the socket and exit, instead of
+    /// the previous best-effort behavior of relying on every clone being
+    /// dropped. Idempotent; safe to call more than once.
+    pub async fn close(&self) -> Result<()> {
+        debug!("Closing WebSocket client");
+        self.cancel.cancel();
+        Ok(())
+    }
+}
+
+/// Forward outbound envelopes onto the socket until the sender side is
+/// dropped, the write fails, or `cancel` fires.
+async fn run_writer(
+    mut write: WsSink,
+    mut outbound: mpsc::Receiver<Message>,
+    state: Arc<watch::Sender<ConnectionState>>,
+    cancel: CancellationToken,
+) {
+    loop {
+        let message = tokio::select! {
+            _ = cancel.cancelled() => break,
+            message = outbound.recv() => match message {
+                Some(message) => message,
+                None => break,
+            },
+        };
+
+        if let Err(e) = write.send(message).await {
+            error!("WebSocket write error: {}", e);
+            state.send_replace(ConnectionState::Failed);
+            break;
+        }
+    }
+
+    let _ = write.close().await;
+}
+
+/// Read inbound envelopes off the socket and route each one to the channel
+/// registered for its correlation id: a unary `inference()` id resolves its
+/// `oneshot` exactly once, a subscription id forwards `Event`s until its
+/// `EventSubscription` is dropped, and a streaming id forwards chunks until
+/// a terminal (`is_complete`) frame arrives. All three routing tables are
+/// drained with errors once the socket closes (or `cancel` fires) so no
+/// caller hangs forever.
+async fn run_reader(
+    mut read: WsSource,
+    routes: Arc<Mutex<HashMap<u64, ChunkSender>>>,
+    unary_routes: Arc<Mutex<BTreeMap<u64, UnarySender>>>,
+    subscriptions: Arc<Mutex<HashMap<u64, EventSender>>>,
+    state: Arc<watch::Sender<ConnectionState>>,
+    cancel: CancellationToken,
+) {
+    let mut fairness_id = None;
+    let mut fairness_count = 0usize;
+    let mut closed_since_gc = 0usize;
+
+    loop {
+        let message = tokio::select! {
+            _ = cancel.cancelled() => break,
+            message = read.next() => match message {
+                Some(message) => message,
+                None => break,
+            },
+        };
+
+        let message = match message {
+            Ok(message) => message,
+            Err(e) => {
+                error!("WebSocket read error: {}", e);
+                state.send_replace(ConnectionState::Failed);
+                break;
+            }
+        };
+
+        let Message::Text(text) = message else {
+            continue;
+        };
+
+        let envelope: WsEnvelope<serde_json::Value> = match serde_json::from_str(&text) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                warn!("Failed to parse WebSocket frame: {}", e);
+                continue;
+            }
+        };
+
+        if let Some(sender) = unary_routes.lock().await.remove(&envelope.id) {
+            let response = serde_json::from_value::<InferenceResponse>(envelope.payload)
+                .map_err(|e| LLMRouterError::serialization("Failed to parse unary WS response", Some(e)));
+            let _ = sender.send(response);
+            continue;
+        }
+
+        if let Some(sender) = subscriptions.lock().await.get(&envelope.id).cloned() {
+            let event = serde_json::from_value::<Event>(envelope.payload)
+                .map_err(|e| LLMRouterError::serialization("Failed to parse subscription event", Some(e)));
+            let _ = sender.send(event).await;
+            continue;
+        }
+
+        let payload: StreamingResponse = match serde_json::from_value(envelope.payload) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!("Failed to parse WebSocket streaming frame: {}", e);
+                continue;
+            }
+        };
+
+        let mut routes_guard = routes.lock().await;
+        let Some(sender) = routes_guard.get(&envelope.id) else {
+            continue;
+        };
+
+        let is_done = payload.is_complete;
+        let _ = sender.send(Ok(payload)).await;
+
+        if is_done {
+            routes_guard.remove(&envelope.id);
+            closed_since_gc += 1;
+            if closed_since_gc >= GC_THRESHOLD {
+                routes_guard.retain(|_, sender| !sender.is_closed());
+                closed_since_gc = 0;
+            }
+        }
+        drop(routes_guard);
+
+        // Interleave draining across active ids: once one id has monopolized
+        // FAIRNESS_BATCH consecutive frames, yield so other tasks polling
+        // their own receivers get a turn.
+        if fairness_id == Some(envelope.id) {
+            fairness_count += 1;
+        } else {
+            fairness_id = Some(envelope.id);
+            fairness_count = 1;
+        }
+        if fairness_count >= FAIRNESS_BATCH {
+            fairness_count = 0;
+            tokio::task::yield_now().await;
+        }
+    }
+
+    state.send_replace(ConnectionState::Failed);
+
+    for (_, sender) in std::mem::take(&mut *unary_routes.lock().await) {
+        let _ = sender.send(Err(LLMRouterError::websocket("WebSocket connection closed")));
+    }
+    for (_, sender) in std::mem::take(&mut *routes.lock().await) {
+        let _ = sender.send(Err(LLMRouterError::websocket("WebSocket connection closed"))).await;
+    }
+    for (_, sender) in std::mem::take(&mut *subscriptions.lock().await) {
+        let _ = sender.send(Err(LLMRouterError::websocket("WebSocket connection closed"))).await;
+    }
+}
+
+/// Live handle to a subscription created by `WebSocketClient::subscribe`.
+/// Yields `Err` items on frame-parse failures rather than silently ending
+/// the stream. Dropping it sends an unsubscribe frame and removes its
+/// routing entry so the server and connection both free the subscription's
+/// resources — mirroring how a jsonrpsee/tendermint subscription tears down
+/// when its sink is dropped.
+pub struct EventSubscription {
+    id: u64,
+    rx: mpsc::Receiver<Result<Event>>,
+    outbound: mpsc::Sender<Message>,
+    subscriptions: Arc<Mutex<HashMap<u64, EventSender>>>,
+}
+
+impl EventSubscription {
+    /// The subscription id assigned when the subscribe frame was sent
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Stream for EventSubscription {
+    type Item = Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for EventSubscription {
+    fn drop(&mut self) {
+        let id = self.id;
+        let outbound = self.outbound.clone();
+        let subscriptions = self.subscriptions.clone();
+
+        // Best-effort: can't await in Drop, so hand the unsubscribe frame
+        // and routing-table cleanup off to a detached task.
+        tokio::spawn(async move {
+            subscriptions.lock().await.remove(&id);
+
+            if let Ok(text) = serde_json::to_string(&UnsubscribeEnvelope { id, unsubscribe: true }) {
+                let _ = outbound.send(Message::Text(text)).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ws_envelope_roundtrip() {
+        let envelope = WsEnvelope {
+            id: 7,
+            payload: StreamingResponse {
+                token: "hi".to_string(),
+                is_complete: false,
+                model_id: None,
+                metrics: None,
+                error: None,
+            },
+        };
+
+        let text = serde_json::to_string(&envelope).unwrap();
+        let decoded: WsEnvelope<StreamingResponse> = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(decoded.id, 7);
+        assert_eq!(decoded.payload.token, "hi");
+    }
+
+    #[test]
+    fn test_ws_envelope_unary_response_roundtrip() {
+        let envelope = WsEnvelope {
+            id: 3,
+            payload: InferenceResponse {
+                text: "hi".to_string(),
+                model_id: None,
+                metrics: None,
+                success: true,
+                error: None,
+                metadata: None,
+                tool_calls: None,
+                finish_reason: None,
+            },
+        };
+
+        let text = serde_json::to_string(&envelope).unwrap();
+        let decoded: WsEnvelope<serde_json::Value> = serde_json::from_str(&text).unwrap();
+        let response: InferenceResponse = serde_json::from_value(decoded.payload).unwrap();
+
+        assert_eq!(decoded.id, 3);
+        assert_eq!(response.text, "hi");
+    }
+
+    #[test]
+    fn test_connection_state_defaults_to_connected_and_flips_to_failed() {
+        let (state, mut rx) = watch::channel(ConnectionState::Connected);
+        assert_eq!(*rx.borrow(), ConnectionState::Connected);
+
+        state.send_replace(ConnectionState::Failed);
+        assert_eq!(*rx.borrow_and_update(), ConnectionState::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_close_cancels_reader_and_writer_tasks() {
+        let (_outbound_tx, outbound_rx) = mpsc::channel(1);
+        let (state_tx, _) = watch::channel(ConnectionState::Connected);
+        let client = WebSocketClient {
+            config: Arc::new(RouterConfig::new("http://localhost:3000")),
+            next_id: Arc::new(AtomicU64::new(0)),
+            routes: Arc::new(Mutex::new(HashMap::new())),
+            unary_routes: Arc::new(Mutex::new(BTreeMap::new())),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            outbound: mpsc::channel(1).0,
+            state: Arc::new(state_tx),
+            cancel: CancellationToken::new(),
+        };
+        drop(outbound_rx);
+
+        assert!(!client.cancel.is_cancelled());
+        client.close().await.unwrap();
+        assert!(client.cancel.is_cancelled());
+    }
+
+    #[test]
+    fn test_subscribe_envelope_carries_a_filter_not_a_payload() {
+        let envelope = SubscribeEnvelope {
+            id: 9,
+            filter: SubscriptionFilter::new().event_type(EventType::ModelLoad),
+        };
+
+        let value: serde_json::Value = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(value["id"], 9);
+        assert!(value.get("filter").is_some());
+        assert!(value.get("payload").is_none());
+    }
+
+    #[test]
+    fn test_unsubscribe_envelope_roundtrip() {
+        let envelope = UnsubscribeEnvelope { id: 9, unsubscribe: true };
+        let value: serde_json::Value = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(value["id"], 9);
+        assert_eq!(value["unsubscribe"], true);
+    }
+
+    #[tokio::test]
+    async fn test_event_subscription_yields_events_and_propagates_parse_errors() {
+        let (tx, rx) = mpsc::channel(4);
+        let (outbound_tx, _outbound_rx) = mpsc::channel(4);
+        let mut subscription = EventSubscription {
+            id: 1,
+            rx,
+            outbound: outbound_tx,
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+        };
+
+        tx.send(Ok(Event {
+            event_type: EventType::ModelLoad,
+            model_id: Some("llama-3".to_string()),
+            latency_ms: None,
+            timestamp: chrono::DateTime::from_timestamp(0, 0).unwrap(),
+            payload: None,
+        }))
+        .await
+        .unwrap();
+        tx.send(Err(LLMRouterError::websocket("bad frame"))).await.unwrap();
+
+        let first = subscription.next().await.unwrap();
+        assert_eq!(first.unwrap().model_id, Some("llama-3".to_string()));
+
+        let second = subscription.next().await.unwrap();
+        assert!(second.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dropping_event_subscription_sends_unsubscribe_frame() {
+        let (_tx, rx) = mpsc::channel(4);
+        let (outbound_tx, mut outbound_rx) = mpsc::channel(4);
+        let subscriptions = Arc::new(Mutex::new(HashMap::new()));
+        subscriptions.lock().await.insert(1u64, mpsc::channel(1).0);
+
+        let subscription = EventSubscription {
+            id: 1,
+            rx,
+            outbound: outbound_tx,
+            subscriptions: subscriptions.clone(),
+        };
+        drop(subscription);
+
+        let Message::Text(text) = outbound_rx.recv().await.unwrap() else {
+            panic!("expected a text frame");
+        };
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["id"], 1);
+        assert_eq!(value["unsubscribe"], true);
+
+        assert!(subscriptions.lock().await.is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+//! Pluggable chat-completion backends.
+//!
+//! `Client` used to hardcode the native router's HTTP dialect for
+//! `chat_completion`/`inference`/`stream_inference`. `Provider` abstracts
+//! that dispatch behind one trait so the same call sites can also target an
+//! OpenAI-compatible server, mirroring how `protocols` abstracts the
+//! transport (HTTP/gRPC/WebSocket) a request travels over. Request/response
+//! mapping, SSE parsing, model listing, and error classification all live
+//! inside each `Provider` impl, so `InferenceRequest`/`InferenceResponse`
+//! stay the one stable surface callers see regardless of backend.
+
+mod native;
+mod openai;
+
+pub use native::NativeProvider;
+pub use openai::OpenAiCompatibleProvider;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+
+use crate::{
+    config::{ProviderConfig, RouterConfig},
+    error::Result,
+    models::{
+        ChatMessage, EmbeddingRequest, EmbeddingResponse, InferenceOptions, InferenceRequest, InferenceResponse,
+        ModelInfo, StreamingResponse,
+    },
+    protocols::http::HttpClient,
+};
+
+/// A chat-completion backend `Client` dispatches through, keyed by
+/// `RouterConfig::provider`.
+#[async_trait]
+pub trait Provider: Send + Sync {
+    /// Human-readable backend name, used in log lines and `Client`'s `Debug` impl.
+    fn name(&self) -> &'static str;
+
+    /// Perform inference from a raw prompt.
+    async fn inference(&self, request: InferenceRequest) -> Result<InferenceResponse>;
+
+    /// Stream inference tokens from a raw prompt.
+    async fn stream_inference(
+        &self,
+        request: InferenceRequest,
+    ) -> Result<BoxStream<'static, Result<StreamingResponse>>>;
+
+    /// Run a non-streaming chat completion.
+    async fn chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model_id: Option<String>,
+        options: Option<InferenceOptions>,
+    ) -> Result<InferenceResponse>;
+
+    /// List models this backend can serve.
+    async fn list_models(&self, include_unloaded: bool) -> Result<Vec<ModelInfo>>;
+
+    /// Embed one or more strings into vectors.
+    async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse>;
+}
+
+/// Build the `Provider` selected by `config.provider`. The native router's
+/// already-constructed `HttpClient` is reused as-is when `ProviderConfig::Native`
+/// is selected, rather than opening a second connection pool.
+pub fn build_provider(config: &RouterConfig, http_client: Arc<HttpClient>) -> Arc<dyn Provider> {
+    match &config.provider {
+        ProviderConfig::Native => Arc::new(NativeProvider::new(http_client)),
+        ProviderConfig::OpenAiCompatible { base_url, api_key, default_model } => {
+            Arc::new(OpenAiCompatibleProvider::new(base_url.clone(), api_key.clone(), default_model.clone()))
+        }
+    }
+}
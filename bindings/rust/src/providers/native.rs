@@ -0,0 +1,84 @@
+//! The native LLM-Runner-Router HTTP dialect, wrapping [`HttpClient`] behind
+//! the [`Provider`] trait. This is the default backend, and the only one
+//! `InferenceRequest` maps onto without reshaping the prompt.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+
+use crate::{
+    error::Result,
+    models::{
+        ChatMessage, EmbeddingRequest, EmbeddingResponse, InferenceOptions, InferenceRequest, InferenceResponse,
+        ModelInfo, StreamingResponse,
+    },
+    protocols::http::HttpClient,
+};
+
+use super::Provider;
+
+/// `Provider` impl that speaks the native router's `/api/v1/inference`
+/// family of endpoints via an already-constructed [`HttpClient`].
+pub struct NativeProvider {
+    http_client: Arc<HttpClient>,
+}
+
+impl NativeProvider {
+    pub fn new(http_client: Arc<HttpClient>) -> Self {
+        Self { http_client }
+    }
+
+    /// Flatten `messages` into the single prompt string the native
+    /// `/inference` endpoint expects.
+    fn flatten_prompt(messages: &[ChatMessage]) -> String {
+        messages
+            .iter()
+            .map(|msg| format!("{}: {}", msg.role, msg.content))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[async_trait]
+impl Provider for NativeProvider {
+    fn name(&self) -> &'static str {
+        "native"
+    }
+
+    async fn inference(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        self.http_client.inference(request).await
+    }
+
+    async fn stream_inference(
+        &self,
+        request: InferenceRequest,
+    ) -> Result<BoxStream<'static, Result<StreamingResponse>>> {
+        let stream = self.http_client.stream_inference(request).await?;
+        Ok(stream.boxed())
+    }
+
+    async fn chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model_id: Option<String>,
+        options: Option<InferenceOptions>,
+    ) -> Result<InferenceResponse> {
+        let mut request = InferenceRequest::new(Self::flatten_prompt(&messages));
+        if let Some(model_id) = model_id {
+            request = request.model_id(model_id);
+        }
+        if let Some(options) = options {
+            request = request.options(options);
+        }
+        self.inference(request).await
+    }
+
+    async fn list_models(&self, include_unloaded: bool) -> Result<Vec<ModelInfo>> {
+        self.http_client.list_models(include_unloaded).await
+    }
+
+    async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        self.http_client.embeddings(request).await
+    }
+}
@@ -0,0 +1,507 @@
+//! Generic OpenAI-compatible `/v1/chat/completions` + `/v1/models` backend,
+//! for local llama.cpp/vLLM/Ollama servers and hosted OpenAI-compatible
+//! endpoints alike. Owns its own `reqwest::Client` rather than reusing the
+//! native router's `HttpClient`, since it talks to a different `base_url`
+//! and auth scheme (`ProviderConfig::OpenAiCompatible`).
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use reqwest::Client as ReqwestClient;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{LLMRouterError, Result},
+    models::{
+        ChatMessage, EmbeddingInput, EmbeddingRequest, EmbeddingResponse, EmbeddingUsage, InferenceMetrics,
+        InferenceOptions, InferenceRequest, InferenceResponse, ModelInfo, StreamingResponse, ToolCall,
+        ToolDefinition,
+    },
+};
+
+use super::Provider;
+
+/// `Provider` impl for the OpenAI `/v1/chat/completions` + `/v1/models`
+/// dialect, configured via `ProviderConfig::OpenAiCompatible`.
+pub struct OpenAiCompatibleProvider {
+    client: ReqwestClient,
+    base_url: String,
+    api_key: Option<String>,
+    default_model: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<OpenAiMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<OpenAiTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiTool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: OpenAiToolFunction,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiToolFunction {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiMessage {
+    role: String,
+    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<OpenAiToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OpenAiToolCall {
+    id: String,
+    function: OpenAiToolCallFunction,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OpenAiToolCallFunction {
+    name: String,
+    arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+    model: Option<String>,
+    usage: Option<ChatCompletionUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatCompletionChoiceMessage,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoiceMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<OpenAiToolCall>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionUsage {
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+    total_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    choices: Vec<ChatCompletionChunkChoice>,
+    model: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunkChoice {
+    #[serde(default)]
+    delta: ChatCompletionDelta,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatCompletionDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelsListResponse {
+    data: Vec<OpenAiModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiModel {
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingsRequestBody {
+    model: String,
+    input: EmbeddingsInput,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum EmbeddingsInput {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponseBody {
+    data: Vec<EmbeddingsResponseItem>,
+    model: Option<String>,
+    usage: Option<EmbeddingsResponseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponseItem {
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponseUsage {
+    prompt_tokens: u32,
+    total_tokens: u32,
+}
+
+impl OpenAiCompatibleProvider {
+    pub fn new(base_url: impl Into<String>, api_key: Option<String>, default_model: impl Into<String>) -> Self {
+        Self {
+            client: ReqwestClient::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key,
+            default_model: default_model.into(),
+        }
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!("{}/{}", self.base_url, endpoint.trim_start_matches('/'))
+    }
+
+    fn authorize(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.api_key {
+            Some(key) => builder.bearer_auth(key),
+            None => builder,
+        }
+    }
+
+    fn build_request(
+        &self,
+        messages: &[ChatMessage],
+        model_id: &Option<String>,
+        options: &Option<InferenceOptions>,
+        stream: bool,
+        tools: &Option<Vec<ToolDefinition>>,
+        tool_choice: &Option<String>,
+    ) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: model_id.clone().unwrap_or_else(|| self.default_model.clone()),
+            messages: messages
+                .iter()
+                .map(|msg| OpenAiMessage {
+                    role: msg.role.clone(),
+                    content: msg.content.clone(),
+                    tool_calls: msg.tool_calls.as_ref().map(|calls| {
+                        calls
+                            .iter()
+                            .map(|call| OpenAiToolCall {
+                                id: call.id.clone(),
+                                function: OpenAiToolCallFunction {
+                                    name: call.name.clone(),
+                                    arguments: call.arguments.clone(),
+                                },
+                            })
+                            .collect()
+                    }),
+                    tool_call_id: msg.tool_call_id.clone(),
+                })
+                .collect(),
+            max_tokens: options.as_ref().and_then(|o| o.max_tokens),
+            temperature: options.as_ref().and_then(|o| o.temperature),
+            stream,
+            tools: tools.as_ref().map(|tools| {
+                tools
+                    .iter()
+                    .map(|tool| OpenAiTool {
+                        kind: "function",
+                        function: OpenAiToolFunction {
+                            name: tool.name.clone(),
+                            description: tool.description.clone(),
+                            parameters: tool.parameters.clone(),
+                        },
+                    })
+                    .collect()
+            }),
+            tool_choice: tool_choice.clone(),
+        }
+    }
+
+    /// Shared implementation behind `Provider::inference` and
+    /// `Provider::chat_completion`; the latter has no `tools`/`tool_choice`
+    /// of its own, so it calls this with `None, None`.
+    async fn send_chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model_id: Option<String>,
+        options: Option<InferenceOptions>,
+        tools: Option<Vec<ToolDefinition>>,
+        tool_choice: Option<String>,
+    ) -> Result<InferenceResponse> {
+        let body = self.build_request(&messages, &model_id, &options, false, &tools, &tool_choice);
+        let request = self.authorize(self.client.post(self.url("v1/chat/completions")).json(&body));
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| LLMRouterError::network("OpenAI-compatible chat completion request failed", Some(e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.ok();
+            return Err(LLMRouterError::http(
+                status.as_u16(),
+                "OpenAI-compatible chat completion failed".to_string(),
+                body,
+            ));
+        }
+
+        let parsed: ChatCompletionResponse = response
+            .json()
+            .await
+            .map_err(|e| LLMRouterError::serialization("Failed to parse OpenAI-compatible response", Some(e)))?;
+
+        let choice = parsed
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| LLMRouterError::serialization("OpenAI-compatible response had no choices", None))?;
+
+        Ok(InferenceResponse {
+            text: choice.message.content.unwrap_or_default(),
+            model_id: parsed.model,
+            metrics: parsed.usage.map(|usage| InferenceMetrics {
+                latency_ms: None,
+                tokens_generated: usage.completion_tokens,
+                tokens_per_second: None,
+                memory_used: None,
+                processing_time: None,
+                queue_time: None,
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                total_tokens: usage.total_tokens,
+                estimated_cost_usd: None,
+            }),
+            success: true,
+            error: None,
+            metadata: None,
+            tool_calls: choice.message.tool_calls.map(|calls| {
+                calls
+                    .into_iter()
+                    .map(|call| ToolCall {
+                        id: call.id,
+                        name: call.function.name,
+                        arguments: call.function.arguments,
+                    })
+                    .collect()
+            }),
+            finish_reason: choice.finish_reason,
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAiCompatibleProvider {
+    fn name(&self) -> &'static str {
+        "openai_compatible"
+    }
+
+    async fn inference(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let message = ChatMessage::user(request.prompt);
+        self.send_chat_completion(vec![message], request.model_id, request.options, request.tools, request.tool_choice)
+            .await
+    }
+
+    async fn stream_inference(
+        &self,
+        request: InferenceRequest,
+    ) -> Result<BoxStream<'static, Result<StreamingResponse>>> {
+        let message = ChatMessage::user(request.prompt);
+        self.stream_chat_completion(vec![message], request.model_id, request.options).await
+    }
+
+    async fn chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model_id: Option<String>,
+        options: Option<InferenceOptions>,
+    ) -> Result<InferenceResponse> {
+        self.send_chat_completion(messages, model_id, options, None, None).await
+    }
+
+    async fn list_models(&self, _include_unloaded: bool) -> Result<Vec<ModelInfo>> {
+        let request = self.authorize(self.client.get(self.url("v1/models")));
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| LLMRouterError::network("Failed to list OpenAI-compatible models", Some(e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.ok();
+            return Err(LLMRouterError::http(
+                status.as_u16(),
+                "Failed to list OpenAI-compatible models".to_string(),
+                body,
+            ));
+        }
+
+        let parsed: ModelsListResponse = response
+            .json()
+            .await
+            .map_err(|e| LLMRouterError::serialization("Failed to parse OpenAI-compatible models response", Some(e)))?;
+
+        Ok(parsed
+            .data
+            .into_iter()
+            .map(|model| ModelInfo {
+                id: model.id,
+                name: None,
+                format: None,
+                source: None,
+                loaded: true,
+                load_time: None,
+                memory_usage: None,
+                parameters: None,
+                version: None,
+                capabilities: None,
+            })
+            .collect())
+    }
+
+    async fn embeddings(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        let model = request.model_id.clone().unwrap_or_else(|| self.default_model.clone());
+        let input = match request.input {
+            EmbeddingInput::Single(text) => EmbeddingsInput::Single(text),
+            EmbeddingInput::Batch(texts) => EmbeddingsInput::Batch(texts),
+        };
+        let body = EmbeddingsRequestBody { model, input };
+
+        let request = self.authorize(self.client.post(self.url("v1/embeddings")).json(&body));
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| LLMRouterError::network("OpenAI-compatible embeddings request failed", Some(e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.ok();
+            return Err(LLMRouterError::http(
+                status.as_u16(),
+                "OpenAI-compatible embeddings request failed".to_string(),
+                body,
+            ));
+        }
+
+        let parsed: EmbeddingsResponseBody = response
+            .json()
+            .await
+            .map_err(|e| LLMRouterError::serialization("Failed to parse OpenAI-compatible embeddings response", Some(e)))?;
+
+        Ok(EmbeddingResponse {
+            vectors: parsed.data.into_iter().map(|item| item.embedding).collect(),
+            model: parsed.model.unwrap_or(body.model),
+            usage: parsed.usage.map(|usage| EmbeddingUsage {
+                prompt_tokens: usage.prompt_tokens,
+                total_tokens: usage.total_tokens,
+            }),
+        })
+    }
+}
+
+impl OpenAiCompatibleProvider {
+    /// Streaming counterpart of [`Provider::chat_completion`]; one HTTP chunk
+    /// can carry several `data: ` SSE events, so each chunk maps to zero or
+    /// more `StreamingResponse`s rather than one. Chunks are parsed
+    /// independently: an event split across two chunks is not reassembled
+    /// and surfaces as a parse error.
+    async fn stream_chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model_id: Option<String>,
+        options: Option<InferenceOptions>,
+    ) -> Result<BoxStream<'static, Result<StreamingResponse>>> {
+        let body = self.build_request(&messages, &model_id, &options, true, &None, &None);
+        let request = self.authorize(self.client.post(self.url("v1/chat/completions")).json(&body));
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| LLMRouterError::network("OpenAI-compatible stream request failed", Some(e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.ok();
+            return Err(LLMRouterError::http(
+                status.as_u16(),
+                "OpenAI-compatible streaming request failed".to_string(),
+                body,
+            ));
+        }
+
+        let byte_stream = response
+            .bytes_stream()
+            .map_err(|e| LLMRouterError::streaming(format!("Stream error: {}", e)));
+
+        let events = byte_stream.map(|chunk| -> Result<Vec<StreamingResponse>> {
+            let chunk = chunk?;
+            let text = String::from_utf8(chunk.to_vec())
+                .map_err(|e| LLMRouterError::streaming(format!("Invalid UTF-8 in stream: {}", e)))?;
+
+            let mut events = Vec::new();
+            for line in text.lines() {
+                let Some(data) = line.strip_prefix("data: ") else { continue };
+                if data == "[DONE]" {
+                    events.push(StreamingResponse {
+                        token: String::new(),
+                        is_complete: true,
+                        model_id: None,
+                        metrics: None,
+                        error: None,
+                    });
+                    continue;
+                }
+
+                let chunk: ChatCompletionChunk = serde_json::from_str(data)
+                    .map_err(|e| LLMRouterError::serialization("Failed to parse OpenAI-compatible stream chunk", Some(e)))?;
+
+                for choice in chunk.choices {
+                    let is_complete = choice.finish_reason.is_some();
+                    if choice.delta.content.is_some() || is_complete {
+                        events.push(StreamingResponse {
+                            token: choice.delta.content.unwrap_or_default(),
+                            is_complete,
+                            model_id: chunk.model.clone(),
+                            metrics: None,
+                            error: None,
+                        });
+                    }
+                }
+            }
+            Ok(events)
+        });
+
+        let flattened = events.flat_map(|result| match result {
+            Ok(events) => futures::stream::iter(events.into_iter().map(Ok)).left_stream(),
+            Err(e) => futures::stream::iter(vec![Err(e)]).right_stream(),
+        });
+
+        Ok(flattened.boxed())
+    }
+}
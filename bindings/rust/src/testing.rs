@@ -0,0 +1,151 @@
+//! In-process mock router for downstream integration tests, gated behind
+//! the `testing` feature.
+//!
+//! The existing test suite reaches for `mockito`, but that's a dev-only
+//! dependency of this crate and isn't available to consumers who want to
+//! exercise their own code against a fake `HttpClient` without standing up
+//! a real LLM Router. `MockRouter` spins up an in-process HTTP server,
+//! lets callers register canned responses per endpoint, optionally inject
+//! failures/latency/rate-limit headers, and hands back a fully-configured
+//! `HttpClient` pointed at it — similar in spirit to actix-web's
+//! `TestRequest` helpers.
+//!
+//! ```no_run
+//! # async fn run() -> llm_runner_router::Result<()> {
+//! use llm_runner_router::testing::{MockBehavior, MockRouter};
+//! use serde_json::json;
+//!
+//! let router = MockRouter::start().await;
+//! router.on_inference(json!({"text": "hi", "success": true})).await;
+//! router.on_list_models_with(json!([]), MockBehavior::default().with_status(503)).await;
+//!
+//! let client = router.client().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{config::RouterConfig, error::Result, protocols::http::HttpClient};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Rate-limit headers to attach to a mocked response, mirroring the
+/// `X-RateLimit-*` trio `HttpClient` already knows how to parse.
+#[derive(Debug, Clone)]
+pub struct MockRateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: u64,
+}
+
+/// Failure/latency/rate-limit behavior layered onto a canned response.
+#[derive(Debug, Clone, Default)]
+pub struct MockBehavior {
+    status: Option<u16>,
+    delay: Option<Duration>,
+    rate_limit: Option<MockRateLimit>,
+}
+
+impl MockBehavior {
+    /// Respond with `status` instead of the default 200, e.g. to simulate a
+    /// 503 and exercise retry behavior.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Delay the response by `delay`, e.g. to exercise timeout handling.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Attach `X-RateLimit-*` headers to the response.
+    pub fn with_rate_limit(mut self, rate_limit: MockRateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+}
+
+/// Builder for an in-process mock LLM Router server.
+pub struct MockRouter {
+    server: MockServer,
+}
+
+impl MockRouter {
+    /// Start an in-process mock server with no registered routes.
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// Register a canned 200 response for `POST /api/v1/inference`.
+    pub async fn on_inference(&self, response: Value) -> &Self {
+        self.on_inference_with(response, MockBehavior::default()).await
+    }
+
+    /// Register a canned response for `POST /api/v1/inference` with
+    /// injected failure/latency/rate-limit behavior.
+    pub async fn on_inference_with(&self, response: Value, behavior: MockBehavior) -> &Self {
+        self.register("POST", "/api/v1/inference", response, behavior).await
+    }
+
+    /// Register a canned 200 response for `GET /api/v1/models`.
+    pub async fn on_list_models(&self, response: Value) -> &Self {
+        self.on_list_models_with(response, MockBehavior::default()).await
+    }
+
+    /// Register a canned response for `GET /api/v1/models` with injected
+    /// failure/latency/rate-limit behavior.
+    pub async fn on_list_models_with(&self, response: Value, behavior: MockBehavior) -> &Self {
+        self.register("GET", "/api/v1/models", response, behavior).await
+    }
+
+    /// Register a canned 200 response for `POST /api/v1/models/load`.
+    pub async fn on_load_model(&self, response: Value) -> &Self {
+        self.on_load_model_with(response, MockBehavior::default()).await
+    }
+
+    /// Register a canned response for `POST /api/v1/models/load` with
+    /// injected failure/latency/rate-limit behavior.
+    pub async fn on_load_model_with(&self, response: Value, behavior: MockBehavior) -> &Self {
+        self.register("POST", "/api/v1/models/load", response, behavior).await
+    }
+
+    async fn register(&self, http_method: &str, endpoint: &str, body: Value, behavior: MockBehavior) -> &Self {
+        let mut template = ResponseTemplate::new(behavior.status.unwrap_or(200)).set_body_json(body);
+
+        if let Some(rate_limit) = &behavior.rate_limit {
+            template = template
+                .insert_header("X-RateLimit-Limit", rate_limit.limit.to_string().as_str())
+                .insert_header("X-RateLimit-Remaining", rate_limit.remaining.to_string().as_str())
+                .insert_header("X-RateLimit-Reset", rate_limit.reset.to_string().as_str());
+        }
+
+        if let Some(delay) = behavior.delay {
+            template = template.set_delay(delay);
+        }
+
+        Mock::given(method(http_method))
+            .and(path(endpoint))
+            .respond_with(template)
+            .mount(&self.server)
+            .await;
+
+        self
+    }
+
+    /// The mock server's base URL, e.g. `http://127.0.0.1:54321`.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Build an `HttpClient` pointed at this mock server.
+    pub async fn client(&self) -> Result<HttpClient> {
+        let config = Arc::new(RouterConfig::new(self.server.uri()));
+        HttpClient::new(config).await
+    }
+}
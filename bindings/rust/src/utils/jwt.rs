@@ -0,0 +1,89 @@
+//! Lazily mints and refreshes a signed JWT bearer token from `JwtAuthConfig`.
+//!
+//! Mirrors `rate_limit::RateLimiter`: one instance is shared across an
+//! `HttpClient`'s concurrent requests behind a `tokio::sync::Mutex`, so two
+//! inference calls racing a near-expired token mint at most one fresh one
+//! between them instead of each minting its own.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::config::JwtAuthConfig;
+use crate::error::{LLMRouterError, Result};
+
+struct CachedToken {
+    token: String,
+    expires_at: i64,
+}
+
+/// Mints and caches a JWT bearer token per `JwtAuthConfig`, re-minting it
+/// `refresh_skew_secs` before it actually expires.
+pub struct JwtTokenMinter {
+    config: JwtAuthConfig,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl JwtTokenMinter {
+    pub fn new(config: JwtAuthConfig) -> Self {
+        Self {
+            config,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// The current bearer token, minting or re-minting it if there's no
+    /// cached token or the cached one is within `refresh_skew_secs` of
+    /// expiry. Held under a lock so concurrent callers share one mint.
+    pub async fn bearer_token(&self) -> Result<String> {
+        let now = Self::now();
+        let mut cached = self.cached.lock().await;
+
+        if let Some(existing) = cached.as_ref() {
+            if existing.expires_at - now > self.config.refresh_skew_secs {
+                return Ok(existing.token.clone());
+            }
+        }
+
+        let (token, expires_at) = self.mint(now)?;
+        *cached = Some(CachedToken {
+            token: token.clone(),
+            expires_at,
+        });
+        Ok(token)
+    }
+
+    fn now() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64
+    }
+
+    fn mint(&self, now: i64) -> Result<(String, i64)> {
+        let expires_at = now + self.config.expires_in_secs;
+
+        let mut claims = self.config.claims.clone();
+        claims.insert("exp".to_string(), Value::from(expires_at));
+        claims.insert("iat".to_string(), Value::from(now));
+        if let Some(ref issuer) = self.config.issuer {
+            claims.insert("iss".to_string(), Value::from(issuer.clone()));
+        }
+
+        let header = Header::new(self.config.algorithm);
+        let encoding_key = match self.config.algorithm {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                EncodingKey::from_secret(&self.config.signing_key)
+            }
+            _ => EncodingKey::from_rsa_pem(&self.config.signing_key)
+                .map_err(|e| LLMRouterError::authentication(format!("Invalid RSA signing key: {}", e)))?,
+        };
+
+        let token = encode(&header, &claims, &encoding_key)
+            .map_err(|e| LLMRouterError::authentication(format!("Failed to mint JWT: {}", e)))?;
+
+        Ok((token, expires_at))
+    }
+}
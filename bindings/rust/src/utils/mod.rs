@@ -0,0 +1,7 @@
+//! Shared client-side utilities: retry/backoff, rate limiting, distributed
+//! tracing, and JWT auth
+
+pub mod jwt;
+pub mod retry;
+pub mod rate_limit;
+pub mod tracing;
@@ -0,0 +1,175 @@
+//! Client-side rate limiting
+
+use crate::config::RateLimitConfig;
+use crate::error::{LLMRouterError, Result};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token-bucket state for a single host: `tokens` is replenished at
+/// `requests_per_minute / 60` per second, capped at `burst_capacity`.
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64) -> Self {
+        Self {
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, rate_per_sec: f64, capacity: f64) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate_per_sec).min(capacity);
+        self.last_refill = now;
+    }
+}
+
+/// Client-side token-bucket rate limiter, constructed from
+/// `RouterConfig::rate_limit`. Keeps one bucket per host so the rate
+/// ceiling interacts sensibly with `ConnectionPoolConfig::max_connections_per_host`
+/// instead of one busy host starving every other host's budget. A no-op
+/// when `RateLimitConfig::enabled` is `false`.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Build a limiter from the given policy
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn capacity(&self) -> f64 {
+        self.config.burst_capacity as f64
+    }
+
+    fn refill_rate(&self) -> f64 {
+        self.config.requests_per_minute as f64 / 60.0
+    }
+
+    /// Try to remove one token for `host`, returning the wait time needed
+    /// to cover the deficit if the bucket was empty.
+    fn try_remove_token(&self, host: &str) -> Option<Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(self.capacity()));
+        bucket.refill(self.refill_rate(), self.capacity());
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return None;
+        }
+
+        let rate = self.refill_rate();
+        let deficit = (1.0 - bucket.tokens).max(0.0);
+        let wait_secs = if rate > 0.0 { deficit / rate } else { 60.0 };
+        Some(Duration::from_secs_f64(wait_secs))
+    }
+
+    /// Acquire a token for `host`, asynchronously sleeping through any
+    /// deficit instead of failing. Used by the async `HttpClient` before
+    /// it dispatches a request.
+    pub async fn acquire(&self, host: &str) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        loop {
+            match self.try_remove_token(host) {
+                None => return Ok(()),
+                Some(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Acquire a token for `host` without blocking, failing fast with
+    /// `LLMRouterError::rate_limit` (carrying the deficit as `retry_after`)
+    /// instead of sleeping. Used by `BlockingHttpClient`, which has no
+    /// async runtime to yield to while it waits out the deficit.
+    pub fn check(&self, host: &str) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        match self.try_remove_token(host) {
+            None => Ok(()),
+            Some(wait) => Err(LLMRouterError::rate_limit(
+                format!("Client-side rate limit exceeded for {}", host),
+                Some(wait),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(requests_per_minute: u32, burst_capacity: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_minute,
+            burst_capacity,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_burst_capacity_is_consumed_then_exhausted() {
+        let limiter = RateLimiter::new(config(60, 3));
+
+        assert!(limiter.try_remove_token("host").is_none());
+        assert!(limiter.try_remove_token("host").is_none());
+        assert!(limiter.try_remove_token("host").is_none());
+        assert!(limiter.try_remove_token("host").is_some());
+    }
+
+    #[test]
+    fn test_disabled_limiter_never_blocks() {
+        let mut disabled = config(1, 1);
+        disabled.enabled = false;
+        let limiter = RateLimiter::new(disabled);
+
+        for _ in 0..10 {
+            assert!(limiter.check("host").is_ok());
+        }
+    }
+
+    #[test]
+    fn test_check_returns_rate_limit_error_with_retry_after() {
+        let limiter = RateLimiter::new(config(60, 1));
+
+        assert!(limiter.check("host").is_ok());
+        let err = limiter.check("host").unwrap_err();
+        assert!(err.retry_delay().is_some());
+    }
+
+    #[test]
+    fn test_buckets_are_keyed_per_host() {
+        let limiter = RateLimiter::new(config(60, 1));
+
+        assert!(limiter.try_remove_token("a").is_none());
+        assert!(limiter.try_remove_token("a").is_some());
+        // A different host has its own untouched bucket.
+        assert!(limiter.try_remove_token("b").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_out_the_deficit() {
+        let limiter = RateLimiter::new(config(600, 1));
+
+        limiter.acquire("host").await.unwrap();
+        // Deficit is ~100ms at 10 tokens/sec; acquire should sleep it off
+        // rather than returning an error.
+        limiter.acquire("host").await.unwrap();
+    }
+}
@@ -1,37 +1,64 @@
 //! Retry utilities with exponential backoff
 
+use crate::config::ReconnectConfig;
 use crate::error::{LLMRouterError, Result};
-use std::{future::Future, time::Duration};
+use futures::stream::{self, BoxStream};
+use futures::{Stream, StreamExt};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use tokio::time::sleep;
 use tracing::{debug, warn};
 
-/// Retry a future with exponential backoff
+/// Retry a future with decorrelated-jitter exponential backoff.
+///
+/// On a retryable error that carries an explicit server-suggested delay
+/// (e.g. `Retry-After`, surfaced via `LLMRouterError::retry_delay()`), that
+/// delay is honored directly (clamped to `cap`), overriding the computed
+/// backoff as a hard floor. Otherwise the delay is re-rolled each attempt as
+/// `random_between(base_delay, min(cap, prev_delay * multiplier))` (AWS's
+/// "decorrelated jitter" formula) rather than a deterministic
+/// `base * multiplier^attempt`, so a burst of clients retrying the same
+/// failure don't all wake up in lockstep.
 pub async fn retry_with_backoff<F, Fut, T>(
     mut operation: F,
     max_retries: u32,
     base_delay: Duration,
+    cap: Duration,
+    multiplier: f64,
 ) -> Result<T>
 where
     F: FnMut() -> Fut,
     Fut: Future<Output = Result<T>>,
 {
     let mut last_error = None;
-    
+    let mut prev_delay = base_delay;
+
     for attempt in 0..=max_retries {
         match operation().await {
             Ok(result) => return Ok(result),
             Err(err) => {
                 if attempt == max_retries {
-                    // Last attempt failed
-                    return Err(err);
+                    // Last attempt failed. Only wrap with attempt-count
+                    // context if a retry was actually attempted; a
+                    // zero-retry budget or an immediately non-retryable
+                    // error should surface as-is.
+                    return Err(if attempt > 0 {
+                        LLMRouterError::retry_exhausted(attempt + 1, err)
+                    } else {
+                        err
+                    });
                 }
-                
+
                 if !err.is_retryable() {
                     // Error is not retryable
                     return Err(err);
                 }
-                
-                let delay = calculate_backoff_delay(attempt, base_delay, &err);
+
+                let delay = next_backoff_delay(prev_delay, base_delay, cap, multiplier, &err);
+                prev_delay = delay;
                 warn!(
                     "Operation failed (attempt {}/{}): {}. Retrying in {:?}",
                     attempt + 1,
@@ -39,13 +66,13 @@ where
                     err,
                     delay
                 );
-                
+
                 sleep(delay).await;
                 last_error = Some(err);
             }
         }
     }
-    
+
     // This should never be reached due to the logic above,
     // but we need to return something for the compiler
     Err(last_error.unwrap_or_else(|| {
@@ -53,29 +80,96 @@ where
     }))
 }
 
-/// Calculate the delay for the next retry attempt
-fn calculate_backoff_delay(attempt: u32, base_delay: Duration, error: &LLMRouterError) -> Duration {
-    // Check if the error suggests a specific retry delay
+/// Decide the delay before the next retry attempt.
+///
+/// Honors an explicit server-suggested delay (from `Retry-After` or a
+/// rate-limit response) when the error carries one, as a hard floor that
+/// overrides the computed backoff; otherwise re-rolls a decorrelated-jitter
+/// delay from `prev_delay`.
+fn next_backoff_delay(
+    prev_delay: Duration,
+    base_delay: Duration,
+    cap: Duration,
+    multiplier: f64,
+    error: &LLMRouterError,
+) -> Duration {
     if let Some(retry_delay) = error.retry_delay() {
-        return retry_delay;
+        return retry_delay.min(cap);
+    }
+
+    decorrelated_jitter_delay(prev_delay, base_delay, cap, multiplier)
+}
+
+/// `delay = random_between(base, min(cap, prev_delay * multiplier))`, the
+/// "decorrelated jitter" backoff formula: each attempt's delay is drawn
+/// independently rather than scaled deterministically, which avoids the
+/// thundering-herd effect of clients retrying in lockstep.
+fn decorrelated_jitter_delay(prev_delay: Duration, base_delay: Duration, cap: Duration, multiplier: f64) -> Duration {
+    let scaled_secs = (prev_delay.as_secs_f64() * multiplier).min(cap.as_secs_f64());
+    let upper_secs = scaled_secs.max(base_delay.as_secs_f64());
+    let upper = Duration::from_secs_f64(upper_secs.max(0.0));
+    let base_ms = base_delay.as_millis() as u64;
+    let upper_ms = upper.as_millis() as u64;
+
+    let delay_ms = if upper_ms <= base_ms {
+        base_ms
+    } else {
+        fastrand::u64(base_ms..=upper_ms)
+    };
+
+    Duration::from_millis(delay_ms)
+}
+
+/// `delay = min(base_delay * 2^(attempt - 1), max_delay)`, optionally
+/// redrawn uniformly from `[0, delay]` ("full jitter") per
+/// `ReconnectConfig::jitter`. Used by `Client`'s gRPC/WebSocket reconnect
+/// supervisor, which re-dials a dropped persistent connection rather than
+/// retrying a single request, so it doesn't share `retry_with_backoff`'s
+/// `is_retryable()`/`Retry-After` handling.
+pub fn reconnect_backoff_delay(policy: &ReconnectConfig, attempt: u32) -> Duration {
+    let scaled_secs = policy.base_delay.as_secs_f64() * 2f64.powi(attempt.saturating_sub(1) as i32);
+    let capped = Duration::from_secs_f64(scaled_secs.min(policy.max_delay.as_secs_f64()));
+
+    if !policy.jitter {
+        return capped;
+    }
+
+    let capped_ms = capped.as_millis() as u64;
+    if capped_ms == 0 {
+        capped
+    } else {
+        Duration::from_millis(fastrand::u64(0..=capped_ms))
     }
-    
-    // Exponential backoff: base_delay * 2^attempt with jitter
-    let exponential_delay = base_delay * 2_u32.pow(attempt);
-    
-    // Cap the delay at 60 seconds
-    let capped_delay = exponential_delay.min(Duration::from_secs(60));
-    
-    // Add jitter (Â±25% of the delay)
-    let jitter_range = capped_delay.as_millis() / 4;
-    let jitter = fastrand::u64(0..=jitter_range as u64 * 2) as i64 - jitter_range as i64;
-    let jittered_delay = (capped_delay.as_millis() as i64 + jitter).max(0) as u64;
-    
-    Duration::from_millis(jittered_delay)
 }
 
+/// Backoff delay strategy selectable on `RetryConfig::backoff_strategy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackoffStrategy {
+    /// Always wait `base_delay`, uncapped by attempt count.
+    Fixed,
+    /// `base_delay * backoff_multiplier^attempt`, capped at `max_delay` and
+    /// optionally jittered by a symmetric +/-25% per `RetryConfig::jitter`.
+    /// The default, preserving the pre-`BackoffStrategy` behavior.
+    #[default]
+    Exponential,
+    /// AWS "full jitter": `random_between(0, min(max_delay, base * multiplier^attempt))`.
+    FullJitter,
+    /// AWS "decorrelated jitter": `random_between(base_delay, min(max_delay, prev_delay * multiplier))`,
+    /// seeded with `prev_delay = base_delay`. Stateful across attempts --
+    /// see [`RetryConfig::calculate_delay_from`].
+    DecorrelatedJitter,
+}
+
+/// A caller-supplied override for whether a given error/attempt should be
+/// retried; see [`RetryConfig::retry_if`].
+type RetryPredicate = Arc<dyn Fn(&LLMRouterError, u32) -> bool + Send + Sync>;
+
+/// A caller-supplied hook invoked just before each retry's sleep; see
+/// [`RetryConfig::on_retry`].
+type RetryObserver = Arc<dyn Fn(&LLMRouterError, Duration, u32) + Send + Sync>;
+
 /// Retry configuration
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RetryConfig {
     /// Maximum number of retry attempts
     pub max_retries: u32,
@@ -85,8 +179,38 @@ pub struct RetryConfig {
     pub max_delay: Duration,
     /// Backoff multiplier
     pub backoff_multiplier: f64,
-    /// Whether to add jitter to delays
+    /// Whether to add jitter to delays (only consulted by
+    /// `BackoffStrategy::Exponential`; the other strategies are jittered,
+    /// or not, by definition)
     pub jitter: bool,
+    /// Which formula `calculate_delay`/`calculate_delay_from` use to turn an
+    /// attempt number into a delay
+    pub backoff_strategy: BackoffStrategy,
+    /// Shared client-wide retry budget to charge each retry attempt
+    /// against; see [`retry_with_budget`]. `None` (the default) means no
+    /// budget is enforced beyond `max_retries`.
+    pub retry_budget: Option<RetryTokenBucket>,
+    /// Overrides `LLMRouterError::is_retryable()` when set; see
+    /// [`RetryConfig::retry_if`].
+    retry_predicate: Option<RetryPredicate>,
+    /// Invoked just before each retry's sleep; see [`RetryConfig::on_retry`].
+    on_retry: Option<RetryObserver>,
+}
+
+impl std::fmt::Debug for RetryConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryConfig")
+            .field("max_retries", &self.max_retries)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("backoff_multiplier", &self.backoff_multiplier)
+            .field("jitter", &self.jitter)
+            .field("backoff_strategy", &self.backoff_strategy)
+            .field("retry_budget", &self.retry_budget)
+            .field("retry_predicate", &self.retry_predicate.as_ref().map(|_| "Fn(..)"))
+            .field("on_retry", &self.on_retry.as_ref().map(|_| "Fn(..)"))
+            .finish()
+    }
 }
 
 impl Default for RetryConfig {
@@ -97,7 +221,74 @@ impl Default for RetryConfig {
             max_delay: Duration::from_secs(60),
             backoff_multiplier: 2.0,
             jitter: true,
+            backoff_strategy: BackoffStrategy::default(),
+            retry_budget: None,
+            retry_predicate: None,
+            on_retry: None,
+        }
+    }
+}
+
+/// Client-wide shared retry budget, modeled as a token bucket. Prevents a
+/// widespread backend outage from letting every concurrent caller burn its
+/// full `max_retries` allowance independently and amplify load: each
+/// *retry* attempt (not the initial try) withdraws `cost_per_retry` tokens,
+/// a successful call refunds whatever that call withdrew, and once the
+/// bucket is empty `retry_with_budget` gives up immediately instead of
+/// sleeping and retrying. Cheap to clone -- clones share the same balance --
+/// so one bucket can be constructed per `Client` and passed to every call.
+#[derive(Debug, Clone)]
+pub struct RetryTokenBucket {
+    balance: Arc<Mutex<f64>>,
+    capacity: f64,
+    cost_per_retry: f64,
+}
+
+impl RetryTokenBucket {
+    /// Seed a new bucket with `capacity` tokens, charging `cost_per_retry`
+    /// tokens per retry attempt.
+    pub fn new(capacity: f64, cost_per_retry: f64) -> Self {
+        Self {
+            balance: Arc::new(Mutex::new(capacity)),
+            capacity,
+            cost_per_retry,
+        }
+    }
+
+    /// Current token balance, mostly useful for metrics/tests.
+    pub fn balance(&self) -> f64 {
+        *self.balance.lock().unwrap()
+    }
+
+    /// Try to withdraw one retry's worth of tokens. Returns `false` (leaving
+    /// the balance untouched) if the bucket can't afford it.
+    fn try_withdraw(&self) -> bool {
+        let mut balance = self.balance.lock().unwrap();
+        if *balance >= self.cost_per_retry {
+            *balance -= self.cost_per_retry;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Refund the tokens spent on `attempts_retried` retries, capped at
+    /// `capacity`. Called once a call that had retried succeeds.
+    fn refund(&self, attempts_retried: u32) {
+        if attempts_retried == 0 {
+            return;
         }
+        let mut balance = self.balance.lock().unwrap();
+        *balance = (*balance + self.cost_per_retry * attempts_retried as f64).min(self.capacity);
+    }
+}
+
+impl Default for RetryTokenBucket {
+    /// 500 tokens, 5 tokens per retry -- a burst of up to 100 retries when
+    /// the budget is full, matching the values suggested for a typical
+    /// client-wide deployment.
+    fn default() -> Self {
+        Self::new(500.0, 5.0)
     }
 }
 
@@ -136,47 +327,292 @@ impl RetryConfig {
         self.jitter = jitter;
         self
     }
-    
-    /// Calculate the delay for a given attempt
+
+    /// Attach a shared client-wide retry budget, enforced by
+    /// [`retry_with_budget`]
+    pub fn retry_budget(mut self, bucket: RetryTokenBucket) -> Self {
+        self.retry_budget = Some(bucket);
+        self
+    }
+
+    /// Select the backoff formula `calculate_delay`/`calculate_delay_from` use
+    pub fn backoff_strategy(mut self, strategy: BackoffStrategy) -> Self {
+        self.backoff_strategy = strategy;
+        self
+    }
+
+    /// Override whether a given error/attempt should be retried, instead of
+    /// relying solely on `LLMRouterError::is_retryable()`. `attempt` is the
+    /// zero-based attempt number that just failed, so e.g. a `ModelNotFound`
+    /// can be retried once (to wait for a lazy load) but not again:
+    /// `retry_if(|err, attempt| matches!(err, LLMRouterError::ModelNotFound { .. }) && attempt == 0)`.
+    pub fn retry_if(mut self, predicate: impl Fn(&LLMRouterError, u32) -> bool + Send + Sync + 'static) -> Self {
+        self.retry_predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Register a callback invoked just before each retry's sleep, with the
+    /// triggering error, the chosen delay, and the zero-based attempt number
+    /// that just failed. Useful for per-attempt metrics/tracing without
+    /// forking the retry loop.
+    pub fn on_retry(mut self, callback: impl Fn(&LLMRouterError, Duration, u32) + Send + Sync + 'static) -> Self {
+        self.on_retry = Some(Arc::new(callback));
+        self
+    }
+
+    /// Whether `error`, having just failed on the zero-based `attempt`,
+    /// should be retried: `self.retry_predicate` if one was set via
+    /// [`RetryConfig::retry_if`], otherwise `error.is_retryable()`.
+    pub fn should_retry(&self, error: &LLMRouterError, attempt: u32) -> bool {
+        match &self.retry_predicate {
+            Some(predicate) => predicate(error, attempt),
+            None => error.is_retryable(),
+        }
+    }
+
+    /// Invoke the [`RetryConfig::on_retry`] callback, if one was set, just
+    /// before sleeping for `delay` ahead of a retry.
+    fn notify_retry(&self, error: &LLMRouterError, delay: Duration, attempt: u32) {
+        if let Some(callback) = &self.on_retry {
+            callback(error, delay, attempt);
+        }
+    }
+
+    /// Calculate the delay for a given attempt per `self.backoff_strategy`.
+    ///
+    /// `BackoffStrategy::DecorrelatedJitter` needs the previous attempt's
+    /// delay as its seed rather than a pure function of `attempt`; this
+    /// always seeds it from `self.base_delay`, so a multi-attempt retry loop
+    /// using that strategy should call [`RetryConfig::calculate_delay_from`]
+    /// with the real previous delay instead, as `retry_with_config` and
+    /// `retry_with_budget` do.
     pub fn calculate_delay(&self, attempt: u32, error: Option<&LLMRouterError>) -> Duration {
+        self.calculate_delay_from(attempt, self.base_delay, error)
+    }
+
+    /// Calculate the delay for a given attempt per `self.backoff_strategy`,
+    /// seeding `DecorrelatedJitter`'s state from `prev_delay` (the delay
+    /// chosen for the previous attempt, or `self.base_delay` for the first).
+    pub fn calculate_delay_from(&self, attempt: u32, prev_delay: Duration, error: Option<&LLMRouterError>) -> Duration {
         // Check if the error suggests a specific retry delay
         if let Some(error) = error {
             if let Some(retry_delay) = error.retry_delay() {
                 return retry_delay.min(self.max_delay);
             }
         }
-        
-        // Calculate exponential backoff
+
+        match self.backoff_strategy {
+            BackoffStrategy::Fixed => self.base_delay.min(self.max_delay),
+            BackoffStrategy::Exponential => self.exponential_delay(attempt),
+            BackoffStrategy::FullJitter => self.full_jitter_delay(attempt),
+            BackoffStrategy::DecorrelatedJitter => {
+                decorrelated_jitter_delay(prev_delay, self.base_delay, self.max_delay, self.backoff_multiplier)
+            }
+        }
+    }
+
+    /// `base_delay * backoff_multiplier^attempt`, capped at `max_delay` and
+    /// optionally redrawn uniformly from `[delay - 25%, delay + 25%]` per
+    /// `self.jitter`.
+    fn exponential_delay(&self, attempt: u32) -> Duration {
         let multiplier = self.backoff_multiplier.powi(attempt as i32);
         let delay_ms = (self.base_delay.as_millis() as f64 * multiplier) as u64;
         let mut delay = Duration::from_millis(delay_ms).min(self.max_delay);
-        
-        // Add jitter if enabled
+
         if self.jitter {
             let jitter_range = delay.as_millis() / 4;
             let jitter = fastrand::u64(0..=jitter_range as u64 * 2) as i64 - jitter_range as i64;
             let jittered_delay = (delay.as_millis() as i64 + jitter).max(0) as u64;
             delay = Duration::from_millis(jittered_delay);
         }
-        
+
         delay
     }
+
+    /// "Full jitter": `random_between(0, min(max_delay, base_delay * backoff_multiplier^attempt))`.
+    fn full_jitter_delay(&self, attempt: u32) -> Duration {
+        let multiplier = self.backoff_multiplier.powi(attempt as i32);
+        let uncapped_ms = (self.base_delay.as_millis() as f64 * multiplier) as u64;
+        let capped_ms = uncapped_ms.min(self.max_delay.as_millis() as u64);
+
+        if capped_ms == 0 {
+            Duration::from_millis(0)
+        } else {
+            Duration::from_millis(fastrand::u64(0..=capped_ms))
+        }
+    }
+}
+
+/// Retry a blocking operation with exponential backoff.
+///
+/// Sibling of [`retry_with_backoff`] for the `blocking` feature: same
+/// backoff/jitter/retryability rules, but sleeps the current thread instead
+/// of awaiting, since `protocols::http_blocking` has no Tokio runtime to
+/// schedule a timer on.
+#[cfg(feature = "blocking")]
+pub fn retry_with_backoff_blocking<F, T>(
+    mut operation: F,
+    max_retries: u32,
+    base_delay: Duration,
+    cap: Duration,
+    multiplier: f64,
+) -> Result<T>
+where
+    F: FnMut() -> Result<T>,
+{
+    let mut last_error = None;
+    let mut prev_delay = base_delay;
+
+    for attempt in 0..=max_retries {
+        match operation() {
+            Ok(result) => return Ok(result),
+            Err(err) => {
+                if attempt == max_retries {
+                    return Err(if attempt > 0 {
+                        LLMRouterError::retry_exhausted(attempt + 1, err)
+                    } else {
+                        err
+                    });
+                }
+
+                if !err.is_retryable() {
+                    return Err(err);
+                }
+
+                let delay = next_backoff_delay(prev_delay, base_delay, cap, multiplier, &err);
+                prev_delay = delay;
+                warn!(
+                    "Operation failed (attempt {}/{}): {}. Retrying in {:?}",
+                    attempt + 1,
+                    max_retries + 1,
+                    err,
+                    delay
+                );
+
+                std::thread::sleep(delay);
+                last_error = Some(err);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| {
+        LLMRouterError::other("Retry loop completed without result", None::<std::io::Error>)
+    }))
 }
 
 /// Retry a future with custom configuration
 pub async fn retry_with_config<F, Fut, T>(
+    operation: F,
+    config: &RetryConfig,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    retry_with_config_observed(operation, config).await.map(|(value, _outcome)| value)
+}
+
+/// Structured record of what a retry loop spent trying to succeed, returned
+/// by [`retry_with_config_observed`] so callers can report or aggregate
+/// per-call retry cost instead of only pass/fail.
+#[derive(Debug, Clone, Default)]
+pub struct RetryOutcome {
+    /// Number of retry attempts made (0 if the first try succeeded)
+    pub attempts: u32,
+    /// Sum of every sleep the loop actually performed between attempts
+    pub total_delay: Duration,
+    /// Every error encountered, oldest first
+    pub errors: Vec<LLMRouterError>,
+}
+
+/// Like [`retry_with_config`], but returns a [`RetryOutcome`] alongside the
+/// value on success, and on exhaustion fails with
+/// `LLMRouterError::RetryExhaustedChain` carrying every attempt's error
+/// (oldest first) instead of only the last one.
+pub async fn retry_with_config_observed<F, Fut, T>(
     mut operation: F,
     config: &RetryConfig,
+) -> Result<(T, RetryOutcome)>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut outcome = RetryOutcome::default();
+    let mut prev_delay = config.base_delay;
+
+    for attempt in 0..=config.max_retries {
+        match operation().await {
+            Ok(result) => {
+                if attempt > 0 {
+                    debug!("Operation succeeded after {} retries", attempt);
+                }
+                return Ok((result, outcome));
+            }
+            Err(err) => {
+                if !config.should_retry(&err, attempt) {
+                    // The predicate rejected this error: surface it as-is
+                    // rather than wrapping it in a chain, even if earlier
+                    // attempts were retried.
+                    return Err(err);
+                }
+
+                if attempt == config.max_retries {
+                    if attempt == 0 {
+                        // A zero-retry budget: nothing was actually
+                        // retried, so surface the error as-is rather than
+                        // wrapping a chain of one.
+                        return Err(err);
+                    }
+                    outcome.attempts = attempt;
+                    outcome.errors.push(err);
+                    return Err(LLMRouterError::retry_exhausted_chain(outcome.attempts, outcome.errors));
+                }
+
+                let delay = config.calculate_delay_from(attempt, prev_delay, Some(&err));
+                prev_delay = delay;
+                config.notify_retry(&err, delay, attempt);
+                warn!(
+                    "Operation failed (attempt {}/{}): {}. Retrying in {:?}",
+                    attempt + 1,
+                    config.max_retries + 1,
+                    err,
+                    delay
+                );
+
+                outcome.total_delay += delay;
+                outcome.attempts = attempt + 1;
+                outcome.errors.push(err);
+
+                sleep(delay).await;
+            }
+        }
+    }
+
+    unreachable!("the loop above always returns by attempt == config.max_retries")
+}
+
+/// Retry a future with custom configuration, charging each retry attempt
+/// against `bucket` and giving up immediately (without sleeping, returning
+/// the triggering error as-is) once the bucket can't afford another retry --
+/// even if `config.max_retries` hasn't been reached yet. A successful call
+/// refunds whatever it withdrew. See [`RetryTokenBucket`].
+pub async fn retry_with_budget<F, Fut, T>(
+    mut operation: F,
+    config: &RetryConfig,
+    bucket: &RetryTokenBucket,
 ) -> Result<T>
 where
     F: FnMut() -> Fut,
     Fut: Future<Output = Result<T>>,
 {
     let mut last_error = None;
-    
+    let mut attempts_retried = 0;
+    let mut prev_delay = config.base_delay;
+
     for attempt in 0..=config.max_retries {
         match operation().await {
             Ok(result) => {
+                bucket.refund(attempts_retried);
                 if attempt > 0 {
                     debug!("Operation succeeded after {} retries", attempt);
                 }
@@ -186,12 +622,24 @@ where
                 if attempt == config.max_retries {
                     return Err(err);
                 }
-                
-                if !err.is_retryable() {
+
+                if !config.should_retry(&err, attempt) {
+                    return Err(err);
+                }
+
+                if !bucket.try_withdraw() {
+                    warn!(
+                        "Retry budget exhausted, giving up after {} attempt(s): {}",
+                        attempt + 1,
+                        err
+                    );
                     return Err(err);
                 }
-                
-                let delay = config.calculate_delay(attempt, Some(&err));
+                attempts_retried += 1;
+
+                let delay = config.calculate_delay_from(attempt, prev_delay, Some(&err));
+                prev_delay = delay;
+                config.notify_retry(&err, delay, attempt);
                 warn!(
                     "Operation failed (attempt {}/{}): {}. Retrying in {:?}",
                     attempt + 1,
@@ -199,24 +647,162 @@ where
                     err,
                     delay
                 );
-                
+
                 sleep(delay).await;
                 last_error = Some(err);
             }
         }
     }
-    
+
     Err(last_error.unwrap_or_else(|| {
         LLMRouterError::other("Retry loop completed without result", None::<std::io::Error>)
     }))
 }
 
+/// Internal state threaded through [`retry_stream`]'s `futures::stream::unfold`.
+/// `Idle` covers both the very first connection attempt and each reconnect;
+/// the "delaying" step between a failure and the next `Idle` is the
+/// `sleep(delay).await` inline in the unfold closure rather than a distinct
+/// variant, since unfold already suspends there.
+enum StreamRetryState<T> {
+    /// No live stream yet; the next poll calls the factory.
+    Idle { tokens_delivered: usize, attempt: u32, prev_delay: Duration },
+    /// Forwarding items from a live stream.
+    Running {
+        stream: BoxStream<'static, Result<T>>,
+        attempt: u32,
+        prev_delay: Duration,
+        tokens_delivered: usize,
+    },
+    /// Retries exhausted, the predicate rejected the error, or the stream
+    /// ended on its own; no more items follow.
+    Done,
+}
+
+/// Wrap a `StreamingResponse`-style stream factory so a failure partway
+/// through a generation reconnects instead of ending it: `factory` is called
+/// with the count of items already forwarded (so a reissued request can skip
+/// or annotate what the caller already has) to obtain a fresh
+/// `BoxStream`, and on an `Err` item that `config.should_retry` accepts, the
+/// wrapper sleeps for `config`'s backoff delay and calls `factory` again
+/// rather than surfacing the error. `Ok` items are forwarded transparently;
+/// an error only reaches the caller once `config.max_retries` (or
+/// `config.retry_if`) says to give up.
+///
+/// Conceptually this is an `Idle -> Delaying(sleep) -> Running(stream)`
+/// state machine; see [`StreamRetryState`].
+pub fn retry_stream<T, F, Fut>(factory: F, config: RetryConfig) -> impl Stream<Item = Result<T>>
+where
+    T: Send + 'static,
+    F: Fn(usize) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<BoxStream<'static, Result<T>>>> + Send + 'static,
+{
+    let factory = Arc::new(factory);
+    let initial = StreamRetryState::Idle {
+        tokens_delivered: 0,
+        attempt: 0,
+        prev_delay: config.base_delay,
+    };
+
+    stream::unfold(initial, move |mut state| {
+        let factory = factory.clone();
+        let config = config.clone();
+        async move {
+            loop {
+                match state {
+                    StreamRetryState::Idle { tokens_delivered, attempt, prev_delay } => {
+                        match factory(tokens_delivered).await {
+                            Ok(stream) => {
+                                state = StreamRetryState::Running { stream, attempt, prev_delay, tokens_delivered };
+                            }
+                            Err(err) => {
+                                if attempt >= config.max_retries || !config.should_retry(&err, attempt) {
+                                    return Some((Err(err), StreamRetryState::Done));
+                                }
+                                let delay = config.calculate_delay_from(attempt, prev_delay, Some(&err));
+                                config.notify_retry(&err, delay, attempt);
+                                sleep(delay).await;
+                                state = StreamRetryState::Idle {
+                                    tokens_delivered,
+                                    attempt: attempt + 1,
+                                    prev_delay: delay,
+                                };
+                            }
+                        }
+                    }
+                    StreamRetryState::Running { mut stream, attempt, prev_delay, tokens_delivered } => {
+                        match stream.next().await {
+                            Some(Ok(item)) => {
+                                return Some((
+                                    Ok(item),
+                                    StreamRetryState::Running {
+                                        stream,
+                                        attempt,
+                                        prev_delay,
+                                        tokens_delivered: tokens_delivered + 1,
+                                    },
+                                ));
+                            }
+                            Some(Err(err)) => {
+                                if attempt >= config.max_retries || !config.should_retry(&err, attempt) {
+                                    return Some((Err(err), StreamRetryState::Done));
+                                }
+                                let delay = config.calculate_delay_from(attempt, prev_delay, Some(&err));
+                                config.notify_retry(&err, delay, attempt);
+                                sleep(delay).await;
+                                state = StreamRetryState::Idle {
+                                    tokens_delivered,
+                                    attempt: attempt + 1,
+                                    prev_delay: delay,
+                                };
+                            }
+                            None => return None,
+                        }
+                    }
+                    StreamRetryState::Done => return None,
+                }
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
 
+    #[test]
+    fn test_reconnect_backoff_delay_doubles_and_caps() {
+        let policy = ReconnectConfig {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: false,
+        };
+
+        assert_eq!(reconnect_backoff_delay(&policy, 1), Duration::from_millis(100));
+        assert_eq!(reconnect_backoff_delay(&policy, 2), Duration::from_millis(200));
+        assert_eq!(reconnect_backoff_delay(&policy, 3), Duration::from_millis(400));
+        // 100 * 2^4 = 1600ms, clamped to the 1s cap
+        assert_eq!(reconnect_backoff_delay(&policy, 5), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_reconnect_backoff_delay_with_jitter_stays_in_bounds() {
+        let policy = ReconnectConfig {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: true,
+        };
+
+        for attempt in 1..=5 {
+            let delay = reconnect_backoff_delay(&policy, attempt);
+            assert!(delay <= Duration::from_secs(1));
+        }
+    }
+
     #[tokio::test]
     async fn test_retry_success() {
         let counter = Arc::new(AtomicU32::new(0));
@@ -236,6 +822,8 @@ mod tests {
             },
             3,
             Duration::from_millis(10),
+            Duration::from_millis(100),
+            3.0,
         ).await;
         
         assert!(result.is_ok());
@@ -258,10 +846,16 @@ mod tests {
             },
             2,
             Duration::from_millis(10),
+            Duration::from_millis(100),
+            3.0,
         ).await;
         
         assert!(result.is_err());
         assert_eq!(counter.load(Ordering::SeqCst), 3); // Initial + 2 retries
+
+        let err = result.unwrap_err();
+        assert_eq!(err.attempts(), Some(3));
+        assert!(matches!(err.last_attempt_error(), Some(LLMRouterError::Network { .. })));
     }
 
     #[tokio::test]
@@ -279,10 +873,13 @@ mod tests {
             },
             3,
             Duration::from_millis(10),
+            Duration::from_millis(100),
+            3.0,
         ).await;
-        
+
         assert!(result.is_err());
         assert_eq!(counter.load(Ordering::SeqCst), 1); // No retries for validation errors
+        assert_eq!(result.unwrap_err().attempts(), None); // Not wrapped: never actually retried
     }
 
     #[test]
@@ -301,6 +898,32 @@ mod tests {
         assert!(!config.jitter);
     }
 
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_retry_with_backoff_blocking_success() {
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        let result = retry_with_backoff_blocking(
+            move || {
+                let count = counter_clone.fetch_add(1, Ordering::SeqCst);
+                if count < 2 {
+                    Err(LLMRouterError::network("Temporary failure", None::<std::io::Error>))
+                } else {
+                    Ok("Success")
+                }
+            },
+            3,
+            Duration::from_millis(10),
+            Duration::from_millis(100),
+            3.0,
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "Success");
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+    }
+
     #[test]
     fn test_backoff_delay_calculation() {
         let config = RetryConfig::new()
@@ -316,4 +939,328 @@ mod tests {
         assert_eq!(delay1, Duration::from_millis(200));
         assert_eq!(delay2, Duration::from_millis(400));
     }
+
+    #[test]
+    fn test_decorrelated_jitter_delay_stays_within_bounds() {
+        let base = Duration::from_millis(100);
+        let cap = Duration::from_secs(1);
+        let mut prev = base;
+
+        for _ in 0..20 {
+            let delay = decorrelated_jitter_delay(prev, base, cap, 3.0);
+            assert!(delay >= base);
+            assert!(delay <= cap);
+            prev = delay;
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_delay_respects_cap() {
+        let base = Duration::from_millis(100);
+        let cap = Duration::from_millis(250);
+        let delay = decorrelated_jitter_delay(Duration::from_secs(10), base, cap, 3.0);
+
+        assert!(delay <= cap);
+    }
+
+    #[test]
+    fn test_next_backoff_delay_honors_server_retry_after() {
+        let base = Duration::from_millis(100);
+        let cap = Duration::from_secs(10);
+        let err = LLMRouterError::rate_limit("rate limited", Some(Duration::from_secs(2)));
+
+        let delay = next_backoff_delay(base, base, cap, 3.0, &err);
+        assert_eq!(delay, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_backoff_strategy_fixed_ignores_attempt() {
+        let config = RetryConfig::new()
+            .base_delay(Duration::from_millis(100))
+            .backoff_strategy(BackoffStrategy::Fixed);
+
+        assert_eq!(config.calculate_delay(0, None), Duration::from_millis(100));
+        assert_eq!(config.calculate_delay(5, None), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_backoff_strategy_full_jitter_stays_within_bounds() {
+        let config = RetryConfig::new()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1))
+            .backoff_multiplier(2.0)
+            .backoff_strategy(BackoffStrategy::FullJitter);
+
+        for attempt in 0..5 {
+            let delay = config.calculate_delay(attempt, None);
+            assert!(delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn test_backoff_strategy_decorrelated_jitter_uses_prev_delay() {
+        let config = RetryConfig::new()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1))
+            .backoff_multiplier(3.0)
+            .backoff_strategy(BackoffStrategy::DecorrelatedJitter);
+
+        let mut prev = config.base_delay;
+        for attempt in 0..20 {
+            let delay = config.calculate_delay_from(attempt, prev, None);
+            assert!(delay >= config.base_delay);
+            assert!(delay <= config.max_delay);
+            prev = delay;
+        }
+    }
+
+    #[test]
+    fn test_backoff_strategy_defaults_to_exponential() {
+        assert_eq!(RetryConfig::new().backoff_strategy, BackoffStrategy::Exponential);
+    }
+
+    #[test]
+    fn test_should_retry_defaults_to_is_retryable() {
+        let config = RetryConfig::new();
+        let retryable = LLMRouterError::network("boom", None::<std::io::Error>);
+        let not_retryable = LLMRouterError::validation("bad input", None);
+
+        assert!(config.should_retry(&retryable, 0));
+        assert!(!config.should_retry(&not_retryable, 0));
+    }
+
+    #[tokio::test]
+    async fn test_custom_retry_predicate_overrides_is_retryable() {
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        // Validation errors aren't retryable by default; force a retry on
+        // the first attempt only.
+        let config = RetryConfig::new()
+            .max_retries(3)
+            .base_delay(Duration::from_millis(1))
+            .jitter(false)
+            .retry_if(|err, attempt| matches!(err, LLMRouterError::Validation { .. }) && attempt == 0);
+
+        let result = retry_with_config(
+            move || {
+                let counter = counter_clone.clone();
+                async move {
+                    let count = counter.fetch_add(1, Ordering::SeqCst);
+                    if count == 0 {
+                        Err(LLMRouterError::validation("bad input", None))
+                    } else {
+                        Ok("Success")
+                    }
+                }
+            },
+            &config,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_on_retry_callback_fires_per_attempt() {
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+        let observed = Arc::new(AtomicU32::new(0));
+        let observed_clone = observed.clone();
+
+        let config = RetryConfig::new()
+            .max_retries(3)
+            .base_delay(Duration::from_millis(1))
+            .jitter(false)
+            .on_retry(move |_err, _delay, _attempt| {
+                observed_clone.fetch_add(1, Ordering::SeqCst);
+            });
+
+        let result = retry_with_config(
+            move || {
+                let counter = counter_clone.clone();
+                async move {
+                    let count = counter.fetch_add(1, Ordering::SeqCst);
+                    if count < 2 {
+                        Err(LLMRouterError::network("Temporary failure", None::<std::io::Error>))
+                    } else {
+                        Ok("Success")
+                    }
+                }
+            },
+            &config,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(observed.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stream_reconnects_after_mid_stream_error() {
+        let connects = Arc::new(AtomicU32::new(0));
+        let connects_clone = connects.clone();
+
+        let config = RetryConfig::new().max_retries(2).base_delay(Duration::from_millis(1)).jitter(false);
+
+        let stream = retry_stream(
+            move |tokens_delivered| {
+                let connect = connects_clone.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if connect == 0 {
+                        // First connection: yields one token then drops with
+                        // a retryable error.
+                        assert_eq!(tokens_delivered, 0);
+                        let items: Vec<Result<&'static str>> = vec![
+                            Ok("a"),
+                            Err(LLMRouterError::network("dropped", None::<std::io::Error>)),
+                        ];
+                        Ok(Box::pin(stream::iter(items)) as BoxStream<'static, Result<&'static str>>)
+                    } else {
+                        // Reconnect: the caller told us it already has 1 item.
+                        assert_eq!(tokens_delivered, 1);
+                        let items: Vec<Result<&'static str>> = vec![Ok("b")];
+                        Ok(Box::pin(stream::iter(items)) as BoxStream<'static, Result<&'static str>>)
+                    }
+                }
+            },
+            config,
+        );
+
+        let results: Vec<Result<&'static str>> = stream.collect().await;
+        let values: Vec<&str> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec!["a", "b"]);
+        assert_eq!(connects.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stream_gives_up_once_retries_exhausted() {
+        let config = RetryConfig::new().max_retries(1).base_delay(Duration::from_millis(1)).jitter(false);
+
+        let stream = retry_stream(
+            move |_tokens_delivered| async move {
+                let items: Vec<Result<&'static str>> =
+                    vec![Err(LLMRouterError::network("always fails", None::<std::io::Error>))];
+                Ok(Box::pin(stream::iter(items)) as BoxStream<'static, Result<&'static str>>)
+            },
+            config,
+        );
+
+        let results: Vec<Result<&'static str>> = stream.collect().await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_config_observed_records_attempts_and_total_delay() {
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        let config = RetryConfig::new()
+            .max_retries(3)
+            .base_delay(Duration::from_millis(10))
+            .backoff_multiplier(1.0)
+            .jitter(false);
+
+        let (value, outcome) = retry_with_config_observed(
+            move || {
+                let counter = counter_clone.clone();
+                async move {
+                    let count = counter.fetch_add(1, Ordering::SeqCst);
+                    if count < 2 {
+                        Err(LLMRouterError::network("Temporary failure", None::<std::io::Error>))
+                    } else {
+                        Ok("Success")
+                    }
+                }
+            },
+            &config,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(value, "Success");
+        assert_eq!(outcome.attempts, 2);
+        assert_eq!(outcome.errors.len(), 2);
+        // Fixed 10ms delay per retry (multiplier 1.0, no jitter) x 2 retries.
+        assert_eq!(outcome.total_delay, Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_config_observed_fails_with_error_chain() {
+        let config = RetryConfig::new().max_retries(2).base_delay(Duration::from_millis(1)).jitter(false);
+
+        let result = retry_with_config_observed(
+            move || async move {
+                Err::<&str, _>(LLMRouterError::network("Persistent failure", None::<std::io::Error>))
+            },
+            &config,
+        )
+        .await;
+
+        let err = result.unwrap_err();
+        assert_eq!(err.attempts(), Some(2));
+        assert_eq!(err.error_chain().map(|errors| errors.len()), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_budget_refunds_tokens_on_success() {
+        let bucket = RetryTokenBucket::new(100.0, 5.0);
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        let config = RetryConfig::new().max_retries(5).base_delay(Duration::from_millis(1)).jitter(false);
+
+        let result = retry_with_budget(
+            move || {
+                let counter = counter_clone.clone();
+                async move {
+                    let count = counter.fetch_add(1, Ordering::SeqCst);
+                    if count < 2 {
+                        Err(LLMRouterError::network("Temporary failure", None::<std::io::Error>))
+                    } else {
+                        Ok("Success")
+                    }
+                }
+            },
+            &config,
+            &bucket,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        // 2 retries withdrawn at 5 tokens each, then fully refunded on success.
+        assert_eq!(bucket.balance(), 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_budget_gives_up_immediately_once_exhausted() {
+        // Only enough tokens for one retry.
+        let bucket = RetryTokenBucket::new(5.0, 5.0);
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        let config = RetryConfig::new().max_retries(10).base_delay(Duration::from_millis(1)).jitter(false);
+
+        let result = retry_with_budget(
+            move || {
+                let counter = counter_clone.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Err::<&str, _>(LLMRouterError::network("Persistent failure", None::<std::io::Error>))
+                }
+            },
+            &config,
+            &bucket,
+        )
+        .await;
+
+        assert!(result.is_err());
+        // Initial attempt + 1 retry (the only one the budget could afford),
+        // then the budget is empty and the loop gives up without reaching
+        // `max_retries`.
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        assert_eq!(bucket.balance(), 0.0);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+//! OpenTelemetry OTLP trace export and W3C `traceparent` propagation.
+//!
+//! `crate::init_tracing` only sets up local stdout tracing.
+//! `init_tracing_with_config` additionally wires an OTLP exporter when
+//! `TracingConfig::otlp_endpoint` is set, and [`inject_traceparent`] lets
+//! [`crate::protocols::http::HttpClient`] attach the current span's
+//! `traceparent` header to every outgoing request so the router service can
+//! correlate its spans with the client's.
+
+use opentelemetry::global;
+use opentelemetry::propagation::Injector;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    trace::{self, Sampler},
+    Resource,
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::config::TracingConfig;
+use crate::error::{LLMRouterError, Result};
+
+/// Initialize tracing per `config`: always installs the local
+/// `tracing-subscriber` fmt layer, plus a W3C trace-context propagator, and
+/// additionally installs a batched OTLP exporter when
+/// `config.otlp_endpoint` is set.
+pub fn init_tracing_with_config(config: &TracingConfig) -> Result<()> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let filter = EnvFilter::from_default_env();
+    let fmt_layer = tracing_subscriber::fmt::layer();
+
+    let Some(endpoint) = &config.otlp_endpoint else {
+        return tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt_layer)
+            .try_init()
+            .map_err(|e| LLMRouterError::configuration(format!("Failed to install tracing subscriber: {}", e)));
+    };
+
+    let exporter = opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint.clone());
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(exporter)
+        .with_trace_config(trace::config().with_sampler(Sampler::TraceIdRatioBased(config.sampler_ratio)).with_resource(
+            Resource::new(vec![opentelemetry::KeyValue::new("service.name", config.service_name.clone())]),
+        ))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .map_err(|e| LLMRouterError::configuration(format!("Failed to install OTLP exporter: {}", e)))?;
+
+    let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(otel_layer)
+        .try_init()
+        .map_err(|e| LLMRouterError::configuration(format!("Failed to install tracing subscriber: {}", e)))
+}
+
+/// Adapts a `reqwest::header::HeaderMap` to `opentelemetry::propagation::Injector`
+/// so the text-map propagator can write `traceparent`/`tracestate` into it.
+struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
+
+impl<'a> Injector for HeaderInjector<'a> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+            reqwest::header::HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Inject the current span's W3C `traceparent` (and `tracestate`, if any) into
+/// `headers`, so the server can correlate its spans with this request's. A
+/// no-op outside of any span context.
+pub fn inject_traceparent(headers: &mut reqwest::header::HeaderMap) {
+    let context = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers));
+    });
+}
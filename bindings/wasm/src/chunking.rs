@@ -0,0 +1,115 @@
+//! Chunked transport for payloads that exceed a configured MTU.
+//!
+//! `ChunkList::split` breaks an oversized payload into `(message_id, index,
+//! total, bytes)` framed pieces; `ChunkManager` sits on the receiving side,
+//! buffering frames by `message_id` (tolerating out-of-order arrival) and
+//! only handing back the reassembled payload once every index has arrived.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+/// Default maximum chunk size, used when `RouterConfig::max_chunk_size` isn't
+/// overridden: comfortably under common constrained-transport message limits.
+pub(crate) const DEFAULT_MAX_CHUNK_SIZE: u32 = 64 * 1024;
+
+/// Maximum number of distinct messages a `ChunkManager` buffers partial
+/// frames for at once. Bounds memory when a sender drops a message
+/// mid-stream (crashed tab, cancelled request) and never sends its
+/// remaining chunks: the oldest incomplete message is evicted to make room,
+/// the same FIFO-eviction idea `LruCache` uses for the response cache.
+const MAX_PENDING_MESSAGES: usize = 64;
+
+/// One framed piece of a chunked message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ChunkFrame {
+    pub(crate) message_id: u64,
+    pub(crate) index: u32,
+    pub(crate) total: u32,
+    pub(crate) bytes: Vec<u8>,
+}
+
+/// Splits payloads into MTU-sized `ChunkFrame`s.
+pub(crate) struct ChunkList;
+
+impl ChunkList {
+    /// Split `payload` into frames of at most `max_chunk_size` bytes each,
+    /// all sharing `message_id`. A payload that already fits in one chunk
+    /// still comes back as a single-frame (`total == 1`) list, so callers
+    /// don't need a separate non-chunked code path.
+    pub(crate) fn split(message_id: u64, payload: &[u8], max_chunk_size: u32) -> Vec<ChunkFrame> {
+        let max_chunk_size = max_chunk_size.max(1) as usize;
+        let total = payload.chunks(max_chunk_size).count().max(1) as u32;
+
+        payload
+            .chunks(max_chunk_size)
+            .enumerate()
+            .map(|(index, bytes)| ChunkFrame {
+                message_id,
+                index: index as u32,
+                total,
+                bytes: bytes.to_vec(),
+            })
+            .collect()
+    }
+}
+
+/// Reassembles chunked messages on the receiving side, one `message_id` at a time.
+#[derive(Default)]
+pub(crate) struct ChunkManager {
+    partial: HashMap<u64, HashMap<u32, Vec<u8>>>,
+    totals: HashMap<u64, u32>,
+    /// Insertion order of still-incomplete `message_id`s, oldest first, used
+    /// to evict once `MAX_PENDING_MESSAGES` is exceeded.
+    pending_order: VecDeque<u64>,
+}
+
+impl ChunkManager {
+    pub(crate) fn new() -> Self {
+        ChunkManager::default()
+    }
+
+    /// Buffer `frame`. Returns the fully reassembled payload (in index
+    /// order) once every index for its `message_id` has arrived, discarding
+    /// that message's buffered state; otherwise returns `None`.
+    ///
+    /// Duplicate frames for an already-seen index are idempotent (the later
+    /// one simply overwrites the earlier), and frames may arrive out of
+    /// order — reassembly only looks at `index`, never arrival order.
+    pub(crate) fn ingest(&mut self, frame: ChunkFrame) -> Option<Vec<u8>> {
+        if !self.partial.contains_key(&frame.message_id) {
+            self.pending_order.push_back(frame.message_id);
+            self.evict_oldest_if_over_capacity();
+        }
+
+        self.totals.insert(frame.message_id, frame.total);
+        let received = self.partial.entry(frame.message_id).or_default();
+        received.insert(frame.index, frame.bytes);
+
+        if received.len() as u32 != frame.total {
+            return None;
+        }
+
+        let received = self.partial.remove(&frame.message_id)?;
+        self.totals.remove(&frame.message_id);
+        self.pending_order.retain(|id| *id != frame.message_id);
+
+        let mut assembled = Vec::new();
+        for index in 0..frame.total {
+            assembled.extend(received.get(&index)?);
+        }
+        Some(assembled)
+    }
+
+    /// Drop the oldest still-incomplete message once more than
+    /// `MAX_PENDING_MESSAGES` are buffered, so a sender that never finishes
+    /// sending one message's chunks can't grow this map without bound.
+    fn evict_oldest_if_over_capacity(&mut self) {
+        while self.pending_order.len() > MAX_PENDING_MESSAGES {
+            if let Some(oldest) = self.pending_order.pop_front() {
+                self.partial.remove(&oldest);
+                self.totals.remove(&oldest);
+            }
+        }
+    }
+}
@@ -8,17 +8,32 @@ use crate::{
 
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{console, AbortController, Headers, Request, RequestInit, RequestMode, Response};
-use futures::stream::Stream;
+use web_sys::{console, AbortController, Headers, Request, RequestInit, Response};
+use futures::{channel::oneshot, stream::{self, Stream}};
+use futures_util::{SinkExt, StreamExt};
+use gloo_net::websocket::{futures::WebSocket, Message};
+use std::cell::RefCell;
 use std::collections::HashMap;
 
 type Result<T> = std::result::Result<T, LLMRouterError>;
 
+/// Number of recent inference responses kept in the client-side cache.
+const RESPONSE_CACHE_CAPACITY: usize = 32;
+
 /// Main LLM Router client for WebAssembly
 #[wasm_bindgen]
 pub struct LLMRouterClient {
     config: RouterConfig,
     session_id: Option<String>,
+    /// Recent cacheable responses, keyed by `InferenceRequest::content_hash()`.
+    response_cache: RefCell<LruCache<u64, InferenceResponse>>,
+    /// Waiters coalesced onto an in-flight request sharing the same hash.
+    in_flight: RefCell<HashMap<u64, Vec<oneshot::Sender<std::result::Result<InferenceResponse, String>>>>>,
+    /// Monotonically increasing nonce fed into each signed request, so a
+    /// replayed request (same timestamp) is still rejectable by the server.
+    signing_nonce: std::cell::Cell<u64>,
+    /// Monotonically increasing id assigned to each outgoing chunked message.
+    chunk_message_id: std::cell::Cell<u64>,
 }
 
 #[wasm_bindgen]
@@ -27,10 +42,14 @@ impl LLMRouterClient {
     #[wasm_bindgen(constructor)]
     pub fn new(config: RouterConfig) -> LLMRouterClient {
         console::log_1(&"Creating LLM Router WASM client".into());
-        
+
         LLMRouterClient {
             config,
             session_id: None,
+            response_cache: RefCell::new(LruCache::new(RESPONSE_CACHE_CAPACITY)),
+            in_flight: RefCell::new(HashMap::new()),
+            signing_nonce: std::cell::Cell::new(0),
+            chunk_message_id: std::cell::Cell::new(0),
         }
     }
 
@@ -122,7 +141,26 @@ impl LLMRouterClient {
         }
     }
 
-    /// Perform inference
+    /// Unload a model
+    #[wasm_bindgen(js_name = "unloadModel")]
+    pub async fn unload_model(&self, model_id: String, force: Option<bool>) -> Result<JsValue, JSError> {
+        let body = serde_wasm_bindgen::to_value(&serde_json::json!({
+            "model_id": model_id,
+            "force": force.unwrap_or(false),
+        }))
+        .map_err(|e| JSError::from(LLMRouterError::Serialization {
+            message: "Failed to serialize unload request".to_string()
+        }))?;
+
+        let result = self.make_request("POST", "models/unload", Some(body)).await;
+        match result {
+            Ok(value) => Ok(value),
+            Err(e) => Err(JSError::from(e)),
+        }
+    }
+
+    /// Perform inference, deduping concurrent identical requests and serving
+    /// from the response cache where possible.
     #[wasm_bindgen(js_name = "inference")]
     pub async fn inference(&self, mut request: InferenceRequest) -> Result<InferenceResponse, JSError> {
         // Add session ID if available
@@ -130,20 +168,65 @@ impl LLMRouterClient {
             request.session_id = self.session_id.clone();
         }
 
-        let body = request.to_object()
-            .map_err(|e| JSError::from(LLMRouterError::Serialization {
-                message: "Failed to serialize inference request".to_string()
-            }))?;
-        
-        match self.make_request("POST", "inference", Some(body)).await {
-            Ok(response_value) => {
-                InferenceResponse::from_object(&response_value)
-                    .map_err(|e| JSError::from(LLMRouterError::Serialization {
-                        message: "Failed to parse inference response".to_string()
-                    }))
+        if !request.is_cacheable() {
+            return self.run_inference(request).await.map_err(JSError::from);
+        }
+
+        let hash = request.content_hash();
+
+        if let Some(cached) = self.response_cache.borrow_mut().get(&hash) {
+            return Ok(cached);
+        }
+
+        {
+            let mut in_flight = self.in_flight.borrow_mut();
+            if let Some(waiters) = in_flight.get_mut(&hash) {
+                let (tx, rx) = oneshot::channel();
+                waiters.push(tx);
+                drop(in_flight);
+                return rx
+                    .await
+                    .map_err(|_| JSError::from(LLMRouterError::Other {
+                        message: "In-flight request was dropped before completing".to_string(),
+                    }))?
+                    .map_err(|message| JSError::from(LLMRouterError::Other { message }));
+            }
+            in_flight.insert(hash, Vec::new());
+        }
+
+        let result = self.run_inference(request).await;
+
+        let waiters = self.in_flight.borrow_mut().remove(&hash).unwrap_or_default();
+        match &result {
+            Ok(response) => {
+                self.response_cache.borrow_mut().insert(hash, response.clone());
+                for waiter in waiters {
+                    let _ = waiter.send(Ok(response.clone()));
+                }
+            }
+            Err(err) => {
+                let message = err.to_string();
+                for waiter in waiters {
+                    let _ = waiter.send(Err(message.clone()));
+                }
             }
-            Err(e) => Err(JSError::from(e)),
         }
+
+        result.map_err(JSError::from)
+    }
+
+    /// Issue the inference request over HTTP with no dedup/caching applied.
+    async fn run_inference(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let body = request.to_object()
+            .map_err(|e| LLMRouterError::Serialization {
+                message: "Failed to serialize inference request".to_string()
+            })?;
+
+        let response_value = self.send_possibly_chunked("inference", body).await?;
+        InferenceResponse::from_object(&response_value)
+            .map_err(|e| LLMRouterError::Serialization {
+                message: "Failed to parse inference response".to_string()
+            })
     }
 
     /// Quick inference with minimal setup
@@ -186,37 +269,242 @@ impl LLMRouterClient {
             .map_err(|e| JSError::from(LLMRouterError::Serialization {
                 message: "Failed to serialize stream request".to_string()
             }))?;
-        
-        match self.make_stream_request("POST", "inference/stream", Some(body)).await {
-            Ok(reader) => Ok(reader),
-            Err(e) => Err(JSError::from(e)),
+
+        let result = match self.config.stream_transport {
+            StreamTransport::Http => self.make_stream_request("POST", "inference/stream", Some(body)).await,
+            StreamTransport::WebSocket => self.make_websocket_stream_request(body).await,
+        };
+
+        result.map_err(JSError::from)
+    }
+
+    /// Start streaming inference over a WebSocket regardless of the
+    /// configured `stream_transport`, for callers that specifically want the
+    /// full-duplex socket so they can keep pushing follow-up turns via
+    /// [`StreamReader::send`] — e.g. an interactive, multi-turn session kept
+    /// alive under one `session_id`. Unlike [`Self::stream_inference`], this
+    /// never falls back to the one-shot SSE-over-HTTP path.
+    #[wasm_bindgen(js_name = "streamInferenceWs")]
+    pub async fn stream_inference_ws(&self, mut request: InferenceRequest) -> Result<StreamReader, JSError> {
+        if let Some(ref mut options) = request.options {
+            options.set_stream(Some(true));
+        } else {
+            let mut options = InferenceOptions::new();
+            options.set_stream(Some(true));
+            request.set_options(Some(options));
+        }
+
+        if request.session_id.is_none() {
+            request.session_id = self.session_id.clone();
         }
+
+        let body = request.to_object()
+            .map_err(|e| JSError::from(LLMRouterError::Serialization {
+                message: "Failed to serialize stream request".to_string()
+            }))?;
+
+        self.make_websocket_stream_request(body).await.map_err(JSError::from)
     }
 
-    /// Make HTTP request
+    /// Run streaming inference and deliver tokens to JS.
+    ///
+    /// If `callback` is provided, it's invoked with each decoded
+    /// `StreamingResponse` (including the final `is_complete = true` marker)
+    /// as it arrives, and this method resolves to `null` once the stream
+    /// ends. Without a callback, the caller instead gets the `StreamReader`
+    /// back to pull chunks manually via `readChunk()`, e.g. from a JS async
+    /// iterator.
+    #[wasm_bindgen(js_name = "inferStream")]
+    pub async fn infer_stream(
+        &self,
+        request: InferenceRequest,
+        callback: Option<js_sys::Function>,
+    ) -> Result<Option<StreamReader>, JSError> {
+        let mut reader = self.stream_inference(request).await?;
+
+        let Some(callback) = callback else {
+            return Ok(Some(reader));
+        };
+
+        while let Some(chunk) = reader.read_chunk().await? {
+            let is_complete = chunk.is_complete;
+            let value = chunk.to_object().unwrap_or(JsValue::NULL);
+            let _ = callback.call1(&JsValue::undefined(), &value);
+            if is_complete {
+                break;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Check the configured `NetworkingPolicy` for `url`'s host before any request
+    /// is dispatched, rewriting through a registered proxy entry if one exists.
+    fn apply_networking_policy(&self, url: &str) -> Result<String> {
+        let parsed = web_sys::Url::new(url)
+            .map_err(|_| LLMRouterError::Validation {
+                message: format!("Invalid request URL: {}", url),
+            })?;
+        let host = parsed.host();
+
+        if !self.config.networking_policy.is_allowed(&host) {
+            return Err(LLMRouterError::Validation {
+                message: format!("Host '{}' is not permitted by the networking policy", host),
+            });
+        }
+
+        if let Some(proxy_url) = self.config.networking_policy.proxy_for(&host) {
+            let path_and_query = format!("{}{}", parsed.pathname(), parsed.search());
+            return Ok(format!("{}{}", proxy_url.trim_end_matches('/'), path_and_query));
+        }
+
+        Ok(url.to_string())
+    }
+
+    /// Draw the next nonce in this client's monotonically increasing sequence.
+    fn next_nonce(&self) -> u64 {
+        let nonce = self.signing_nonce.get();
+        self.signing_nonce.set(nonce + 1);
+        nonce
+    }
+
+    /// Draw the next id in this client's chunked-message sequence.
+    fn next_chunk_message_id(&self) -> u64 {
+        let id = self.chunk_message_id.get();
+        self.chunk_message_id.set(id + 1);
+        id
+    }
+
+    /// Send `body` to `endpoint`, transparently splitting it across sequential
+    /// `{endpoint}/chunk` requests when it exceeds `config.max_chunk_size`.
+    /// Only the final frame's response carries the real answer; a body that
+    /// already fits under the limit bypasses chunking and is sent as-is.
+    async fn send_possibly_chunked(&self, endpoint: &str, body: JsValue) -> Result<JsValue> {
+        let body_string = js_sys::JSON::stringify(&body)
+            .map_err(|e| LLMRouterError::Serialization {
+                message: "Failed to stringify request body".to_string()
+            })?;
+        let body_bytes = body_string.as_string().unwrap_or_default().into_bytes();
+
+        if body_bytes.len() <= self.config.max_chunk_size as usize {
+            return self.make_request("POST", endpoint, Some(body)).await;
+        }
+
+        let message_id = self.next_chunk_message_id();
+        let frames = crate::chunking::ChunkList::split(message_id, &body_bytes, self.config.max_chunk_size);
+        let chunk_endpoint = format!("{}/chunk", endpoint.trim_end_matches('/'));
+
+        let mut response = None;
+        for frame in frames {
+            let frame_body = serde_wasm_bindgen::to_value(&frame)
+                .map_err(|e| LLMRouterError::Serialization {
+                    message: "Failed to serialize chunk frame".to_string()
+                })?;
+            response = Some(self.make_request("POST", &chunk_endpoint, Some(frame_body)).await?);
+        }
+
+        response.ok_or_else(|| LLMRouterError::Other {
+            message: "Chunked request produced no frames".to_string(),
+        })
+    }
+
+    /// When a signing key is registered, canonicalize `(method, path, body)`
+    /// with a fresh timestamp/nonce and attach the Ed25519 signature and
+    /// public key as headers. A no-op when no signing key is configured.
+    fn apply_signing_headers(&self, headers: &Headers, method: &str, path: &str, body: &[u8]) -> Result<()> {
+        let Some(signing_key) = self.config.signing_key.as_ref() else {
+            return Ok(());
+        };
+
+        let timestamp_ms = js_sys::Date::now();
+        let nonce = self.next_nonce();
+        let message = crate::signing::canonicalize(method, path, timestamp_ms, nonce, body);
+
+        let set_header = |name: &str, value: &str| {
+            headers.set(name, value).map_err(|_| LLMRouterError::JavaScript {
+                message: format!("Failed to set {} header", name),
+            })
+        };
+
+        set_header("X-Signature", &signing_key.sign_hex(&message))?;
+        set_header("X-Signature-Public-Key", &signing_key.public_key_hex())?;
+        set_header("X-Signature-Timestamp", &(timestamp_ms as u64).to_string())?;
+        set_header("X-Signature-Nonce", &nonce.to_string())?;
+
+        Ok(())
+    }
+
+    /// Apply every `config.extra_headers` entry (e.g. a gateway key such as
+    /// `X-Api-Gateway-Key`) registered via `RouterConfig::addExtraHeader`.
+    fn apply_extra_headers(&self, headers: &Headers) -> Result<()> {
+        for (name, value) in &self.config.extra_headers {
+            headers.append(name, value).map_err(|e| LLMRouterError::JavaScript {
+                message: format!("Failed to set extra header '{}'", name),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Make HTTP request, retrying transient failures up to `config.max_retries`
+    /// times with exponential backoff and jitter. Only `GET` requests and the
+    /// inference endpoints are retried — `models/load` and `models/unload`
+    /// are not idempotent, so a failure there is surfaced immediately rather
+    /// than risked a second time.
     async fn make_request(
         &self,
         method: &str,
         endpoint: &str,
         body: Option<JsValue>,
+    ) -> Result<JsValue> {
+        let retry_safe = method == "GET" || endpoint.starts_with("inference");
+        let mut last_err = None;
+
+        for attempt in 0..=self.config.max_retries {
+            match self.make_request_once(method, endpoint, body.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    if attempt == self.config.max_retries || !retry_safe || !err.is_retryable() {
+                        return Err(err);
+                    }
+                    let delay = backoff_delay_ms(attempt, self.config.base_retry_delay_ms, &err, self.config.retry_jitter);
+                    sleep(delay).await.map_err(LLMRouterError::from)?;
+                    last_err = Some(err);
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or(LLMRouterError::Other {
+            message: "Retry loop completed without result".to_string(),
+        }))
+    }
+
+    /// Perform a single HTTP request attempt (no retries).
+    async fn make_request_once(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: Option<JsValue>,
     ) -> Result<JsValue> {
         let url = format!("{}/api/v1/{}", self.config.base_url, endpoint.trim_start_matches('/'));
-        
+        let url = self.apply_networking_policy(&url)?;
+
         let mut opts = RequestInit::new();
         opts.method(method);
-        opts.mode(RequestMode::Cors);
+        opts.mode(self.config.request_mode.into());
+        opts.credentials(self.config.credentials.into());
 
         // Set headers
         let headers = Headers::new()
             .map_err(|e| LLMRouterError::JavaScript {
                 message: "Failed to create headers".to_string()
             })?;
-        
+
         headers.set("Content-Type", "application/json")
             .map_err(|e| LLMRouterError::JavaScript {
                 message: "Failed to set content type".to_string()
             })?;
-        
+
         // Add authentication header if API key is provided
         if let Some(ref api_key) = self.config.api_key {
             headers.set("Authorization", &format!("Bearer {}", api_key))
@@ -224,21 +512,35 @@ impl LLMRouterClient {
                     message: "Failed to set authorization header".to_string()
                 })?;
         }
-        
+
         headers.set("User-Agent", &self.config.user_agent)
             .map_err(|e| LLMRouterError::JavaScript {
                 message: "Failed to set user agent".to_string()
             })?;
 
+        self.apply_extra_headers(&headers)?;
+
+        // Stringify the body up front (if any) so it can both be signed and sent.
+        let body_string = match body {
+            Some(body) => Some(
+                js_sys::JSON::stringify(&body)
+                    .map_err(|e| LLMRouterError::Serialization {
+                        message: "Failed to stringify request body".to_string()
+                    })?,
+            ),
+            None => None,
+        };
+
+        let body_bytes = body_string
+            .as_ref()
+            .and_then(|s| s.as_string())
+            .unwrap_or_default();
+        self.apply_signing_headers(&headers, method, &endpoint_path(&url), body_bytes.as_bytes())?;
+
         opts.headers(&headers);
 
-        // Set body if provided
-        if let Some(body) = body {
-            let body_string = js_sys::JSON::stringify(&body)
-                .map_err(|e| LLMRouterError::Serialization {
-                    message: "Failed to stringify request body".to_string()
-                })?;
-            opts.body(Some(&body_string));
+        if let Some(ref body_string) = body_string {
+            opts.body(Some(body_string));
         }
 
         // Create abort controller for timeout
@@ -299,47 +601,23 @@ impl LLMRouterClient {
             Ok(json)
         } else {
             let status = resp.status();
-            let status_text = resp.status_text();
-            
-            // Try to get error message from response body
-            let error_message = if let Ok(text_promise) = resp.text() {
-                if let Ok(text_value) = JsFuture::from(text_promise).await {
-                    if let Some(text) = text_value.as_string() {
-                        // Try to parse as JSON to get error field
-                        if let Ok(json) = js_sys::JSON::parse(&text) {
-                            if let Ok(error_field) = js_sys::Reflect::get(&json, &"error".into()) {
-                                if let Some(error_str) = error_field.as_string() {
-                                    error_str
-                                } else {
-                                    text
-                                }
-                            } else {
-                                text
-                            }
-                        } else {
-                            text
-                        }
-                    } else {
-                        status_text.clone()
-                    }
-                } else {
-                    status_text.clone()
-                }
+            let retry_after_ms = retry_after_ms_from(&resp);
+
+            let body_text = if let Ok(text_promise) = resp.text() {
+                JsFuture::from(text_promise)
+                    .await
+                    .ok()
+                    .and_then(|v| v.as_string())
+                    .unwrap_or_default()
             } else {
-                status_text.clone()
+                String::new()
             };
 
-            let error = match status {
-                401 | 403 => LLMRouterError::Authentication { message: error_message },
-                404 => LLMRouterError::ModelNotFound { model_id: "unknown".to_string() },
-                408 => LLMRouterError::Timeout { message: error_message },
-                429 => LLMRouterError::RateLimit { message: error_message },
-                400 => LLMRouterError::Validation { message: error_message },
-                500..=599 => LLMRouterError::Inference { message: error_message },
-                _ => LLMRouterError::Network { message: error_message },
-            };
+            if status == 408 {
+                return Err(LLMRouterError::Timeout { message: body_text });
+            }
 
-            Err(error)
+            Err(LLMRouterError::from_response(status, &body_text, retry_after_ms))
         }
     }
 
@@ -351,27 +629,29 @@ impl LLMRouterClient {
         body: Option<JsValue>,
     ) -> Result<StreamReader> {
         let url = format!("{}/api/v1/{}", self.config.base_url, endpoint.trim_start_matches('/'));
-        
+        let url = self.apply_networking_policy(&url)?;
+
         let mut opts = RequestInit::new();
         opts.method(method);
-        opts.mode(RequestMode::Cors);
+        opts.mode(self.config.request_mode.into());
+        opts.credentials(self.config.credentials.into());
 
         // Set headers
         let headers = Headers::new()
             .map_err(|e| LLMRouterError::JavaScript {
                 message: "Failed to create headers".to_string()
             })?;
-        
+
         headers.set("Content-Type", "application/json")
             .map_err(|e| LLMRouterError::JavaScript {
                 message: "Failed to set content type".to_string()
             })?;
-        
+
         headers.set("Accept", "text/event-stream")
             .map_err(|e| LLMRouterError::JavaScript {
                 message: "Failed to set accept header".to_string()
             })?;
-        
+
         if let Some(ref api_key) = self.config.api_key {
             headers.set("Authorization", &format!("Bearer {}", api_key))
                 .map_err(|e| LLMRouterError::JavaScript {
@@ -379,14 +659,28 @@ impl LLMRouterClient {
                 })?;
         }
 
+        self.apply_extra_headers(&headers)?;
+
+        let body_string = match body {
+            Some(body) => Some(
+                js_sys::JSON::stringify(&body)
+                    .map_err(|e| LLMRouterError::Serialization {
+                        message: "Failed to stringify request body".to_string()
+                    })?,
+            ),
+            None => None,
+        };
+
+        let body_bytes = body_string
+            .as_ref()
+            .and_then(|s| s.as_string())
+            .unwrap_or_default();
+        self.apply_signing_headers(&headers, method, &endpoint_path(&url), body_bytes.as_bytes())?;
+
         opts.headers(&headers);
 
-        if let Some(body) = body {
-            let body_string = js_sys::JSON::stringify(&body)
-                .map_err(|e| LLMRouterError::Serialization {
-                    message: "Failed to stringify request body".to_string()
-                })?;
-            opts.body(Some(&body_string));
+        if let Some(ref body_string) = body_string {
+            opts.body(Some(body_string));
         }
 
         let request = Request::new_with_str_and_init(&url, &opts)
@@ -414,48 +708,206 @@ impl LLMRouterClient {
             }
         } else {
             let status = resp.status();
-            let error_message = format!("Stream request failed: {} {}", status, resp.status_text());
-            
-            Err(match status {
-                401 | 403 => LLMRouterError::Authentication { message: error_message },
-                404 => LLMRouterError::ModelNotFound { model_id: "unknown".to_string() },
-                408 => LLMRouterError::Timeout { message: error_message },
-                429 => LLMRouterError::RateLimit { message: error_message },
-                _ => LLMRouterError::Network { message: error_message },
-            })
+            let retry_after_ms = retry_after_ms_from(&resp);
+
+            if status == 408 {
+                return Err(LLMRouterError::Timeout {
+                    message: format!("Stream request failed: {} {}", status, resp.status_text()),
+                });
+            }
+
+            Err(LLMRouterError::from_response(status, "", retry_after_ms))
         }
     }
+
+    /// Open a persistent WebSocket to the streaming endpoint, send the
+    /// already-serialized request body once, and hand back a [`StreamReader`]
+    /// that pulls tokens off the socket as they arrive — the `StreamTransport::WebSocket`
+    /// counterpart to [`Self::make_stream_request`]'s SSE-over-HTTP path.
+    async fn make_websocket_stream_request(&self, body: JsValue) -> Result<StreamReader> {
+        let url = format!("{}/api/v1/inference/stream", self.config.base_url.trim_end_matches('/'));
+        let url = self.apply_networking_policy(&url)?;
+        let url = to_websocket_url(&url, self.config.upgrade_to_wss)?;
+
+        let socket = WebSocket::open(&url).map_err(|e| LLMRouterError::Network {
+            message: format!("Failed to open streaming socket: {}", e),
+        })?;
+        let (mut write, read) = socket.split();
+
+        let payload = js_sys::JSON::stringify(&body)
+            .map_err(|e| LLMRouterError::Serialization {
+                message: "Failed to stringify stream request".to_string()
+            })?
+            .as_string()
+            .unwrap_or_default();
+
+        write.send(Message::Text(payload)).await.map_err(|e| LLMRouterError::Network {
+            message: format!("Failed to send stream request over socket: {}", e),
+        })?;
+
+        Ok(StreamReader::from_websocket(read, write))
+    }
+}
+
+/// Rewrite `url`'s scheme to `ws`/`wss` for the WebSocket streaming
+/// transport, upgrading to `wss` when `upgrade_to_wss` is set or the URL was
+/// already `https`, matching `RouterConfig::upgrade_to_https`'s mixed-content
+/// avoidance for the HTTP transport.
+fn to_websocket_url(url: &str, upgrade_to_wss: bool) -> Result<String> {
+    let parsed = web_sys::Url::new(url)
+        .map_err(|_| LLMRouterError::Validation {
+            message: format!("Invalid stream URL: {}", url),
+        })?;
+
+    let secure = upgrade_to_wss || parsed.protocol() == "https:";
+    let scheme = if secure { "wss" } else { "ws" };
+
+    Ok(format!("{}://{}{}{}", scheme, parsed.host(), parsed.pathname(), parsed.search()))
+}
+
+/// Extract the path-and-query portion of `url`, for use in the signed
+/// request's canonical form. Falls back to the full URL if it doesn't parse.
+fn endpoint_path(url: &str) -> String {
+    web_sys::Url::new(url)
+        .map(|parsed| format!("{}{}", parsed.pathname(), parsed.search()))
+        .unwrap_or_else(|_| url.to_string())
+}
+
+/// Parse a `Retry-After` response header (seconds, per RFC 9110) into milliseconds.
+fn retry_after_ms_from(resp: &Response) -> Option<u32> {
+    resp.headers()
+        .get("Retry-After")
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse::<u32>().ok())
+        .map(|seconds| seconds.saturating_mul(1000))
 }
 
-/// Stream reader for handling streaming responses
+/// Stream reader for handling streaming responses.
+///
+/// Buffers decoded text across reads so an SSE event split across two or more
+/// underlying fetch chunks — or several events packed into one chunk — is
+/// parsed correctly rather than dropped or rejected as malformed JSON. Events
+/// are delimited by a blank line (`\n\n` or `\r\n\r\n`); within an event, every
+/// `data:` line is unwrapped and joined before being parsed as JSON. A
+/// trailing partial multi-byte UTF-8 sequence at the end of a chunk is held
+/// back in `pending_bytes` rather than decoded, since splitting there would
+/// otherwise corrupt the next character.
+///
+/// Backed by either a fetch `ReadableStream` (the `StreamTransport::Http`
+/// path) or a WebSocket (`StreamTransport::WebSocket`) — exactly one of
+/// `reader`/`socket_read` is set, and [`Self::read_chunk`] pulls from
+/// whichever is present. Either way the caller sees the same pull-based
+/// `readChunk()` interface.
 #[wasm_bindgen]
 pub struct StreamReader {
     #[wasm_bindgen(skip)]
     reader: Option<web_sys::ReadableStreamDefaultReader>,
+    #[wasm_bindgen(skip)]
+    socket_read: Option<futures_util::stream::SplitStream<WebSocket>>,
+    /// Send half of the streaming socket, kept alive so the connection isn't
+    /// half-closed while `socket_read` still has frames in flight. The
+    /// initial request is sent once from
+    /// [`LLMRouterClient::make_websocket_stream_request`]; [`Self::send`]
+    /// lets a caller push further messages (e.g. follow-up turns in the same
+    /// session) over this same socket. `None` for an HTTP-backed reader.
+    #[wasm_bindgen(skip)]
+    socket_write: Option<futures_util::stream::SplitSink<WebSocket, Message>>,
+    /// Decoded-but-not-yet-parsed SSE text, spanning as many fetch chunks as
+    /// it takes to see a full event (`\n\n`/`\r\n\r\n`-terminated).
+    #[wasm_bindgen(skip)]
+    buffer: String,
+    /// Raw bytes at the tail of the last read that didn't decode as valid
+    /// UTF-8 on their own -- a multi-byte character split across two fetch
+    /// chunks -- held back until the rest of the character arrives.
+    #[wasm_bindgen(skip)]
+    pending_bytes: Vec<u8>,
+    #[wasm_bindgen(skip)]
+    pending: std::collections::VecDeque<StreamingResponse>,
+    #[wasm_bindgen(skip)]
+    done: bool,
+    #[wasm_bindgen(skip)]
+    chunk_manager: crate::chunking::ChunkManager,
 }
 
 #[wasm_bindgen]
 impl StreamReader {
     pub(crate) fn new(stream: web_sys::ReadableStream) -> Self {
         let reader = stream.get_reader().dyn_into().ok();
-        StreamReader { reader }
+        StreamReader {
+            reader,
+            socket_read: None,
+            socket_write: None,
+            buffer: String::new(),
+            pending_bytes: Vec::new(),
+            pending: std::collections::VecDeque::new(),
+            done: false,
+            chunk_manager: crate::chunking::ChunkManager::new(),
+        }
+    }
+
+    /// Build a reader that pulls frames off an already-connected WebSocket
+    /// instead of a fetch `ReadableStream`. `write` is retained only to keep
+    /// the socket's send half alive; the stream request itself was already
+    /// written before this reader was constructed.
+    pub(crate) fn from_websocket(
+        read: futures_util::stream::SplitStream<WebSocket>,
+        write: futures_util::stream::SplitSink<WebSocket, Message>,
+    ) -> Self {
+        StreamReader {
+            reader: None,
+            socket_read: Some(read),
+            socket_write: Some(write),
+            buffer: String::new(),
+            pending_bytes: Vec::new(),
+            pending: std::collections::VecDeque::new(),
+            done: false,
+            chunk_manager: crate::chunking::ChunkManager::new(),
+        }
     }
 
-    /// Read next chunk from stream
+    /// Read the next parsed `StreamingResponse`: pulls and decodes as many
+    /// underlying fetch chunks as needed until a complete SSE frame is
+    /// available (`StreamTransport::Http`), or the next WebSocket frame
+    /// (`StreamTransport::WebSocket`). Returns `None` once the stream is
+    /// fully drained.
     #[wasm_bindgen(js_name = "readChunk")]
     pub async fn read_chunk(&mut self) -> Result<Option<StreamingResponse>, JSError> {
-        if let Some(ref mut reader) = self.reader {
+        loop {
+            if let Some(chunk) = self.pending.pop_front() {
+                return Ok(Some(chunk));
+            }
+
+            if self.done {
+                return Ok(None);
+            }
+
+            if self.socket_read.is_some() {
+                self.read_socket_frame().await?;
+                continue;
+            }
+
+            let reader = match self.reader.as_mut() {
+                Some(reader) => reader,
+                None => {
+                    return Err(JSError::from(LLMRouterError::Network {
+                        message: "Stream reader not available".to_string(),
+                    }))
+                }
+            };
+
             match JsFuture::from(reader.read()).await {
                 Ok(result) => {
-                    let done = js_sys::Reflect::get(&result, &"done".into())
+                    let is_done = js_sys::Reflect::get(&result, &"done".into())
                         .map_err(|e| JSError::from(LLMRouterError::JavaScript {
                             message: "Failed to get done property".to_string()
                         }))?
                         .as_bool()
                         .unwrap_or(true);
 
-                    if done {
-                        return Ok(None);
+                    if is_done {
+                        self.finish();
+                        continue;
                     }
 
                     let value = js_sys::Reflect::get(&result, &"value".into())
@@ -463,39 +915,60 @@ impl StreamReader {
                             message: "Failed to get value property".to_string()
                         }))?;
 
-                    // Convert Uint8Array to string
                     let uint8_array = js_sys::Uint8Array::new(&value);
                     let mut bytes = vec![0; uint8_array.length() as usize];
                     uint8_array.copy_to(&mut bytes);
-                    
-                    let text = String::from_utf8(bytes)
-                        .map_err(|e| JSError::from(LLMRouterError::Serialization {
-                            message: "Invalid UTF-8 in stream".to_string()
-                        }))?;
-
-                    // Parse Server-Sent Events format
-                    for line in text.lines() {
-                        if let Some(data) = line.strip_prefix("data: ") {
-                            let chunk: StreamingResponse = serde_json::from_str(data)
-                                .map_err(|e| JSError::from(LLMRouterError::Serialization {
-                                    message: "Failed to parse stream chunk".to_string()
-                                }))?;
-                            return Ok(Some(chunk));
-                        }
-                    }
-
-                    // If no data found, try again
-                    Ok(None)
+                    self.decode_and_buffer(bytes);
+                    self.drain_complete_events();
+                }
+                Err(e) => {
+                    // Surface mid-stream transport errors as a terminal
+                    // `StreamingResponse` rather than failing the whole read,
+                    // so a caller draining tokens one at a time still sees it.
+                    self.done = true;
+                    self.pending.push_back(StreamingResponse {
+                        token: String::new(),
+                        is_complete: true,
+                        model_id: None,
+                        error: Some(LLMRouterError::from(e).to_string()),
+                        chunk_message_id: None,
+                        chunk_index: None,
+                        chunk_total: None,
+                    });
                 }
-                Err(e) => Err(JSError::from(LLMRouterError::from(e))),
             }
-        } else {
-            Err(JSError::from(LLMRouterError::Network {
-                message: "Stream reader not available".to_string()
-            }))
         }
     }
 
+    /// Push a follow-up message over the underlying WebSocket, for an
+    /// interactive session that keeps sending turns on the same connection
+    /// (e.g. a multi-turn conversation sharing one `session_id`). Only valid
+    /// for a reader backed by [`LLMRouterClient::stream_inference_ws`]; an
+    /// HTTP-backed reader has no send half and returns a `Network` error.
+    #[wasm_bindgen(js_name = "send")]
+    pub async fn send(&mut self, request: InferenceRequest) -> Result<(), JSError> {
+        let Some(write) = self.socket_write.as_mut() else {
+            return Err(JSError::from(LLMRouterError::Network {
+                message: "Stream reader has no send half; it is not backed by a WebSocket".to_string(),
+            }));
+        };
+
+        let body = request.to_object()
+            .map_err(|e| JSError::from(LLMRouterError::Serialization {
+                message: "Failed to serialize follow-up message".to_string()
+            }))?;
+        let payload = js_sys::JSON::stringify(&body)
+            .map_err(|e| JSError::from(LLMRouterError::Serialization {
+                message: "Failed to stringify follow-up message".to_string()
+            }))?
+            .as_string()
+            .unwrap_or_default();
+
+        write.send(Message::Text(payload)).await.map_err(|e| JSError::from(LLMRouterError::Network {
+            message: format!("Failed to send follow-up message over socket: {}", e),
+        }))
+    }
+
     /// Close the stream
     #[wasm_bindgen(js_name = "close")]
     pub async fn close(&mut self) -> Result<(), JSError> {
@@ -504,6 +977,216 @@ impl StreamReader {
                 .await
                 .map_err(|e| JSError::from(LLMRouterError::from(e)))?;
         }
+
+        if let Some(mut write) = self.socket_write.take() {
+            self.socket_read = None;
+            write.close().await.map_err(|e| JSError::from(LLMRouterError::Network {
+                message: format!("Failed to close streaming socket: {}", e),
+            }))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StreamReader {
+    /// Adapt this reader into a `futures::Stream` of parsed chunks, for
+    /// non-browser WASM callers and Rust-side consumers that want
+    /// `StreamExt` combinators (`map`, `take_while`, `for_each`, ...) instead
+    /// of manually looping on [`Self::read_chunk`]. Terminates cleanly once
+    /// `read_chunk` returns `Ok(None)`. The `readChunk`/`close` JS API is
+    /// untouched; this is an additional, Rust-only way to drain the reader.
+    pub fn into_stream(self) -> impl Stream<Item = Result<StreamingResponse>> {
+        stream::unfold(Some(self), |state| async move {
+            let mut reader = state?;
+            match reader.read_chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(reader))),
+                Ok(None) => None,
+                Err(e) => Some((
+                    Err(LLMRouterError::Other { message: e.message() }),
+                    None,
+                )),
+            }
+        })
+    }
+
+    /// Pull one frame off `socket_read`, parsing it as a (possibly chunked)
+    /// `StreamingResponse` and queuing the result — the WebSocket-transport
+    /// counterpart to [`Self::drain_complete_events`]'s SSE parsing.
+    async fn read_socket_frame(&mut self) -> Result<(), JSError> {
+        let Some(socket) = self.socket_read.as_mut() else {
+            return Err(JSError::from(LLMRouterError::Network {
+                message: "Stream reader not available".to_string(),
+            }));
+        };
+
+        match socket.next().await {
+            Some(Ok(Message::Text(text))) => self.parse_socket_message(&text),
+            Some(Ok(Message::Bytes(bytes))) => {
+                if let Ok(text) = String::from_utf8(bytes) {
+                    self.parse_socket_message(&text);
+                }
+            }
+            Some(Err(e)) => {
+                self.done = true;
+                self.pending.push_back(StreamingResponse {
+                    token: String::new(),
+                    is_complete: true,
+                    model_id: None,
+                    error: Some(format!("Streaming socket error: {}", e)),
+                    chunk_message_id: None,
+                    chunk_index: None,
+                    chunk_total: None,
+                });
+            }
+            None => self.finish(),
+        }
+
         Ok(())
     }
+
+    /// Parse one WebSocket frame's text as a `StreamingResponse` (frames
+    /// carry raw JSON, unlike the SSE path's `data: ...`-prefixed lines) and
+    /// route it through the same chunk-reassembly path as `parse_sse_event`.
+    fn parse_socket_message(&mut self, text: &str) {
+        match serde_json::from_str::<StreamingResponse>(text) {
+            Ok(chunk) => self.ingest_streaming_response(chunk),
+            Err(e) => self.pending.push_back(StreamingResponse {
+                token: String::new(),
+                is_complete: true,
+                model_id: None,
+                error: Some(format!("Failed to parse stream chunk: {}", e)),
+                chunk_message_id: None,
+                chunk_index: None,
+                chunk_total: None,
+            }),
+        }
+    }
+
+    /// Decode as much of `bytes` as is valid UTF-8, prepending any
+    /// `pending_bytes` left over from the previous read, and append the
+    /// decoded text onto `self.buffer`. A trailing partial multi-byte
+    /// sequence (a character split across two fetch chunks) is held back in
+    /// `pending_bytes` instead of being decoded.
+    fn decode_and_buffer(&mut self, bytes: Vec<u8>) {
+        let mut combined = std::mem::take(&mut self.pending_bytes);
+        combined.extend(bytes);
+
+        match std::str::from_utf8(&combined) {
+            Ok(text) => self.buffer.push_str(text),
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                self.buffer
+                    .push_str(std::str::from_utf8(&combined[..valid_up_to]).unwrap_or_default());
+                self.pending_bytes = combined[valid_up_to..].to_vec();
+            }
+        }
+    }
+
+    /// Split `self.buffer` on complete SSE events (a blank-line boundary,
+    /// `\n\n` or `\r\n\r\n`), parsing each into a queued `StreamingResponse`.
+    /// Any trailing partial event is left in the buffer for the next chunk.
+    fn drain_complete_events(&mut self) {
+        while let Some((event, rest_start)) = Self::find_event_boundary(&self.buffer) {
+            let event = event.to_string();
+            self.buffer.drain(..rest_start);
+            self.parse_sse_event(&event);
+            if self.done {
+                break;
+            }
+        }
+    }
+
+    /// Locate the earliest blank-line boundary in `buffer`, returning the
+    /// event text before it and the byte offset where the remaining buffer
+    /// content starts.
+    fn find_event_boundary(buffer: &str) -> Option<(&str, usize)> {
+        let crlf = buffer.find("\r\n\r\n").map(|pos| (pos, pos + 4));
+        let lf = buffer.find("\n\n").map(|pos| (pos, pos + 2));
+        let (pos, rest_start) = match (crlf, lf) {
+            (Some(a), Some(b)) => if a.0 <= b.0 { a } else { b },
+            (a, b) => a.or(b)?,
+        };
+        Some((&buffer[..pos], rest_start))
+    }
+
+    /// Parse one complete SSE event: gather every `data:` line, strip the
+    /// prefix and optional leading space, and join them with `\n`. A joined
+    /// payload of the literal `[DONE]` sentinel marks the stream finished
+    /// without being parsed as JSON; otherwise it's parsed into a queued
+    /// `StreamingResponse`.
+    fn parse_sse_event(&mut self, event: &str) {
+        let data = event
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: ").or_else(|| line.strip_prefix("data:")))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if data.trim().is_empty() {
+            return;
+        }
+
+        if data.trim() == "[DONE]" {
+            self.done = true;
+            return;
+        }
+
+        match serde_json::from_str::<StreamingResponse>(&data) {
+            Ok(chunk) => self.ingest_streaming_response(chunk),
+            Err(e) => self.pending.push_back(StreamingResponse {
+                token: String::new(),
+                is_complete: true,
+                model_id: None,
+                error: Some(format!("Failed to parse stream chunk: {}", e)),
+                chunk_message_id: None,
+                chunk_index: None,
+                chunk_total: None,
+            }),
+        }
+    }
+
+    /// Route a freshly parsed `StreamingResponse` through chunk reassembly.
+    /// Frames carrying no `chunk_message_id` are queued as-is; frames that are
+    /// part of a chunked message are buffered until every index for that
+    /// message has arrived, at which point the reassembled token is queued.
+    fn ingest_streaming_response(&mut self, chunk: StreamingResponse) {
+        let Some(message_id) = chunk.chunk_message_id else {
+            self.pending.push_back(chunk);
+            return;
+        };
+
+        let frame = crate::chunking::ChunkFrame {
+            message_id,
+            index: chunk.chunk_index.unwrap_or(0),
+            total: chunk.chunk_total.unwrap_or(1),
+            bytes: chunk.token.into_bytes(),
+        };
+
+        if let Some(bytes) = self.chunk_manager.ingest(frame) {
+            self.pending.push_back(StreamingResponse {
+                token: String::from_utf8_lossy(&bytes).into_owned(),
+                is_complete: chunk.is_complete,
+                model_id: chunk.model_id,
+                error: chunk.error,
+                chunk_message_id: None,
+                chunk_index: None,
+                chunk_total: None,
+            });
+        }
+    }
+
+    /// Flush any trailing buffered event and make sure a final
+    /// `is_complete = true` marker is queued, then mark the stream exhausted.
+    fn finish(&mut self) {
+        let trailing = std::mem::take(&mut self.buffer);
+        if !trailing.trim().is_empty() {
+            self.parse_sse_event(&trailing);
+        }
+
+        if !matches!(self.pending.back(), Some(chunk) if chunk.is_complete) {
+            self.pending.push_back(StreamingResponse::new(String::new(), true));
+        }
+
+        self.done = true;
+    }
 }
\ No newline at end of file
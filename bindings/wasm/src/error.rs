@@ -13,7 +13,11 @@ pub enum LLMRouterError {
     Timeout { message: String },
 
     #[error("Rate limit exceeded: {message}")]
-    RateLimit { message: String },
+    RateLimit {
+        message: String,
+        /// Server-specified delay (from a `Retry-After` header), if any
+        retry_after_ms: Option<u32>,
+    },
 
     #[error("Authentication error: {message}")]
     Authentication { message: String },
@@ -38,6 +42,24 @@ pub enum LLMRouterError {
 }
 
 impl LLMRouterError {
+    /// Build a variant from an HTTP status code and raw response body, parsing
+    /// common provider error envelopes (`{"error": {"message", "type", "code"}}`)
+    /// and threading a server `Retry-After` value through to `retry_delay_ms()`.
+    pub fn from_response(status: u16, body: &str, retry_after_ms: Option<u32>) -> Self {
+        let (message, model_id) = parse_error_envelope(body, status);
+
+        match status {
+            401 | 403 => LLMRouterError::Authentication { message },
+            404 => LLMRouterError::ModelNotFound {
+                model_id: model_id.unwrap_or_else(|| "unknown".to_string()),
+            },
+            429 => LLMRouterError::RateLimit { message, retry_after_ms },
+            400 | 422 => LLMRouterError::Validation { message },
+            500..=599 => LLMRouterError::Network { message },
+            _ => LLMRouterError::Network { message },
+        }
+    }
+
     /// Check if the error is retryable
     pub fn is_retryable(&self) -> bool {
         matches!(
@@ -51,7 +73,7 @@ impl LLMRouterError {
     /// Get suggested retry delay in milliseconds
     pub fn retry_delay_ms(&self) -> Option<u32> {
         match self {
-            LLMRouterError::RateLimit { .. } => Some(60000), // 1 minute
+            LLMRouterError::RateLimit { retry_after_ms, .. } => retry_after_ms.or(Some(60000)), // server value, else 1 minute
             LLMRouterError::Timeout { .. } => Some(2000),    // 2 seconds
             LLMRouterError::Network { .. } => Some(1000),    // 1 second
             _ => None,
@@ -59,6 +81,33 @@ impl LLMRouterError {
     }
 }
 
+/// Parse a provider error envelope of the shape `{"error": {"message", "type", "code"}}`,
+/// returning `(message, model_id)`. Falls back to the raw body (or a generic
+/// message) when it doesn't match that shape.
+fn parse_error_envelope(body: &str, status: u16) -> (String, Option<String>) {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(body) {
+        if let Some(error) = value.get("error") {
+            let message = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .map(|s| s.to_string());
+            let model_id = error
+                .get("code")
+                .and_then(|c| c.as_str())
+                .map(|s| s.to_string());
+            if let Some(message) = message {
+                return (message, model_id);
+            }
+        }
+    }
+
+    if body.is_empty() {
+        (format!("Request failed with status {}", status), None)
+    } else {
+        (body.to_string(), None)
+    }
+}
+
 // Convert to JsValue for JavaScript interop
 impl From<LLMRouterError> for JsValue {
     fn from(error: LLMRouterError) -> Self {
@@ -179,7 +228,7 @@ pub fn create_error(error_type: &str, message: &str) -> JSError {
     let error = match error_type {
         "NetworkError" => LLMRouterError::Network { message: message.to_string() },
         "TimeoutError" => LLMRouterError::Timeout { message: message.to_string() },
-        "RateLimitError" => LLMRouterError::RateLimit { message: message.to_string() },
+        "RateLimitError" => LLMRouterError::RateLimit { message: message.to_string(), retry_after_ms: None },
         "AuthenticationError" => LLMRouterError::Authentication { message: message.to_string() },
         "ValidationError" => LLMRouterError::Validation { message: message.to_string() },
         "ModelNotFoundError" => LLMRouterError::ModelNotFound { model_id: message.to_string() },
@@ -19,19 +19,22 @@ macro_rules! console_log {
     ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
 }
 
-// Global allocator for WASM
-#[global_allocator]
-static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
-
+mod chunking;
 mod client;
 mod error;
+mod memory;
 mod models;
+mod protocol;
+mod signing;
+mod streaming;
 mod utils;
 mod worker;
 
 pub use client::*;
 pub use error::*;
+pub use memory::*;
 pub use models::*;
+pub use streaming::*;
 pub use worker::*;
 
 // Re-export for convenience
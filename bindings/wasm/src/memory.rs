@@ -0,0 +1,117 @@
+//! Allocator instrumentation and memory statistics for the WASM build
+//!
+//! Mirrors the native crate's `MemoryManager`/`TrackingAlloc`
+//! (`src/native/src/memory.rs`) so browser embedders get the same
+//! allocated/peak-byte observability N-API callers already have, plus the
+//! current `WebAssembly.Memory` page count so they can watch for growth
+//! across inference calls.
+
+use std::alloc::{GlobalAlloc, Layout};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wasm_bindgen::prelude::*;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+fn track_allocation(size: usize) {
+    let allocated = ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(allocated, Ordering::Relaxed);
+}
+
+fn track_deallocation(size: usize) {
+    ALLOCATED_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// `GlobalAlloc` wrapper that keeps `ALLOCATED_BYTES`/`PEAK_BYTES` in sync
+/// with every real allocation, so `get_memory_stats()` reflects live usage
+/// regardless of which backing allocator is selected below.
+pub struct TrackingAlloc<A> {
+    inner: A,
+}
+
+impl<A> TrackingAlloc<A> {
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            track_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        track_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                track_allocation(new_size - layout.size());
+            } else {
+                track_deallocation(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+// `wee_alloc` is unmaintained but smaller; `dlmalloc` is the default since
+// it's actively maintained and faster. Sizing vs. throughput is the
+// embedder's call, made at build time with the `wee_alloc` feature.
+#[cfg(feature = "wee_alloc")]
+#[global_allocator]
+static ALLOC: TrackingAlloc<wee_alloc::WeeAlloc> = TrackingAlloc::new(wee_alloc::WeeAlloc::INIT);
+
+#[cfg(not(feature = "wee_alloc"))]
+#[global_allocator]
+static ALLOC: TrackingAlloc<dlmalloc::GlobalDlmalloc> =
+    TrackingAlloc::new(dlmalloc::GlobalDlmalloc);
+
+/// Allocator and linear-memory statistics, mirroring the native crate's
+/// `MemoryStats` (`src/native/src/lib.rs`).
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct MemoryStats {
+    allocated_bytes: u32,
+    peak_bytes: u32,
+    memory_pages: u32,
+}
+
+#[wasm_bindgen]
+impl MemoryStats {
+    #[wasm_bindgen(getter, js_name = "allocatedBytes")]
+    pub fn allocated_bytes(&self) -> u32 {
+        self.allocated_bytes
+    }
+
+    #[wasm_bindgen(getter, js_name = "peakBytes")]
+    pub fn peak_bytes(&self) -> u32 {
+        self.peak_bytes
+    }
+
+    /// Number of 64KiB pages currently backing this module's
+    /// `WebAssembly.Memory` (`core::arch::wasm32::memory_size(0)`).
+    #[wasm_bindgen(getter, js_name = "memoryPages")]
+    pub fn memory_pages(&self) -> u32 {
+        self.memory_pages
+    }
+}
+
+/// Current allocator counters plus the live `WebAssembly.Memory` page count,
+/// so JS callers can watch a loaded model's footprint and detect leaks across
+/// inference calls.
+#[wasm_bindgen]
+pub fn get_memory_stats() -> MemoryStats {
+    MemoryStats {
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) as u32,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed) as u32,
+        memory_pages: core::arch::wasm32::memory_size(0) as u32,
+    }
+}
@@ -4,6 +4,160 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use std::collections::HashMap;
 
+use crate::error::JSError;
+
+/// Which outbound hosts the client is permitted to reach.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkingAccessMode {
+    /// No restrictions; any host may be contacted.
+    All,
+    /// Only hosts registered via `NetworkingPolicy::allowHost` may be contacted.
+    Allowlist,
+    /// All outbound requests are rejected.
+    None,
+}
+
+/// A proxy rewrite rule: requests to `host` are routed through `proxy_url` instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProxyRule {
+    host: String,
+    proxy_url: String,
+}
+
+/// Outbound networking policy, consulted before every request so embedders can
+/// sandbox which inference endpoints the WASM module is permitted to reach.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkingPolicy {
+    mode: NetworkingAccessMode,
+    #[wasm_bindgen(skip)]
+    allowed_hosts: Vec<String>,
+    #[wasm_bindgen(skip)]
+    proxies: Vec<ProxyRule>,
+}
+
+#[wasm_bindgen]
+impl NetworkingPolicy {
+    #[wasm_bindgen(constructor)]
+    pub fn new(mode: NetworkingAccessMode) -> NetworkingPolicy {
+        NetworkingPolicy {
+            mode,
+            allowed_hosts: Vec::new(),
+            proxies: Vec::new(),
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mode(&self) -> NetworkingAccessMode {
+        self.mode
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_mode(&mut self, mode: NetworkingAccessMode) {
+        self.mode = mode;
+    }
+
+    /// Add `host` to the allowlist.
+    #[wasm_bindgen(js_name = "allowHost")]
+    pub fn allow_host(&mut self, host: String) {
+        self.allowed_hosts.push(host);
+    }
+
+    /// Route requests to `host` through `proxy_url` instead of contacting it directly.
+    #[wasm_bindgen(js_name = "addProxy")]
+    pub fn add_proxy(&mut self, host: String, proxy_url: String) {
+        self.proxies.push(ProxyRule { host, proxy_url });
+    }
+}
+
+impl NetworkingPolicy {
+    /// Check whether `host` is permitted by this policy.
+    pub(crate) fn is_allowed(&self, host: &str) -> bool {
+        match self.mode {
+            NetworkingAccessMode::All => true,
+            NetworkingAccessMode::None => false,
+            NetworkingAccessMode::Allowlist => self.allowed_hosts.iter().any(|h| h == host),
+        }
+    }
+
+    /// Look up a registered proxy rewrite for `host`, if any.
+    pub(crate) fn proxy_for(&self, host: &str) -> Option<&str> {
+        self.proxies
+            .iter()
+            .find(|rule| rule.host == host)
+            .map(|rule| rule.proxy_url.as_str())
+    }
+
+    /// Validate that every registered proxy URL is well-formed.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        for rule in &self.proxies {
+            web_sys::Url::new(&rule.proxy_url)
+                .map_err(|_| format!("Invalid proxy URL for host '{}'", rule.host))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for NetworkingPolicy {
+    fn default() -> Self {
+        NetworkingPolicy::new(NetworkingAccessMode::All)
+    }
+}
+
+/// `web_sys::RequestMode` variant used for every outgoing fetch. Defaults to
+/// `Cors`; `SameOrigin` and `NoCors` matter mainly for deployments sitting
+/// behind an auth proxy on the same origin as the page.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RequestModeConfig {
+    Cors,
+    SameOrigin,
+    NoCors,
+}
+
+impl From<RequestModeConfig> for web_sys::RequestMode {
+    fn from(mode: RequestModeConfig) -> Self {
+        match mode {
+            RequestModeConfig::Cors => web_sys::RequestMode::Cors,
+            RequestModeConfig::SameOrigin => web_sys::RequestMode::SameOrigin,
+            RequestModeConfig::NoCors => web_sys::RequestMode::NoCors,
+        }
+    }
+}
+
+/// `web_sys::RequestCredentials` variant used for every outgoing fetch.
+/// `Include` sends and stores cookies even across origins, for deployments
+/// that authenticate via a session cookie instead of a bearer token.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CredentialsMode {
+    Omit,
+    SameOrigin,
+    Include,
+}
+
+impl From<CredentialsMode> for web_sys::RequestCredentials {
+    fn from(mode: CredentialsMode) -> Self {
+        match mode {
+            CredentialsMode::Omit => web_sys::RequestCredentials::Omit,
+            CredentialsMode::SameOrigin => web_sys::RequestCredentials::SameOrigin,
+            CredentialsMode::Include => web_sys::RequestCredentials::Include,
+        }
+    }
+}
+
+/// Transport `LLMRouterClient::streamInference` uses to deliver tokens.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamTransport {
+    /// Server-Sent Events over a chunked HTTP response (the default).
+    Http,
+    /// A persistent WebSocket opened once per stream and fed tokens as they
+    /// arrive, avoiding per-chunk HTTP overhead.
+    WebSocket,
+}
+
 /// Configuration for the router client
 #[wasm_bindgen]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +172,46 @@ pub struct RouterConfig {
     pub api_key: Option<String>,
     /// User agent string
     pub user_agent: String,
+    /// Rewrite `http://` base URLs to `https://` when the embedding page is itself
+    /// served over HTTPS, to avoid mixed-content failures
+    pub upgrade_to_https: bool,
+    /// Capability-style sandbox over which hosts the client may contact
+    #[wasm_bindgen(skip)]
+    pub networking_policy: NetworkingPolicy,
+    /// Opt-in Ed25519 request signing, for servers that authenticate clients
+    /// cryptographically instead of (or in addition to) `api_key`. Never
+    /// serialized or printed: see `crate::signing::SigningSecret`.
+    #[wasm_bindgen(skip)]
+    #[serde(skip)]
+    pub(crate) signing_key: Option<crate::signing::SigningSecret>,
+    /// Maximum body size, in bytes, before the client transparently splits a
+    /// request (and reassembles a response) using the chunked transport.
+    pub max_chunk_size: u32,
+    /// Transport `streamInference` uses to receive tokens.
+    pub stream_transport: StreamTransport,
+    /// Rewrite `ws://` stream URLs to `wss://` when the embedding page is
+    /// itself served over HTTPS, mirroring `upgrade_to_https` for the
+    /// `StreamTransport::WebSocket` transport.
+    pub upgrade_to_wss: bool,
+    /// Base delay, in milliseconds, for the exponential backoff between
+    /// retry attempts: the first retry waits roughly `base_retry_delay_ms`,
+    /// and each further retry doubles it (`base_retry_delay_ms * 2^(N-1)`
+    /// for the Nth retry, capped at 60s).
+    pub base_retry_delay_ms: u32,
+    /// Whether to randomize each computed backoff delay by up to ±25% to
+    /// avoid many clients retrying in lockstep. Disabling this is mainly
+    /// useful for deterministic tests.
+    pub retry_jitter: bool,
+    /// `RequestMode` applied to every outgoing fetch.
+    pub request_mode: RequestModeConfig,
+    /// `RequestCredentials` applied to every outgoing fetch.
+    pub credentials: CredentialsMode,
+    /// Extra headers (e.g. a gateway API key) applied to every request, in
+    /// addition to `Content-Type`/`Authorization`/`User-Agent`. An ordered
+    /// list rather than a `HashMap` so header order is preserved exactly as
+    /// registered via `addExtraHeader`.
+    #[wasm_bindgen(skip)]
+    pub extra_headers: Vec<(String, String)>,
 }
 
 #[wasm_bindgen]
@@ -30,9 +224,52 @@ impl RouterConfig {
             max_retries: 3,
             api_key: None,
             user_agent: format!("llm-router-wasm/{}", env!("CARGO_PKG_VERSION")),
+            upgrade_to_https: true,
+            networking_policy: NetworkingPolicy::default(),
+            signing_key: None,
+            max_chunk_size: crate::chunking::DEFAULT_MAX_CHUNK_SIZE,
+            stream_transport: StreamTransport::Http,
+            upgrade_to_wss: true,
+            base_retry_delay_ms: 1000,
+            retry_jitter: true,
+            request_mode: RequestModeConfig::Cors,
+            credentials: CredentialsMode::Omit,
+            extra_headers: Vec::new(),
         }
     }
 
+    /// Register an Ed25519 signing key (base64- or hex-encoded 32-byte seed)
+    /// used to sign every outgoing request instead of relying solely on `api_key`.
+    #[wasm_bindgen(js_name = "setSigningKey")]
+    pub fn set_signing_key(&mut self, encoded_secret_key: &str) -> std::result::Result<(), JSError> {
+        self.signing_key = Some(
+            crate::signing::SigningSecret::parse(encoded_secret_key).map_err(JSError::from)?,
+        );
+        Ok(())
+    }
+
+    /// Remove a previously registered signing key.
+    #[wasm_bindgen(js_name = "clearSigningKey")]
+    pub fn clear_signing_key(&mut self) {
+        self.signing_key = None;
+    }
+
+    /// Whether a signing key is currently registered.
+    #[wasm_bindgen(getter, js_name = "hasSigningKey")]
+    pub fn has_signing_key(&self) -> bool {
+        self.signing_key.is_some()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_chunk_size(&mut self, max_chunk_size: u32) {
+        self.max_chunk_size = max_chunk_size;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn max_chunk_size(&self) -> u32 {
+        self.max_chunk_size
+    }
+
     #[wasm_bindgen(setter)]
     pub fn set_timeout_ms(&mut self, timeout_ms: u32) {
         self.timeout_ms = timeout_ms;
@@ -72,6 +309,101 @@ impl RouterConfig {
     pub fn user_agent(&self) -> String {
         self.user_agent.clone()
     }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_upgrade_to_https(&mut self, upgrade_to_https: bool) {
+        self.upgrade_to_https = upgrade_to_https;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn upgrade_to_https(&self) -> bool {
+        self.upgrade_to_https
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_networking_policy(&mut self, networking_policy: NetworkingPolicy) {
+        self.networking_policy = networking_policy;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn networking_policy(&self) -> NetworkingPolicy {
+        self.networking_policy.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_stream_transport(&mut self, stream_transport: StreamTransport) {
+        self.stream_transport = stream_transport;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn stream_transport(&self) -> StreamTransport {
+        self.stream_transport
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_upgrade_to_wss(&mut self, upgrade_to_wss: bool) {
+        self.upgrade_to_wss = upgrade_to_wss;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn upgrade_to_wss(&self) -> bool {
+        self.upgrade_to_wss
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_base_retry_delay_ms(&mut self, base_retry_delay_ms: u32) {
+        self.base_retry_delay_ms = base_retry_delay_ms;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn base_retry_delay_ms(&self) -> u32 {
+        self.base_retry_delay_ms
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_retry_jitter(&mut self, retry_jitter: bool) {
+        self.retry_jitter = retry_jitter;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn retry_jitter(&self) -> bool {
+        self.retry_jitter
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_request_mode(&mut self, request_mode: RequestModeConfig) {
+        self.request_mode = request_mode;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn request_mode(&self) -> RequestModeConfig {
+        self.request_mode
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_credentials(&mut self, credentials: CredentialsMode) {
+        self.credentials = credentials;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn credentials(&self) -> CredentialsMode {
+        self.credentials
+    }
+
+    /// Add a header applied to every outgoing request, e.g. a gateway key
+    /// (`X-Api-Gateway-Key`) required by a proxy in front of the router.
+    /// Repeated calls with the same `name` append another entry rather than
+    /// overwriting, matching how `Headers::set` vs. `Headers::append` differ.
+    #[wasm_bindgen(js_name = "addExtraHeader")]
+    pub fn add_extra_header(&mut self, name: String, value: String) {
+        self.extra_headers.push((name, value));
+    }
+
+    /// Remove every previously registered extra header.
+    #[wasm_bindgen(js_name = "clearExtraHeaders")]
+    pub fn clear_extra_headers(&mut self) {
+        self.extra_headers.clear();
+    }
 }
 
 /// Options for inference requests
@@ -84,6 +416,10 @@ pub struct InferenceOptions {
     top_k: Option<u32>,
     stream: Option<bool>,
     seed: Option<u32>,
+    /// When set, excludes `session_id` from the request's cache/dedup key so
+    /// otherwise-identical requests made under different sessions can still
+    /// share a cached response.
+    cache_ignore_session: Option<bool>,
 }
 
 #[wasm_bindgen]
@@ -97,6 +433,7 @@ impl InferenceOptions {
             top_k: None,
             stream: Some(false),
             seed: None,
+            cache_ignore_session: None,
         }
     }
 
@@ -139,6 +476,16 @@ impl InferenceOptions {
     pub fn stream(&self) -> Option<bool> {
         self.stream
     }
+
+    #[wasm_bindgen(setter, js_name = "cacheIgnoreSession")]
+    pub fn set_cache_ignore_session(&mut self, cache_ignore_session: Option<bool>) {
+        self.cache_ignore_session = cache_ignore_session;
+    }
+
+    #[wasm_bindgen(getter, js_name = "cacheIgnoreSession")]
+    pub fn cache_ignore_session(&self) -> Option<bool> {
+        self.cache_ignore_session
+    }
 }
 
 /// Request for inference
@@ -196,6 +543,49 @@ impl InferenceRequest {
     pub fn session_id(&self) -> Option<String> {
         self.session_id.clone()
     }
+
+    /// Stable content hash over this request (prompt, model_id, options,
+    /// metadata and session_id), used by `LLMRouterClient` to dedupe
+    /// in-flight requests and key its response cache.
+    #[wasm_bindgen(js_name = "requestHash")]
+    pub fn request_hash(&self) -> u64 {
+        self.content_hash()
+    }
+}
+
+impl InferenceRequest {
+    /// Build the cache/dedup key: a progressive djb2 hash folded over each
+    /// field in turn, so the hash depends on the whole request rather than
+    /// restarting per field. `session_id` is excluded when `options.cache_ignore_session`
+    /// is set.
+    pub(crate) fn content_hash(&self) -> u64 {
+        let ignore_session = self
+            .options
+            .as_ref()
+            .and_then(|options| options.cache_ignore_session)
+            .unwrap_or(false);
+
+        let mut hasher = crate::utils::Djb2Hasher::new();
+        hasher.feed(&self.prompt);
+        hasher.feed(&self.model_id);
+        hasher.feed(&self.options);
+        // Sorted so the key order (and thus the hash) doesn't depend on HashMap iteration order.
+        hasher.feed(&self.metadata.as_ref().map(|m| m.iter().collect::<std::collections::BTreeMap<_, _>>()));
+        if !ignore_session {
+            hasher.feed(&self.session_id);
+        }
+        hasher.finish()
+    }
+
+    /// Whether this request's response may be deduped/cached: streaming
+    /// responses and seeded (non-deterministic-by-intent) requests are
+    /// always re-issued.
+    pub(crate) fn is_cacheable(&self) -> bool {
+        match &self.options {
+            Some(options) => options.stream != Some(true) && options.seed.is_none(),
+            None => true,
+        }
+    }
 }
 
 /// Response from inference
@@ -249,6 +639,12 @@ pub struct StreamingResponse {
     pub is_complete: bool,
     pub model_id: Option<String>,
     pub error: Option<String>,
+    /// Chunked-transport framing, present only when the server split an
+    /// oversized token payload across multiple SSE frames sharing this id.
+    /// `StreamReader` reassembles these before surfacing a response to JS.
+    pub chunk_message_id: Option<u64>,
+    pub chunk_index: Option<u32>,
+    pub chunk_total: Option<u32>,
 }
 
 #[wasm_bindgen]
@@ -260,6 +656,9 @@ impl StreamingResponse {
             is_complete,
             model_id: None,
             error: None,
+            chunk_message_id: None,
+            chunk_index: None,
+            chunk_total: None,
         }
     }
 
@@ -282,6 +681,12 @@ impl StreamingResponse {
     pub fn error(&self) -> Option<String> {
         self.error.clone()
     }
+
+    /// Whether this frame is part of a chunked (multi-frame) message.
+    #[wasm_bindgen(js_name = "isChunked")]
+    pub fn is_chunked(&self) -> bool {
+        self.chunk_message_id.is_some()
+    }
 }
 
 /// Model information
@@ -417,17 +822,61 @@ impl InferenceResponse {
     }
 }
 
+#[wasm_bindgen]
+impl StreamingResponse {
+    /// Create from JavaScript object
+    #[wasm_bindgen(js_name = fromObject)]
+    pub fn from_object(obj: &JsValue) -> Result<StreamingResponse, JsValue> {
+        serde_wasm_bindgen::from_value(obj.clone())
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse StreamingResponse: {}", e)))
+    }
+
+    /// Convert to JavaScript object
+    #[wasm_bindgen(js_name = toObject)]
+    pub fn to_object(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(self)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize StreamingResponse: {}", e)))
+    }
+}
+
 // Type definitions for JavaScript
 #[wasm_bindgen(typescript_custom_section)]
 const TS_APPEND_CONTENT: &'static str = r#"
+export type NetworkingAccessMode = "All" | "Allowlist" | "None";
+
+export interface IProxyRule {
+    host: string;
+    proxy_url: string;
+}
+
+export interface INetworkingPolicy {
+    mode: NetworkingAccessMode;
+    allowed_hosts?: string[];
+    proxies?: IProxyRule[];
+}
+
+export type StreamTransport = "Http" | "WebSocket";
+
 export interface IRouterConfig {
     base_url: string;
     timeout_ms?: number;
     max_retries?: number;
     api_key?: string;
     user_agent?: string;
+    upgrade_to_https?: boolean;
+    networking_policy?: INetworkingPolicy;
+    max_chunk_size?: number;
+    stream_transport?: StreamTransport;
+    upgrade_to_wss?: boolean;
+    base_retry_delay_ms?: number;
+    retry_jitter?: boolean;
+    request_mode?: RequestModeConfig;
+    credentials?: CredentialsMode;
 }
 
+export type RequestModeConfig = "Cors" | "SameOrigin" | "NoCors";
+export type CredentialsMode = "Omit" | "SameOrigin" | "Include";
+
 export interface IInferenceOptions {
     max_tokens?: number;
     temperature?: number;
@@ -435,6 +884,7 @@ export interface IInferenceOptions {
     top_k?: number;
     stream?: boolean;
     seed?: number;
+    cache_ignore_session?: boolean;
 }
 
 export interface IInferenceRequest {
@@ -457,6 +907,9 @@ export interface IStreamingResponse {
     is_complete: boolean;
     model_id?: string;
     error?: string;
+    chunk_message_id?: number;
+    chunk_index?: number;
+    chunk_total?: number;
 }
 
 export interface IModelInfo {
@@ -0,0 +1,75 @@
+//! Typed worker RPC protocol.
+//!
+//! `process_message` used to `match` on a bare `type` string and hand-parse
+//! each variant's fields out of the payload with `js_sys::Reflect::get`,
+//! which duplicated validation per message and gave no compile-time check
+//! that a handler actually read the fields its message carries. `WorkerRequest`
+//! replaces that with one `serde`-tagged enum deserialized straight from the
+//! incoming `JsValue`, so adding a message type means adding one variant
+//! rather than editing parsing, dispatch, and documentation separately.
+
+use serde::Deserialize;
+
+use crate::models::{InferenceRequest, LoadModelRequest};
+
+/// One incoming worker message, `{ "type": ..., "payload": ... }` decoded
+/// directly into its variant. Mirrors the message types `process_message`
+/// has always accepted; `Unknown` catches anything else so deserialization
+/// itself never fails on an unrecognized `type`; the caller reports the
+/// "unknown message type" error instead.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub(crate) enum WorkerRequest {
+    HealthCheck,
+    GetStatus,
+    ListModels {
+        #[serde(default)]
+        include_unloaded: Option<bool>,
+    },
+    LoadModel(LoadModelRequest),
+    Inference(InferenceRequest),
+    QuickInference {
+        prompt: String,
+    },
+    SetSessionId {
+        session_id: String,
+    },
+    ClearSession,
+    Abort {
+        #[serde(rename = "requestId", default)]
+        request_id: Option<String>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// Envelope around a [`WorkerRequest`]: the `requestId` used to correlate a
+/// reply lives alongside `type`/`payload` rather than inside either, so it's
+/// pulled in via `#[serde(flatten)]` instead of being part of the enum.
+#[derive(Debug, Deserialize)]
+pub(crate) struct WorkerRequestEnvelope {
+    #[serde(rename = "requestId", default)]
+    pub(crate) request_id: Option<String>,
+    #[serde(flatten)]
+    pub(crate) request: WorkerRequest,
+}
+
+impl WorkerRequest {
+    /// The `type` string this variant was decoded from, used to name the
+    /// `{type}_response` reply and in "unknown message type" errors —
+    /// kept in sync with the `rename_all = "snake_case"` tag above.
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            WorkerRequest::HealthCheck => "health_check",
+            WorkerRequest::GetStatus => "get_status",
+            WorkerRequest::ListModels { .. } => "list_models",
+            WorkerRequest::LoadModel(_) => "load_model",
+            WorkerRequest::Inference(_) => "inference",
+            WorkerRequest::QuickInference { .. } => "quick_inference",
+            WorkerRequest::SetSessionId { .. } => "set_session_id",
+            WorkerRequest::ClearSession => "clear_session",
+            WorkerRequest::Abort { .. } => "abort",
+            WorkerRequest::Unknown => "unknown",
+        }
+    }
+}
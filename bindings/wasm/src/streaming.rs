@@ -0,0 +1,158 @@
+//! WebSocket-based streaming transport for incremental token output
+
+use crate::{
+    error::{JSError, LLMRouterError},
+    models::StreamingResponse,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use gloo_net::websocket::{futures::WebSocket, Message};
+use wasm_bindgen::prelude::*;
+
+/// A live WebSocket-backed streaming inference session.
+///
+/// Tokens arrive over the socket as they're generated instead of waiting for
+/// a full HTTP response. Register callbacks with [`StreamingSession::on_token`]
+/// and [`StreamingSession::on_done`] before the read loop (spawned in
+/// [`StreamingSession::connect`]) starts delivering frames.
+#[wasm_bindgen]
+pub struct StreamingSession {
+    #[wasm_bindgen(skip)]
+    write: Option<futures_util::stream::SplitSink<WebSocket, Message>>,
+    #[wasm_bindgen(skip)]
+    on_token: Option<js_sys::Function>,
+    #[wasm_bindgen(skip)]
+    on_done: Option<js_sys::Function>,
+}
+
+#[wasm_bindgen]
+impl StreamingSession {
+    /// Open a streaming session against `url` and start reading frames.
+    #[wasm_bindgen(js_name = "connect")]
+    pub fn connect(url: &str) -> Result<StreamingSession, JSError> {
+        let socket = WebSocket::open(url).map_err(|e| {
+            JSError::from(LLMRouterError::Network {
+                message: format!("Failed to open streaming socket: {}", e),
+            })
+        })?;
+
+        let (write, mut read) = socket.split();
+        let session = StreamingSession {
+            write: Some(write),
+            on_token: None,
+            on_done: None,
+        };
+
+        let on_token = std::rc::Rc::new(std::cell::RefCell::new(None::<js_sys::Function>));
+        let on_done = std::rc::Rc::new(std::cell::RefCell::new(None::<js_sys::Function>));
+        session.share_callbacks(&on_token, &on_done);
+
+        wasm_bindgen_futures::spawn_local(async move {
+            while let Some(message) = read.next().await {
+                match message {
+                    Ok(Message::Text(text)) => {
+                        dispatch_chunk(&on_token, &on_done, &text);
+                    }
+                    Ok(Message::Bytes(bytes)) => {
+                        if let Ok(text) = String::from_utf8(bytes) {
+                            dispatch_chunk(&on_token, &on_done, &text);
+                        }
+                    }
+                    Err(e) => {
+                        let error = LLMRouterError::Network {
+                            message: format!("Streaming socket error: {}", e),
+                        };
+                        call_done(&on_done, Err(error));
+                        return;
+                    }
+                }
+            }
+
+            // Socket closed without an explicit error frame; treat as a clean end.
+            call_done(&on_done, Ok(()));
+        });
+
+        Ok(session)
+    }
+
+    /// Register a callback invoked with each decoded token/chunk.
+    #[wasm_bindgen(js_name = "onToken")]
+    pub fn on_token(&mut self, callback: js_sys::Function) {
+        self.on_token = Some(callback);
+    }
+
+    /// Register a callback invoked once the stream completes (successfully or not).
+    #[wasm_bindgen(js_name = "onDone")]
+    pub fn on_done(&mut self, callback: js_sys::Function) {
+        self.on_done = Some(callback);
+    }
+
+    /// Close the underlying socket.
+    #[wasm_bindgen(js_name = "close")]
+    pub async fn close(&mut self) -> Result<(), JSError> {
+        if let Some(mut write) = self.write.take() {
+            write.close().await.map_err(|e| {
+                JSError::from(LLMRouterError::Network {
+                    message: format!("Failed to close streaming socket: {}", e),
+                })
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl StreamingSession {
+    /// Snapshot the current callbacks into shared cells the read loop can poll.
+    fn share_callbacks(
+        &self,
+        on_token: &std::rc::Rc<std::cell::RefCell<Option<js_sys::Function>>>,
+        on_done: &std::rc::Rc<std::cell::RefCell<Option<js_sys::Function>>>,
+    ) {
+        *on_token.borrow_mut() = self.on_token.clone();
+        *on_done.borrow_mut() = self.on_done.clone();
+    }
+}
+
+fn dispatch_chunk(
+    on_token: &std::rc::Rc<std::cell::RefCell<Option<js_sys::Function>>>,
+    on_done: &std::rc::Rc<std::cell::RefCell<Option<js_sys::Function>>>,
+    text: &str,
+) {
+    let data = text.strip_prefix("data: ").unwrap_or(text);
+    match serde_json::from_str::<StreamingResponse>(data) {
+        Ok(chunk) => {
+            if let Some(callback) = on_token.borrow().as_ref() {
+                let value = chunk
+                    .to_object()
+                    .unwrap_or_else(|_| JsValue::from_str(&chunk.token));
+                let _ = callback.call1(&JsValue::undefined(), &value);
+            }
+
+            if chunk.is_complete {
+                call_done(on_done, Ok(()));
+            }
+        }
+        Err(e) => {
+            let error = LLMRouterError::Serialization {
+                message: format!("Failed to parse stream chunk: {}", e),
+            };
+            call_done(on_done, Err(error));
+        }
+    }
+}
+
+fn call_done(
+    on_done: &std::rc::Rc<std::cell::RefCell<Option<js_sys::Function>>>,
+    result: Result<(), LLMRouterError>,
+) {
+    if let Some(callback) = on_done.borrow().as_ref() {
+        match result {
+            Ok(()) => {
+                let _ = callback.call1(&JsValue::undefined(), &JsValue::null());
+            }
+            Err(e) => {
+                let _ = callback.call1(&JsValue::undefined(), &JSError::from(e).to_error().into());
+            }
+        }
+    }
+}
@@ -2,6 +2,73 @@
 
 use wasm_bindgen::prelude::*;
 use crate::error::{LLMRouterError, JSError};
+use crate::models::RouterConfig;
+use url::Url;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Unified handle to whatever global scope the module is running in.
+///
+/// `window()` is only available on the main thread, but `DedicatedWorkerGlobalScope`
+/// exposes the same timer/performance APIs under a different type. Routing through
+/// this enum lets callers stay agnostic to the environment instead of `unwrap()`-ing
+/// `web_sys::window()` and panicking inside a worker.
+pub enum GlobalScope {
+    Window(web_sys::Window),
+    Worker(web_sys::WorkerGlobalScope),
+    Other(js_sys::Object),
+}
+
+impl GlobalScope {
+    /// Resolve the current global scope by downcasting `js_sys::global()`.
+    pub fn current() -> Self {
+        let global = js_sys::global();
+        if let Ok(window) = global.clone().dyn_into::<web_sys::Window>() {
+            GlobalScope::Window(window)
+        } else if let Ok(worker) = global.clone().dyn_into::<web_sys::WorkerGlobalScope>() {
+            GlobalScope::Worker(worker)
+        } else {
+            GlobalScope::Other(global.into())
+        }
+    }
+
+    /// Schedule `handler` to run after `ms` milliseconds, returning the timeout handle.
+    pub fn set_timeout(&self, handler: &js_sys::Function, ms: i32) -> Result<i32, LLMRouterError> {
+        let to_js_err = |_| LLMRouterError::JavaScript {
+            message: "Failed to schedule timeout".to_string(),
+        };
+
+        match self {
+            GlobalScope::Window(window) => window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(handler, ms)
+                .map_err(to_js_err),
+            GlobalScope::Worker(worker) => worker
+                .set_timeout_with_callback_and_timeout_and_arguments_0(handler, ms)
+                .map_err(to_js_err),
+            GlobalScope::Other(_) => Err(LLMRouterError::JavaScript {
+                message: "No timer API available in this global scope".to_string(),
+            }),
+        }
+    }
+
+    /// Clear a timeout previously scheduled with `set_timeout`.
+    pub fn clear_timeout(&self, handle: i32) {
+        match self {
+            GlobalScope::Window(window) => window.clear_timeout_with_handle(handle),
+            GlobalScope::Worker(worker) => worker.clear_timeout_with_handle(handle),
+            GlobalScope::Other(_) => {}
+        }
+    }
+
+    /// Get the `Performance` object for this scope, if one is available.
+    pub fn performance(&self) -> Option<web_sys::Performance> {
+        match self {
+            GlobalScope::Window(window) => window.performance(),
+            GlobalScope::Worker(worker) => worker.performance(),
+            GlobalScope::Other(_) => None,
+        }
+    }
+}
 
 /// Generate a random UUID v4
 #[wasm_bindgen(js_name = "generateUuid")]
@@ -18,89 +85,142 @@ pub fn get_current_timestamp() -> f64 {
 /// Sleep for specified milliseconds
 #[wasm_bindgen(js_name = "sleep")]
 pub async fn sleep(ms: u32) -> Result<(), JsValue> {
-    let promise = js_sys::Promise::new(&mut |resolve, _| {
-        let window = web_sys::window().unwrap();
+    let scope = GlobalScope::current();
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
         let closure = Closure::once_into_js(move || {
             resolve.call0(&JsValue::undefined()).unwrap();
         });
-        
-        window.set_timeout_with_callback_and_timeout_and_arguments_0(
-            closure.as_ref().unchecked_ref(),
-            ms as i32,
-        ).unwrap();
+
+        if let Err(e) = scope.set_timeout(closure.unchecked_ref::<js_sys::Function>(), ms as i32) {
+            reject.call1(&JsValue::undefined(), &JsValue::from(JSError::from(e))).unwrap();
+            return;
+        }
+
+        // Keep the closure alive until the timer fires.
+        std::mem::forget(closure);
     });
-    
+
     wasm_bindgen_futures::JsFuture::from(promise).await?;
     Ok(())
 }
 
 /// Retry function with exponential backoff
+///
+/// Honors each error's own retry metadata (`is_retryable`/`retry_delay_ms`,
+/// including a server `Retry-After`) instead of blindly retrying on a fixed
+/// schedule, and accepts an optional `AbortSignal` so callers can cancel a
+/// long backoff wait instead of being stuck for up to 60 seconds.
 #[wasm_bindgen(js_name = "retryWithBackoff")]
 pub async fn retry_with_backoff(
     callback: &js_sys::Function,
     max_retries: u32,
     base_delay_ms: u32,
+    signal: Option<web_sys::AbortSignal>,
 ) -> Result<JsValue, JSError> {
-    let mut last_error = None;
-    
     for attempt in 0..=max_retries {
+        if let Some(ref signal) = signal {
+            if signal.aborted() {
+                return Err(JSError::from(LLMRouterError::Other {
+                    message: "aborted".to_string(),
+                }));
+            }
+        }
+
         // Call the callback function
-        let result = callback.call0(&JsValue::undefined());
-        
-        match result {
+        let err = match callback.call0(&JsValue::undefined()) {
             Ok(value) => {
                 // Check if it's a promise
                 if let Some(promise) = value.dyn_ref::<js_sys::Promise>() {
                     match wasm_bindgen_futures::JsFuture::from(promise.clone()).await {
                         Ok(resolved_value) => return Ok(resolved_value),
-                        Err(error) => {
-                            if attempt == max_retries {
-                                return Err(JSError::from(LLMRouterError::from(error)));
-                            }
-                            last_error = Some(error);
-                        }
+                        Err(error) => LLMRouterError::from(error),
                     }
                 } else {
                     return Ok(value);
                 }
             }
-            Err(error) => {
-                if attempt == max_retries {
-                    return Err(JSError::from(LLMRouterError::from(error)));
-                }
-                last_error = Some(error);
-            }
-        }
-        
-        // Calculate delay with exponential backoff
-        let delay = base_delay_ms * 2_u32.pow(attempt);
-        let max_delay = 60000; // 60 seconds max
-        let actual_delay = delay.min(max_delay);
-        
-        // Add jitter (±25%)
-        let jitter_range = actual_delay / 4;
-        let jitter = (js_sys::Math::random() * (jitter_range * 2) as f64) as u32;
-        let final_delay = if jitter > jitter_range {
-            actual_delay + (jitter - jitter_range)
-        } else {
-            actual_delay.saturating_sub(jitter_range - jitter)
+            Err(error) => LLMRouterError::from(error),
         };
-        
-        sleep(final_delay).await.unwrap();
+
+        if attempt == max_retries || !err.is_retryable() {
+            return Err(JSError::from(err));
+        }
+
+        sleep_or_abort(backoff_delay_ms(attempt, base_delay_ms, &err, true), signal.as_ref())
+            .await
+            .map_err(JSError::from)?;
     }
-    
+
     // This should never be reached, but just in case
     Err(JSError::from(LLMRouterError::Other {
         message: "Retry loop completed without result".to_string(),
     }))
 }
 
+/// Compute the delay before the next retry attempt: exponential backoff
+/// (capped at 60s), floored by any server-suggested `retry_delay_ms` on
+/// `error`, with ±25% jitter applied when `jitter` is set.
+pub(crate) fn backoff_delay_ms(attempt: u32, base_delay_ms: u32, error: &LLMRouterError, jitter: bool) -> u32 {
+    let delay = base_delay_ms * 2_u32.pow(attempt);
+    let max_delay = 60000; // 60 seconds max
+    let exponential_delay = delay.min(max_delay);
+    let actual_delay = error
+        .retry_delay_ms()
+        .map_or(exponential_delay, |suggested| suggested.max(exponential_delay));
+
+    if !jitter {
+        return actual_delay;
+    }
+
+    // Add jitter (±25%)
+    let jitter_range = actual_delay / 4;
+    let jitter = (js_sys::Math::random() * (jitter_range * 2) as f64) as u32;
+    if jitter > jitter_range {
+        actual_delay + (jitter - jitter_range)
+    } else {
+        actual_delay.saturating_sub(jitter_range - jitter)
+    }
+}
+
+/// Sleep for `ms` milliseconds, or reject early with an "aborted" error if
+/// `signal` fires its `abort` event first.
+async fn sleep_or_abort(ms: u32, signal: Option<&web_sys::AbortSignal>) -> Result<(), LLMRouterError> {
+    let signal = match signal {
+        Some(signal) => signal,
+        None => return sleep(ms).await.map_err(LLMRouterError::from),
+    };
+
+    if signal.aborted() {
+        return Err(LLMRouterError::Other {
+            message: "aborted".to_string(),
+        });
+    }
+
+    let abort_promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let closure = Closure::once_into_js(move || {
+            resolve.call0(&JsValue::undefined()).unwrap();
+        });
+        signal
+            .add_event_listener_with_callback("abort", closure.unchecked_ref())
+            .unwrap();
+        std::mem::forget(closure);
+    });
+
+    let sleep_fut = Box::pin(sleep(ms));
+    let abort_fut = Box::pin(wasm_bindgen_futures::JsFuture::from(abort_promise));
+
+    match futures_util::future::select(sleep_fut, abort_fut).await {
+        futures_util::future::Either::Left((result, _)) => result.map_err(LLMRouterError::from),
+        futures_util::future::Either::Right(_) => Err(LLMRouterError::Other {
+            message: "aborted".to_string(),
+        }),
+    }
+}
+
 /// Check if running in a Web Worker
 #[wasm_bindgen(js_name = "isWebWorker")]
 pub fn is_web_worker() -> bool {
-    js_sys::global()
-        .dyn_into::<web_sys::DedicatedWorkerGlobalScope>()
-        .is_ok()
+    matches!(GlobalScope::current(), GlobalScope::Worker(_))
 }
 
 /// Check if running in a browser
@@ -130,16 +250,19 @@ pub fn get_environment_info() -> JsValue {
     ).unwrap();
     
     // User agent (if available)
-    if let Some(window) = web_sys::window() {
-        if let Some(navigator) = window.navigator() {
-            js_sys::Reflect::set(
-                &env_info,
-                &"userAgent".into(),
-                &navigator.user_agent().unwrap_or_else(|_| "unknown".to_string()).into()
-            ).unwrap();
-        }
+    let user_agent = match GlobalScope::current() {
+        GlobalScope::Window(window) => window.navigator().and_then(|n| n.user_agent().ok()),
+        GlobalScope::Worker(worker) => worker.navigator().and_then(|n| n.user_agent().ok()),
+        GlobalScope::Other(_) => None,
+    };
+    if let Some(user_agent) = user_agent {
+        js_sys::Reflect::set(
+            &env_info,
+            &"userAgent".into(),
+            &user_agent.into()
+        ).unwrap();
     }
-    
+
     // WASM support info
     js_sys::Reflect::set(
         &env_info,
@@ -254,41 +377,38 @@ pub struct PerformanceMeasure {
 #[wasm_bindgen]
 impl PerformanceMeasure {
     fn new(name: &str) -> Self {
-        let start_time = if let Some(window) = web_sys::window() {
-            window.performance().unwrap().now()
-        } else {
-            get_current_timestamp()
-        };
-        
+        let start_time = GlobalScope::current()
+            .performance()
+            .map(|p| p.now())
+            .unwrap_or_else(get_current_timestamp);
+
         PerformanceMeasure {
             name: name.to_string(),
             start_time,
         }
     }
-    
+
     /// End the measurement and return duration in milliseconds
     #[wasm_bindgen(js_name = "end")]
     pub fn end(&self) -> f64 {
-        let end_time = if let Some(window) = web_sys::window() {
-            window.performance().unwrap().now()
-        } else {
-            get_current_timestamp()
-        };
-        
+        let end_time = GlobalScope::current()
+            .performance()
+            .map(|p| p.now())
+            .unwrap_or_else(get_current_timestamp);
+
         let duration = end_time - self.start_time;
         log_with_timestamp("debug", &format!("Performance [{}]: {:.2}ms", self.name, duration));
         duration
     }
-    
+
     /// Get the elapsed time without ending the measurement
     #[wasm_bindgen(js_name = "elapsed")]
     pub fn elapsed(&self) -> f64 {
-        let current_time = if let Some(window) = web_sys::window() {
-            window.performance().unwrap().now()
-        } else {
-            get_current_timestamp()
-        };
-        
+        let current_time = GlobalScope::current()
+            .performance()
+            .map(|p| p.now())
+            .unwrap_or_else(get_current_timestamp);
+
         current_time - self.start_time
     }
 }
@@ -301,20 +421,177 @@ pub fn validate_config(config: &RouterConfig) -> Result<(), JSError> {
         .map_err(|e| JSError::from(LLMRouterError::Validation {
             message: "Invalid base URL".to_string()
         }))?;
-    
+
     // Validate timeout
     if config.timeout_ms == 0 {
         return Err(JSError::from(LLMRouterError::Validation {
             message: "Timeout must be greater than 0".to_string()
         }));
     }
-    
+
     // Validate max retries
     if config.max_retries > 10 {
         return Err(JSError::from(LLMRouterError::Validation {
             message: "Max retries should not exceed 10".to_string()
         }));
     }
-    
+
+    // Reject mixed content: an HTTPS page silently calling a plain-HTTP endpoint
+    // fails at fetch time with an opaque browser error, so catch it here instead.
+    if !config.upgrade_to_https && config.base_url.starts_with("http://") && is_secure_context() {
+        return Err(JSError::from(LLMRouterError::Validation {
+            message: "Mixed content: base_url uses http:// while the page is served over https:// \
+                      (enable upgrade_to_https or use an https:// base_url)".to_string()
+        }));
+    }
+
+    // Validate the networking policy's proxy table
+    config.networking_policy.validate()
+        .map_err(|message| JSError::from(LLMRouterError::Validation { message }))?;
+
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Check whether the current page/worker scope is itself served over HTTPS.
+fn is_secure_context() -> bool {
+    match GlobalScope::current() {
+        GlobalScope::Window(window) => window
+            .location()
+            .protocol()
+            .map(|protocol| protocol == "https:")
+            .unwrap_or(false),
+        GlobalScope::Worker(worker) => worker
+            .location()
+            .map(|location| location.protocol().map(|p| p == "https:").unwrap_or(false))
+            .unwrap_or(false),
+        GlobalScope::Other(_) => false,
+    }
+}
+
+/// Resolve `config.base_url`, upgrading it to `https://` when `upgrade_to_https`
+/// is set and the embedding page is itself served over HTTPS.
+fn upgraded_base_url(config: &RouterConfig) -> String {
+    if config.upgrade_to_https && config.base_url.starts_with("http://") && is_secure_context() {
+        format!("https://{}", &config.base_url["http://".len()..])
+    } else {
+        config.base_url.clone()
+    }
+}
+
+/// Pick the base URL to resolve relative paths against: the (possibly
+/// upgraded) `config.base_url`, falling back to `document.baseURI` when unset.
+fn resolve_base_url(config: &RouterConfig) -> Result<String, JSError> {
+    let base = upgraded_base_url(config);
+    if !base.is_empty() {
+        return Ok(base);
+    }
+
+    if let GlobalScope::Window(window) = GlobalScope::current() {
+        if let Some(document) = window.document() {
+            if let Ok(Some(base_uri)) = document.base_uri() {
+                return Ok(base_uri);
+            }
+        }
+    }
+
+    Err(JSError::from(LLMRouterError::Validation {
+        message: "No base URL available to resolve request path".to_string(),
+    }))
+}
+
+/// Resolve a relative model/endpoint path against the client's base URL.
+#[wasm_bindgen(js_name = "preprocessUrl")]
+pub fn preprocess_url(config: &RouterConfig, path: &str) -> Result<String, JSError> {
+    let base = resolve_base_url(config)?;
+    let base_url = Url::parse(&base).map_err(|e| JSError::from(LLMRouterError::Validation {
+        message: format!("Invalid base URL: {}", e),
+    }))?;
+
+    let joined = base_url.join(path).map_err(|e| JSError::from(LLMRouterError::Validation {
+        message: format!("Failed to resolve path against base URL: {}", e),
+    }))?;
+
+    Ok(joined.to_string())
+}
+
+/// Progressive djb2-style hasher: each fed value is serialized to JSON
+/// and every byte is folded into the running accumulator, so the hash
+/// reflects the full sequence of fields rather than just the last one.
+pub(crate) struct Djb2Hasher {
+    state: u64,
+}
+
+impl Djb2Hasher {
+    pub(crate) fn new() -> Self {
+        Djb2Hasher { state: 5381 }
+    }
+
+    /// Serialize `value` to JSON and fold it into the running hash. Map keys
+    /// are serialized in the map's own iteration order.
+    pub(crate) fn feed<T: serde::Serialize>(&mut self, value: &T) {
+        if let Ok(bytes) = serde_json::to_vec(value) {
+            self.feed_bytes(&bytes);
+        }
+    }
+
+    /// Fold raw bytes into the running hash directly, without serializing.
+    pub(crate) fn feed_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state = (self.state << 5).wrapping_add(self.state).wrapping_add(*byte as u64);
+        }
+    }
+
+    pub(crate) fn finish(self) -> u64 {
+        self.state
+    }
+}
+
+/// Small fixed-capacity least-recently-used cache, used to bound the
+/// client's in-memory response cache without pulling in an external crate.
+pub(crate) struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    order: VecDeque<K>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        if self.entries.contains_key(key) {
+            self.touch(key);
+        }
+        self.entries.get(key).cloned()
+    }
+
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        if self.entries.insert(key.clone(), value).is_some() {
+            self.touch(&key);
+            return;
+        }
+
+        self.order.push_back(key);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            if let Some(k) = self.order.remove(pos) {
+                self.order.push_back(k);
+            }
+        }
+    }
+}
@@ -1,19 +1,167 @@
 //! Web Worker support for WASM bindings
 
 use wasm_bindgen::prelude::*;
-use web_sys::{DedicatedWorkerGlobalScope, MessageEvent, Worker};
+use web_sys::{
+    Blob, BlobPropertyBag, DedicatedWorkerGlobalScope, MessageChannel, MessageEvent, MessagePort,
+    SharedWorkerGlobalScope, Url, Worker,
+};
+use futures::channel::oneshot;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
 use crate::{
     client::LLMRouterClient,
     models::*,
     error::{LLMRouterError, JSError},
+    protocol::{WorkerRequest, WorkerRequestEnvelope},
     utils::*,
 };
 
+/// Per-request cancellation flags for [`LLMRouterWorker::process_stream_inference`],
+/// shared (via `Rc`) with the synchronous `"abort"` fast path in
+/// `setup_worker_message_handler` so a cancellation reaches a running stream
+/// task on its very next loop iteration instead of waiting behind any
+/// already-queued `process_message` futures.
+type AbortFlags = Rc<RefCell<HashMap<String, Cell<bool>>>>;
+
+/// Maximum serialized size (bytes) of a worker message before
+/// [`ReplyTarget::post_message_chunked`] splits it into `worker_chunk`
+/// frames, mirroring `RouterConfig::max_chunk_size`'s role for the HTTP
+/// chunked-body path but sized for `postMessage` rather than a request body.
+const WORKER_MESSAGE_MTU: u32 = crate::chunking::DEFAULT_MAX_CHUNK_SIZE;
+
+/// Reads a `MessagePort` transferred in as `message`'s `"port"` field, i.e.
+/// one entangled via [`create_entangled_port`]. `None` means the caller is
+/// using the legacy multiplexed path and wants replies on the global scope.
+fn entangled_port(message: &JsValue) -> Option<MessagePort> {
+    js_sys::Reflect::get(message, &"port".into())
+        .ok()?
+        .dyn_into::<MessagePort>()
+        .ok()
+}
+
+/// Where a worker posts its responses and stream events. Covers every host
+/// context `setup_worker_message_handler` can run in:
+///
+/// - `Dedicated`: the `DedicatedWorkerGlobalScope` shared by every in-flight
+///   request (the legacy path, requiring `requestId` filtering on the main
+///   thread).
+/// - `Port`: a `MessagePort` entangled to one specific request (via
+///   [`create_entangled_port`]), isolating its traffic entirely — also how a
+///   `SharedWorkerGlobalScope` connection replies, since shared workers have
+///   no single "the worker" scope to post to and must use the connecting
+///   client's port instead.
+/// - `Window`: the main thread itself, for running a router client directly
+///   on-page without a worker (e.g. local debugging).
+#[derive(Clone)]
+enum ReplyTarget {
+    Dedicated(DedicatedWorkerGlobalScope),
+    Port(MessagePort),
+    Window(web_sys::Window),
+}
+
+impl ReplyTarget {
+    /// Prefer a port entangled in `message`; fall back to `fallback` (a host
+    /// context's default reply target, set once via
+    /// [`LLMRouterWorker::set_default_reply_target`]) when the message
+    /// carries none; fall back further to resolving `js_sys::global()`
+    /// itself for the classic dedicated-worker and main-thread cases.
+    fn resolve(message: &JsValue, fallback: Option<&ReplyTarget>) -> Result<ReplyTarget, JSError> {
+        if let Some(port) = entangled_port(message) {
+            return Ok(ReplyTarget::Port(port));
+        }
+
+        if let Some(target) = fallback {
+            return Ok(target.clone());
+        }
+
+        let global = js_sys::global();
+        if let Ok(scope) = global.clone().dyn_into::<DedicatedWorkerGlobalScope>() {
+            return Ok(ReplyTarget::Dedicated(scope));
+        }
+        if let Ok(window) = global.dyn_into::<web_sys::Window>() {
+            return Ok(ReplyTarget::Window(window));
+        }
+
+        Err(JSError::from(LLMRouterError::JavaScript {
+            message: "Not running in a worker or window context".to_string(),
+        }))
+    }
+
+    fn post_message(&self, value: &JsValue) -> Result<(), JsValue> {
+        match self {
+            ReplyTarget::Dedicated(scope) => scope.post_message(value),
+            ReplyTarget::Port(port) => port.post_message(value),
+            ReplyTarget::Window(window) => window.post_message(value, "*"),
+        }
+    }
+
+    /// Like [`Self::post_message`], but moves (rather than copies) the
+    /// backing store of every `ArrayBuffer` listed in `transfer` — used for
+    /// the binary-token path so a chunk's bytes aren't structured-cloned.
+    ///
+    /// `web_sys` only binds the two-argument form of `Window::postMessage`,
+    /// so a `Window` target falls back to a plain (copying) `post_message`
+    /// rather than a true transfer.
+    fn post_message_with_transfer(&self, value: &JsValue, transfer: &JsValue) -> Result<(), JsValue> {
+        match self {
+            ReplyTarget::Dedicated(scope) => scope.post_message_with_transfer(value, transfer),
+            ReplyTarget::Port(port) => port.post_message_with_transfer(value, transfer),
+            ReplyTarget::Window(window) => window.post_message(value, "*"),
+        }
+    }
+
+    /// Post `value` as-is when its JSON serialization fits under
+    /// [`WORKER_MESSAGE_MTU`]; otherwise split it into ordered
+    /// `{type: "worker_chunk", messageId, index, total, bytes}` frames via
+    /// [`crate::chunking::ChunkList`] and post each individually. The
+    /// receiving end's [`crate::chunking::ChunkManager`] reassembles them
+    /// and redispatches the original message, so large `load_model`
+    /// manifests or inference payloads can't blow past practical
+    /// `postMessage` size limits. `message_id` must be unique per outgoing
+    /// message so concurrently chunked messages don't interleave.
+    fn post_message_chunked(&self, value: &JsValue, message_id: u64) -> Result<(), JsValue> {
+        let serialized = js_sys::JSON::stringify(value)?;
+        let text: String = serialized.into();
+        let bytes = text.into_bytes();
+
+        if bytes.len() <= WORKER_MESSAGE_MTU as usize {
+            return self.post_message(value);
+        }
+
+        for frame in crate::chunking::ChunkList::split(message_id, &bytes, WORKER_MESSAGE_MTU) {
+            let frame_obj = js_sys::Object::new();
+            js_sys::Reflect::set(&frame_obj, &"type".into(), &"worker_chunk".into())?;
+            js_sys::Reflect::set(&frame_obj, &"messageId".into(), &(frame.message_id as f64).into())?;
+            js_sys::Reflect::set(&frame_obj, &"index".into(), &frame.index.into())?;
+            js_sys::Reflect::set(&frame_obj, &"total".into(), &frame.total.into())?;
+            js_sys::Reflect::set(&frame_obj, &"bytes".into(), &js_sys::Uint8Array::from(frame.bytes.as_slice()))?;
+            self.post_message(&frame_obj.into())?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Web Worker wrapper for LLM Router client
 #[wasm_bindgen]
 pub struct LLMRouterWorker {
     client: LLMRouterClient,
     worker_id: String,
+    #[wasm_bindgen(skip)]
+    abort_flags: AbortFlags,
+    /// Source of unique ids for [`ReplyTarget::post_message_chunked`], one
+    /// per outgoing oversized message.
+    #[wasm_bindgen(skip)]
+    outgoing_chunk_id: Cell<u64>,
+    /// This worker's fallback [`ReplyTarget`] for messages that carry no
+    /// entangled port of their own, set once by `setup_worker_message_handler`
+    /// for host contexts where [`ReplyTarget::resolve`]'s own
+    /// `js_sys::global()` lookup can't find a target — a
+    /// `SharedWorkerGlobalScope` connection's port, or `Window` when
+    /// debugging on the main thread. `None` for a classic dedicated worker.
+    #[wasm_bindgen(skip)]
+    default_reply_target: RefCell<Option<ReplyTarget>>,
 }
 
 #[wasm_bindgen]
@@ -23,128 +171,131 @@ impl LLMRouterWorker {
     pub fn new(config: RouterConfig) -> LLMRouterWorker {
         let worker_id = generate_uuid();
         let client = LLMRouterClient::new(config);
-        
+
         log_with_timestamp("info", &format!("LLM Router Worker created: {}", worker_id));
-        
+
         LLMRouterWorker {
             client,
             worker_id,
+            abort_flags: Rc::new(RefCell::new(HashMap::new())),
+            outgoing_chunk_id: Cell::new(0),
+            default_reply_target: RefCell::new(None),
+        }
+    }
+
+    /// Next unique id for an outgoing chunked message.
+    fn next_chunk_id(&self) -> u64 {
+        let id = self.outgoing_chunk_id.get();
+        self.outgoing_chunk_id.set(id + 1);
+        id
+    }
+
+    /// Pin this worker's replies to `target` for any message that carries no
+    /// entangled port of its own. See [`Self::default_reply_target`] field
+    /// doc for when this needs setting.
+    pub(crate) fn set_default_reply_target(&self, target: ReplyTarget) {
+        *self.default_reply_target.borrow_mut() = Some(target);
+    }
+
+    /// Clone of this worker's fallback [`ReplyTarget`], if one was set via
+    /// [`Self::set_default_reply_target`].
+    fn default_reply_target(&self) -> Option<ReplyTarget> {
+        self.default_reply_target.borrow().clone()
+    }
+
+    /// Signal cancellation for an in-flight [`Self::process_stream_inference`]
+    /// call keyed by `request_id`. Returns `true` if a matching stream was
+    /// found (it will post `stream_cancelled` and stop on its next read-loop
+    /// iteration), `false` if there was no such stream (already finished, or
+    /// never started).
+    pub fn abort(&self, request_id: &str) -> bool {
+        match self.abort_flags.borrow().get(request_id) {
+            Some(flag) => {
+                flag.set(true);
+                true
+            }
+            None => false,
         }
     }
 
+    /// Clone of this worker's abort-flag map, for `setup_worker_message_handler`
+    /// to dispatch `"abort"` messages synchronously without holding a borrow
+    /// of the worker itself. Not exposed to JS (`pub(crate)`).
+    pub(crate) fn abort_flags(&self) -> AbortFlags {
+        self.abort_flags.clone()
+    }
+
     /// Get worker ID
     #[wasm_bindgen(getter, js_name = "workerId")]
     pub fn worker_id(&self) -> String {
         self.worker_id.clone()
     }
 
-    /// Process message from main thread
+    /// Process message from main thread.
+    ///
+    /// Decodes `message` straight into a [`WorkerRequestEnvelope`] instead of
+    /// hand-parsing `type`/`payload` fields with `Reflect::get`: each
+    /// [`WorkerRequest`] variant already carries its typed, validated
+    /// payload, so the `match` below is just dispatch.
     #[wasm_bindgen(js_name = "processMessage")]
     pub async fn process_message(&mut self, message: &JsValue) -> Result<JsValue, JSError> {
-        let message_obj = message.dyn_ref::<js_sys::Object>()
-            .ok_or_else(|| JSError::from(LLMRouterError::Validation {
-                message: "Invalid message format".to_string()
-            }))?;
-
-        // Extract message type
-        let message_type = js_sys::Reflect::get(message_obj, &"type".into())
+        let envelope: WorkerRequestEnvelope = serde_wasm_bindgen::from_value(message.clone())
             .map_err(|e| JSError::from(LLMRouterError::Validation {
-                message: "Missing message type".to_string()
-            }))?
-            .as_string()
-            .ok_or_else(|| JSError::from(LLMRouterError::Validation {
-                message: "Message type must be a string".to_string()
+                message: format!("Invalid message format: {}", e)
             }))?;
 
-        // Extract request ID for response correlation
-        let request_id = js_sys::Reflect::get(message_obj, &"requestId".into())
-            .ok()
-            .and_then(|v| v.as_string())
-            .unwrap_or_else(|| generate_uuid());
+        let request_id = envelope.request_id.unwrap_or_else(generate_uuid);
+        let message_type = envelope.request.type_name();
 
-        // Extract payload
-        let payload = js_sys::Reflect::get(message_obj, &"payload".into())
-            .unwrap_or_else(|_| JsValue::undefined());
-
-        let response = match message_type.as_str() {
-            "health_check" => {
-                self.client.health_check().await
-                    .map_err(|e| e)?
+        let response = match envelope.request {
+            WorkerRequest::HealthCheck => {
+                self.client.health_check().await?
             }
-            "get_status" => {
-                self.client.get_status().await
-                    .map_err(|e| e)?
+            WorkerRequest::GetStatus => {
+                self.client.get_status().await?
             }
-            "list_models" => {
-                let include_unloaded = js_sys::Reflect::get(&payload, &"include_unloaded".into())
-                    .ok()
-                    .and_then(|v| v.as_bool());
-                
-                let models = self.client.list_models(include_unloaded).await
-                    .map_err(|e| e)?;
+            WorkerRequest::ListModels { include_unloaded } => {
+                let models = self.client.list_models(include_unloaded).await?;
                 models.into()
             }
-            "load_model" => {
-                let load_request = LoadModelRequest::from_object(&payload)
-                    .map_err(|e| JSError::from(LLMRouterError::Serialization {
-                        message: "Failed to parse load model request".to_string()
-                    }))?;
-                
-                self.client.load_model(&load_request).await
-                    .map_err(|e| e)?
+            WorkerRequest::LoadModel(load_request) => {
+                self.client.load_model(&load_request).await?
             }
-            "inference" => {
-                let inference_request = InferenceRequest::from_object(&payload)
-                    .map_err(|e| JSError::from(LLMRouterError::Serialization {
-                        message: "Failed to parse inference request".to_string()
-                    }))?;
-                
-                let response = self.client.inference(inference_request).await
-                    .map_err(|e| e)?;
-                
+            WorkerRequest::Inference(inference_request) => {
+                let response = self.client.inference(inference_request).await?;
+
                 response.to_object()
                     .map_err(|e| JSError::from(LLMRouterError::Serialization {
                         message: "Failed to serialize inference response".to_string()
                     }))?
             }
-            "quick_inference" => {
-                let prompt = js_sys::Reflect::get(&payload, &"prompt".into())
-                    .map_err(|e| JSError::from(LLMRouterError::Validation {
-                        message: "Missing prompt".to_string()
-                    }))?
-                    .as_string()
-                    .ok_or_else(|| JSError::from(LLMRouterError::Validation {
-                        message: "Prompt must be a string".to_string()
-                    }))?;
-                
-                let response = self.client.quick_inference(prompt).await
-                    .map_err(|e| e)?;
-                
+            WorkerRequest::QuickInference { prompt } => {
+                let response = self.client.quick_inference(prompt).await?;
+
                 response.to_object()
                     .map_err(|e| JSError::from(LLMRouterError::Serialization {
                         message: "Failed to serialize quick inference response".to_string()
                     }))?
             }
-            "set_session_id" => {
-                let session_id = js_sys::Reflect::get(&payload, &"session_id".into())
-                    .map_err(|e| JSError::from(LLMRouterError::Validation {
-                        message: "Missing session_id".to_string()
-                    }))?
-                    .as_string()
-                    .ok_or_else(|| JSError::from(LLMRouterError::Validation {
-                        message: "Session ID must be a string".to_string()
-                    }))?;
-                
+            WorkerRequest::SetSessionId { session_id } => {
                 self.client.set_session_id(session_id);
                 js_sys::Object::new().into()
             }
-            "clear_session" => {
+            WorkerRequest::ClearSession => {
                 self.client.clear_session();
                 js_sys::Object::new().into()
             }
-            _ => {
+            WorkerRequest::Abort { request_id: target_request_id } => {
+                let target_request_id = target_request_id.unwrap_or_else(|| request_id.clone());
+                let cancelled = self.abort(&target_request_id);
+
+                let result_obj = js_sys::Object::new();
+                js_sys::Reflect::set(&result_obj, &"cancelled".into(), &cancelled.into()).unwrap();
+                result_obj.into()
+            }
+            WorkerRequest::Unknown => {
                 return Err(JSError::from(LLMRouterError::Validation {
-                    message: format!("Unknown message type: {}", message_type)
+                    message: "Unknown message type".to_string()
                 }));
             }
         };
@@ -156,6 +307,7 @@ impl LLMRouterWorker {
         js_sys::Reflect::set(&response_obj, &"success".into(), &true.into()).unwrap();
         js_sys::Reflect::set(&response_obj, &"data".into(), &response).unwrap();
         js_sys::Reflect::set(&response_obj, &"timestamp".into(), &get_current_timestamp().into()).unwrap();
+        js_sys::Reflect::set(&response_obj, &"workerId".into(), &self.worker_id.clone().into()).unwrap();
 
         Ok(response_obj.into())
     }
@@ -181,34 +333,104 @@ impl LLMRouterWorker {
                 message: "Failed to parse stream inference request".to_string()
             }))?;
 
-        let mut stream_reader = self.client.stream_inference(inference_request).await
-            .map_err(|e| e)?;
+        // Opt-in zero-copy path: pack each token's UTF-8 bytes into a
+        // transferable `ArrayBuffer` instead of a JS string, avoiding a
+        // structured-clone copy per chunk. Off by default for backward
+        // compatibility with consumers reading `chunk.token`.
+        let binary_tokens = js_sys::Reflect::get(&payload, &"binaryTokens".into())
+            .ok()
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
-        // Get global scope for posting messages
-        let global = js_sys::global();
-        let worker_scope = global.dyn_into::<DedicatedWorkerGlobalScope>()
-            .map_err(|e| JSError::from(LLMRouterError::JavaScript {
-                message: "Not running in a worker context".to_string()
-            }))?;
+        // Register a cancellation flag for this request *before* the network
+        // call below, so `self.abort(request_id)` (called either directly, or
+        // synchronously from the "abort" fast path in
+        // `setup_worker_message_handler`) can't be dropped by arriving while
+        // `stream_inference` is still in flight.
+        self.abort_flags.borrow_mut().insert(request_id.clone(), Cell::new(false));
+
+        let mut stream_reader = match self.client.stream_inference(inference_request).await {
+            Ok(reader) => reader,
+            Err(e) => {
+                self.abort_flags.borrow_mut().remove(&request_id);
+                return Err(e);
+            }
+        };
+
+        // Prefer a per-request port entangled via `create_entangled_port`;
+        // fall back to this worker's default reply target (if the host
+        // context set one), then to the shared global scope (filtered by
+        // `requestId` on the main thread) when neither applies.
+        let reply_target = match ReplyTarget::resolve(message, self.default_reply_target().as_ref()) {
+            Ok(target) => target,
+            Err(e) => {
+                self.abort_flags.borrow_mut().remove(&request_id);
+                return Err(e);
+            }
+        };
 
         // Read stream chunks and post back to main thread
         loop {
+            let aborted = self.abort_flags.borrow()
+                .get(&request_id)
+                .map(|flag| flag.get())
+                .unwrap_or(false);
+
+            if aborted {
+                let cancelled_obj = js_sys::Object::new();
+                js_sys::Reflect::set(&cancelled_obj, &"requestId".into(), &request_id.clone().into()).unwrap();
+                js_sys::Reflect::set(&cancelled_obj, &"type".into(), &"stream_cancelled".into()).unwrap();
+                js_sys::Reflect::set(&cancelled_obj, &"workerId".into(), &self.worker_id.clone().into()).unwrap();
+
+                // Remove before the fallible post below so a failed
+                // `post_message` (which exits via `?`) can't leak this
+                // request's entry in `abort_flags`.
+                self.abort_flags.borrow_mut().remove(&request_id);
+                reply_target.post_message_chunked(&cancelled_obj.into(), self.next_chunk_id())
+                    .map_err(|e| JSError::from(LLMRouterError::JavaScript {
+                        message: "Failed to post stream cancellation".to_string()
+                    }))?;
+
+                return Ok(());
+            }
+
             match stream_reader.read_chunk().await {
                 Ok(Some(chunk)) => {
                     let chunk_obj = js_sys::Object::new();
-                    js_sys::Reflect::set(&chunk_obj, &"requestId".into(), &request_id.into()).unwrap();
+                    js_sys::Reflect::set(&chunk_obj, &"requestId".into(), &request_id.clone().into()).unwrap();
                     js_sys::Reflect::set(&chunk_obj, &"type".into(), &"stream_chunk".into()).unwrap();
                     js_sys::Reflect::set(&chunk_obj, &"token".into(), &chunk.token().into()).unwrap();
                     js_sys::Reflect::set(&chunk_obj, &"isComplete".into(), &chunk.is_complete().into()).unwrap();
-                    
+                    js_sys::Reflect::set(&chunk_obj, &"workerId".into(), &self.worker_id.clone().into()).unwrap();
+
                     if let Some(error) = chunk.error() {
                         js_sys::Reflect::set(&chunk_obj, &"error".into(), &error.into()).unwrap();
                     }
-                    
-                    worker_scope.post_message(&chunk_obj.into())
-                        .map_err(|e| JSError::from(LLMRouterError::JavaScript {
+
+                    // Binary mode: move the token's bytes over rather than
+                    // cloning the `token` string, via a transferred buffer.
+                    let token_buffer = binary_tokens.then(|| {
+                        let bytes = js_sys::Uint8Array::from(chunk.token().as_bytes());
+                        let buffer = bytes.buffer();
+                        js_sys::Reflect::set(&chunk_obj, &"tokenBytes".into(), &buffer).unwrap();
+                        buffer
+                    });
+
+                    let post_result = match &token_buffer {
+                        Some(buffer) => {
+                            let transfer = js_sys::Array::new();
+                            transfer.push(buffer);
+                            reply_target.post_message_with_transfer(&chunk_obj.into(), &transfer.into())
+                        }
+                        None => reply_target.post_message_chunked(&chunk_obj.into(), self.next_chunk_id()),
+                    };
+
+                    if post_result.is_err() {
+                        self.abort_flags.borrow_mut().remove(&request_id);
+                        return Err(JSError::from(LLMRouterError::JavaScript {
                             message: "Failed to post stream chunk".to_string()
-                        }))?;
+                        }));
+                    }
 
                     if chunk.is_complete() {
                         break;
@@ -221,25 +443,31 @@ impl LLMRouterWorker {
                 Err(e) => {
                     // Post error back to main thread
                     let error_obj = js_sys::Object::new();
-                    js_sys::Reflect::set(&error_obj, &"requestId".into(), &request_id.into()).unwrap();
+                    js_sys::Reflect::set(&error_obj, &"requestId".into(), &request_id.clone().into()).unwrap();
                     js_sys::Reflect::set(&error_obj, &"type".into(), &"stream_error".into()).unwrap();
                     js_sys::Reflect::set(&error_obj, &"error".into(), &format_error(&e)).unwrap();
-                    
-                    worker_scope.post_message(&error_obj.into())
-                        .map_err(|e| JSError::from(LLMRouterError::JavaScript {
+                    js_sys::Reflect::set(&error_obj, &"workerId".into(), &self.worker_id.clone().into()).unwrap();
+
+                    if let Err(_post_err) = reply_target.post_message_chunked(&error_obj.into(), self.next_chunk_id()) {
+                        self.abort_flags.borrow_mut().remove(&request_id);
+                        return Err(JSError::from(LLMRouterError::JavaScript {
                             message: "Failed to post stream error".to_string()
-                        }))?;
+                        }));
+                    }
                     break;
                 }
             }
         }
 
+        self.abort_flags.borrow_mut().remove(&request_id);
+
         // Post stream completion
         let complete_obj = js_sys::Object::new();
         js_sys::Reflect::set(&complete_obj, &"requestId".into(), &request_id.into()).unwrap();
         js_sys::Reflect::set(&complete_obj, &"type".into(), &"stream_complete".into()).unwrap();
-        
-        worker_scope.post_message(&complete_obj.into())
+        js_sys::Reflect::set(&complete_obj, &"workerId".into(), &self.worker_id.clone().into()).unwrap();
+
+        reply_target.post_message_chunked(&complete_obj.into(), self.next_chunk_id())
             .map_err(|e| JSError::from(LLMRouterError::JavaScript {
                 message: "Failed to post stream completion".to_string()
             }))?;
@@ -248,26 +476,131 @@ impl LLMRouterWorker {
     }
 }
 
-/// Worker message handler setup
-#[wasm_bindgen(js_name = "setupWorkerMessageHandler")]
-pub fn setup_worker_message_handler(config: RouterConfig) -> Result<(), JSError> {
-    let global = js_sys::global();
-    let worker_scope = global.dyn_into::<DedicatedWorkerGlobalScope>()
-        .map_err(|e| JSError::from(LLMRouterError::JavaScript {
-            message: "Not running in a worker context".to_string()
-        }))?;
+/// The host contexts `setup_worker_message_handler` knows how to attach to.
+/// Resolved once from `js_sys::global()` so the router client works the same
+/// way whether it's loaded into a dedicated worker, a shared worker, or the
+/// main thread (the last mainly for local debugging without standing up a
+/// real worker).
+enum HostContext {
+    Dedicated(DedicatedWorkerGlobalScope),
+    Shared(SharedWorkerGlobalScope),
+    Window(web_sys::Window),
+}
 
-    let mut worker = LLMRouterWorker::new(config);
-    
-    let closure = Closure::wrap(Box::new(move |event: MessageEvent| {
-        let data = event.data();
-        
-        // Spawn async task to handle the message
-        wasm_bindgen_futures::spawn_local(async move {
-            let message_type = js_sys::Reflect::get(&data, &"type".into())
+impl HostContext {
+    fn current() -> Result<HostContext, JSError> {
+        let global = js_sys::global();
+        if let Ok(scope) = global.clone().dyn_into::<DedicatedWorkerGlobalScope>() {
+            return Ok(HostContext::Dedicated(scope));
+        }
+        if let Ok(scope) = global.clone().dyn_into::<SharedWorkerGlobalScope>() {
+            return Ok(HostContext::Shared(scope));
+        }
+        if let Ok(window) = global.dyn_into::<web_sys::Window>() {
+            return Ok(HostContext::Window(window));
+        }
+        Err(JSError::from(LLMRouterError::JavaScript {
+            message: "setupWorkerMessageHandler requires a dedicated worker, shared worker, or window context".to_string(),
+        }))
+    }
+}
+
+/// Builds the `onmessage` handler shared by every [`HostContext`]: reassembles
+/// `worker_chunk` frames, dispatches `"abort"` synchronously, and otherwise
+/// posts each response through [`ReplyTarget::resolve`] — which already
+/// checks `worker`'s default reply target (see
+/// [`LLMRouterWorker::set_default_reply_target`]) before falling back to
+/// `js_sys::global()`.
+fn build_message_closure(mut worker: LLMRouterWorker) -> Closure<dyn FnMut(MessageEvent)> {
+    let abort_flags = worker.abort_flags();
+    let worker_id = worker.worker_id();
+    // Reassembles `worker_chunk` frames sent by `ReplyTarget::post_message_chunked`
+    // (or an equivalent main-thread sender) back into the original message.
+    let incoming_chunks: Rc<RefCell<crate::chunking::ChunkManager>> =
+        Rc::new(RefCell::new(crate::chunking::ChunkManager::new()));
+
+    Closure::wrap(Box::new(move |event: MessageEvent| {
+        let mut data = event.data();
+
+        let mut message_type = js_sys::Reflect::get(&data, &"type".into())
+            .ok()
+            .and_then(|v| v.as_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        if message_type == "worker_chunk" {
+            let frame = js_sys::Reflect::get(&data, &"messageId".into())
+                .ok()
+                .and_then(|v| v.as_f64())
+                .zip(js_sys::Reflect::get(&data, &"index".into()).ok().and_then(|v| v.as_f64()))
+                .zip(js_sys::Reflect::get(&data, &"total".into()).ok().and_then(|v| v.as_f64()))
+                .zip(js_sys::Reflect::get(&data, &"bytes".into()).ok())
+                .map(|(((message_id, index), total), bytes)| crate::chunking::ChunkFrame {
+                    message_id: message_id as u64,
+                    index: index as u32,
+                    total: total as u32,
+                    bytes: js_sys::Uint8Array::new(&bytes).to_vec(),
+                });
+
+            let Some(frame) = frame else { return };
+
+            let Some(assembled) = incoming_chunks.borrow_mut().ingest(frame) else {
+                return; // Still waiting on more chunks for this message.
+            };
+
+            let Ok(json) = String::from_utf8(assembled) else { return };
+            let Ok(reassembled) = js_sys::JSON::parse(&json) else { return };
+
+            // Redispatch exactly as if the reassembled message had arrived
+            // in one `postMessage` call.
+            data = reassembled;
+            message_type = js_sys::Reflect::get(&data, &"type".into())
                 .ok()
                 .and_then(|v| v.as_string())
                 .unwrap_or_else(|| "unknown".to_string());
+        }
+
+        // Dispatched inline (not via `spawn_local`) so the flag flip is visible
+        // to a running `process_stream_inference` task on its very next loop
+        // iteration, rather than queued behind it.
+        if message_type == "abort" {
+            let target_request_id = js_sys::Reflect::get(&data, &"requestId".into())
+                .ok()
+                .and_then(|v| v.as_string());
+
+            let cancelled = target_request_id
+                .as_deref()
+                .and_then(|id| abort_flags.borrow().get(id).map(|flag| flag.set(true)))
+                .is_some();
+
+            // Shaped like `process_message`'s generic response wrapper
+            // (`requestId`/`type`/`success`/`data`/`timestamp`) so callers
+            // see the same `AbortResponse` shape whether "abort" was
+            // dispatched via `onmessage` or `processMessage` directly.
+            let data_obj = js_sys::Object::new();
+            js_sys::Reflect::set(&data_obj, &"cancelled".into(), &cancelled.into()).unwrap();
+
+            let response_obj = js_sys::Object::new();
+            js_sys::Reflect::set(
+                &response_obj,
+                &"requestId".into(),
+                &target_request_id.clone().unwrap_or_default().into(),
+            ).unwrap();
+            js_sys::Reflect::set(&response_obj, &"type".into(), &"abort_response".into()).unwrap();
+            js_sys::Reflect::set(&response_obj, &"success".into(), &true.into()).unwrap();
+            js_sys::Reflect::set(&response_obj, &"data".into(), &data_obj).unwrap();
+            js_sys::Reflect::set(&response_obj, &"timestamp".into(), &get_current_timestamp().into()).unwrap();
+            js_sys::Reflect::set(&response_obj, &"workerId".into(), &worker_id.clone().into()).unwrap();
+
+            if let Ok(target) = ReplyTarget::resolve(&data, worker.default_reply_target().as_ref()) {
+                let _ = target.post_message(&response_obj.into());
+            }
+            return;
+        }
+
+        // Spawn async task to handle every other message type
+        let fallback_target = worker.default_reply_target();
+        wasm_bindgen_futures::spawn_local(async move {
+            let reply_target = ReplyTarget::resolve(&data, fallback_target.as_ref());
 
             let response = if message_type == "stream_inference" {
                 match worker.process_stream_inference(&data).await {
@@ -291,16 +624,56 @@ pub fn setup_worker_message_handler(config: RouterConfig) -> Result<(), JSError>
                 }
             };
 
-            // Post response back to main thread
-            let global = js_sys::global();
-            if let Ok(worker_scope) = global.dyn_into::<DedicatedWorkerGlobalScope>() {
-                let _ = worker_scope.post_message(&response);
+            // Post response back to main thread, preferring the port
+            // entangled in the original message over the global scope, and
+            // splitting it into `worker_chunk` frames if it's too big for a
+            // single `postMessage`.
+            if let Ok(target) = reply_target {
+                let _ = target.post_message_chunked(&response, worker.next_chunk_id());
             }
         });
-    }) as Box<dyn FnMut(_)>);
+    }) as Box<dyn FnMut(_)>)
+}
 
-    worker_scope.set_onmessage(Some(closure.as_ref().unchecked_ref()));
-    closure.forget(); // Keep the closure alive
+/// Worker message handler setup. Works in a dedicated worker, a shared
+/// worker, or directly on the main thread — see [`HostContext`].
+#[wasm_bindgen(js_name = "setupWorkerMessageHandler")]
+pub fn setup_worker_message_handler(config: RouterConfig) -> Result<(), JSError> {
+    match HostContext::current()? {
+        HostContext::Dedicated(worker_scope) => {
+            let worker = LLMRouterWorker::new(config);
+            let closure = build_message_closure(worker);
+            worker_scope.set_onmessage(Some(closure.as_ref().unchecked_ref()));
+            closure.forget(); // Keep the closure alive
+        }
+        HostContext::Shared(shared_scope) => {
+            // A shared worker has no single "the worker" scope to post to;
+            // each connecting client gets its own port off the `"connect"`
+            // event, and that port — not the global scope — is where this
+            // connection's worker replies.
+            let onconnect = Closure::wrap(Box::new(move |event: MessageEvent| {
+                let Some(port) = event.ports().get(0).dyn_into::<MessagePort>().ok() else {
+                    return;
+                };
+                port.start(); // Ports delivered via "connect" start paused.
+
+                let worker = LLMRouterWorker::new(config.clone());
+                worker.set_default_reply_target(ReplyTarget::Port(port.clone()));
+                let closure = build_message_closure(worker);
+                port.set_onmessage(Some(closure.as_ref().unchecked_ref()));
+                closure.forget();
+            }) as Box<dyn FnMut(_)>);
+            shared_scope.set_onconnect(Some(onconnect.as_ref().unchecked_ref()));
+            onconnect.forget();
+        }
+        HostContext::Window(window) => {
+            let worker = LLMRouterWorker::new(config);
+            worker.set_default_reply_target(ReplyTarget::Window(window.clone()));
+            let closure = build_message_closure(worker);
+            window.set_onmessage(Some(closure.as_ref().unchecked_ref()));
+            closure.forget();
+        }
+    }
 
     log_with_timestamp("info", "Worker message handler setup complete");
     Ok(())
@@ -340,13 +713,323 @@ initWorker();
 "#, js_url, wasm_url)
 }
 
+/// Main-thread helper: entangle `message` to `worker` via a dedicated
+/// `MessageChannel` instead of the shared multiplexed `onmessage` handler.
+/// Creates the channel, attaches its second port to `message`'s `"port"`
+/// field, transfers that port when posting `message` to `worker`, and
+/// returns the first port so the caller can listen for just this request's
+/// replies without filtering by `requestId`.
+#[wasm_bindgen(js_name = "createEntangledPort")]
+pub fn create_entangled_port(worker: &Worker, message: &JsValue) -> Result<MessagePort, JSError> {
+    let message_obj = message.dyn_ref::<js_sys::Object>()
+        .ok_or_else(|| JSError::from(LLMRouterError::Validation {
+            message: "Invalid message format".to_string(),
+        }))?;
+
+    let channel = MessageChannel::new()
+        .map_err(|_| JSError::from(LLMRouterError::JavaScript {
+            message: "Failed to create MessageChannel".to_string(),
+        }))?;
+
+    let local_port = channel.port1();
+    let remote_port = channel.port2();
+
+    js_sys::Reflect::set(message_obj, &"port".into(), &remote_port)
+        .map_err(|_| JSError::from(LLMRouterError::JavaScript {
+            message: "Failed to attach entangled port to message".to_string(),
+        }))?;
+
+    let transfer = js_sys::Array::new();
+    transfer.push(&remote_port);
+
+    worker.post_message_with_transfer(message, &transfer)
+        .map_err(|_| JSError::from(LLMRouterError::JavaScript {
+            message: "Failed to post message with entangled port".to_string(),
+        }))?;
+
+    Ok(local_port)
+}
+
+/// Spawn a real `Worker` running the script [`create_worker_script`]
+/// produces, by serving it from a `Blob` object URL (no network round
+/// trip, and no separate script file for callers to host).
+fn spawn_pool_worker(wasm_url: &str, js_url: &str) -> Result<Worker, JSError> {
+    let script = create_worker_script(wasm_url, js_url);
+
+    let blob_parts = js_sys::Array::new();
+    blob_parts.push(&JsValue::from_str(&script));
+
+    let mut blob_options = BlobPropertyBag::new();
+    blob_options.type_("application/javascript");
+
+    let blob = Blob::new_with_str_sequence_and_options(&blob_parts, &blob_options)
+        .map_err(|_| JSError::from(LLMRouterError::JavaScript {
+            message: "Failed to create worker script blob".to_string(),
+        }))?;
+
+    let url = Url::create_object_url_with_blob(&blob)
+        .map_err(|_| JSError::from(LLMRouterError::JavaScript {
+            message: "Failed to create worker script URL".to_string(),
+        }))?;
+
+    Worker::new(&url).map_err(|_| JSError::from(LLMRouterError::JavaScript {
+        message: "Failed to spawn pool worker".to_string(),
+    }))
+}
+
+/// One `Worker` owned by [`LLMRouterWorkerPool`], tracking how many
+/// dispatched requests it hasn't replied to yet so `least_loaded_index`
+/// can pick a target. Not itself exposed to JS — only through the pool.
+struct PooledWorker {
+    handle: Worker,
+    in_flight: Rc<Cell<u32>>,
+}
+
+/// Load-balancing host for several [`LLMRouterWorker`] instances, modeled
+/// on Deno's `WorkersTable`: spawns `size` real `Worker`s running the
+/// script from [`create_worker_script`] and routes each `processMessage`/
+/// `processStreamInference` call to whichever one has the fewest
+/// outstanding requests, so callers get transparent parallelism without
+/// managing individual workers or `requestId` routing themselves.
+#[wasm_bindgen]
+pub struct LLMRouterWorkerPool {
+    #[wasm_bindgen(skip)]
+    workers: Rc<RefCell<Vec<PooledWorker>>>,
+    /// One-shot resolvers for in-flight `processMessage` calls, keyed by
+    /// `requestId`, fulfilled by whichever worker's `onmessage` handler
+    /// sees a reply carrying that id.
+    #[wasm_bindgen(skip)]
+    pending: Rc<RefCell<HashMap<String, oneshot::Sender<JsValue>>>>,
+    /// JS callbacks registered by `processStreamInference`, invoked for
+    /// every `stream_chunk`/`stream_complete`/`stream_error`/`stream_cancelled`
+    /// carrying that `requestId`, and removed on the terminal one.
+    #[wasm_bindgen(skip)]
+    stream_callbacks: Rc<RefCell<HashMap<String, js_sys::Function>>>,
+}
+
+#[wasm_bindgen]
+impl LLMRouterWorkerPool {
+    /// Spawn `size` workers, each running the script [`create_worker_script`]
+    /// produces for `wasm_url`/`js_url`.
+    ///
+    /// `config` is accepted for parity with [`LLMRouterWorker::new`] so
+    /// callers configure the pool the same way they'd configure a single
+    /// worker; it isn't forwarded into the spawned workers yet, since
+    /// `create_worker_script`'s bootstrap builds its own `RouterConfig`
+    /// rather than accepting one over the wire.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        config: RouterConfig,
+        size: u32,
+        wasm_url: String,
+        js_url: String,
+    ) -> Result<LLMRouterWorkerPool, JSError> {
+        let _ = config;
+
+        let pending: Rc<RefCell<HashMap<String, oneshot::Sender<JsValue>>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let stream_callbacks: Rc<RefCell<HashMap<String, js_sys::Function>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+
+        let mut workers = Vec::with_capacity(size as usize);
+        for _ in 0..size {
+            let handle = spawn_pool_worker(&wasm_url, &js_url)?;
+            let in_flight = Rc::new(Cell::new(0u32));
+
+            let pending_for_worker = pending.clone();
+            let stream_callbacks_for_worker = stream_callbacks.clone();
+            let in_flight_for_worker = in_flight.clone();
+
+            let onmessage = Closure::wrap(Box::new(move |event: MessageEvent| {
+                let data = event.data();
+
+                let message_type = js_sys::Reflect::get(&data, &"type".into())
+                    .ok()
+                    .and_then(|v| v.as_string())
+                    .unwrap_or_default();
+
+                let request_id = match js_sys::Reflect::get(&data, &"requestId".into())
+                    .ok()
+                    .and_then(|v| v.as_string())
+                {
+                    Some(id) => id,
+                    // "worker_ready" / "worker_error" aren't tied to a request
+                    None => return,
+                };
+
+                if let Some(sender) = pending_for_worker.borrow_mut().remove(&request_id) {
+                    in_flight_for_worker.set(in_flight_for_worker.get().saturating_sub(1));
+                    let _ = sender.send(data);
+                    return;
+                }
+
+                let is_terminal_stream_event = matches!(
+                    message_type.as_str(),
+                    "stream_complete" | "stream_error" | "stream_cancelled"
+                );
+
+                let callback = if is_terminal_stream_event {
+                    stream_callbacks_for_worker.borrow_mut().remove(&request_id)
+                } else {
+                    stream_callbacks_for_worker.borrow().get(&request_id).cloned()
+                };
+
+                if let Some(callback) = callback {
+                    if is_terminal_stream_event {
+                        in_flight_for_worker.set(in_flight_for_worker.get().saturating_sub(1));
+                    }
+                    let _ = callback.call1(&JsValue::undefined(), &data);
+                }
+            }) as Box<dyn FnMut(_)>);
+
+            handle.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+            onmessage.forget(); // Keep the closure alive for the worker's lifetime
+
+            workers.push(PooledWorker { handle, in_flight });
+        }
+
+        Ok(LLMRouterWorkerPool {
+            workers: Rc::new(RefCell::new(workers)),
+            pending,
+            stream_callbacks,
+        })
+    }
+
+    /// Number of workers in the pool.
+    #[wasm_bindgen(getter, js_name = "size")]
+    pub fn size(&self) -> u32 {
+        self.workers.borrow().len() as u32
+    }
+
+    /// Index of the worker with the fewest outstanding requests, ties
+    /// broken by pool order.
+    fn least_loaded_index(&self) -> usize {
+        let workers = self.workers.borrow();
+        workers
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, worker)| worker.in_flight.get())
+            .map(|(index, _)| index)
+            .unwrap_or(0)
+    }
+
+    /// Route `message` to the least-loaded worker and resolve with its
+    /// reply, correlated by `requestId` (assigned here if the caller
+    /// didn't supply one).
+    #[wasm_bindgen(js_name = "processMessage")]
+    pub async fn process_message(&self, message: &JsValue) -> Result<JsValue, JSError> {
+        let message_obj = message.dyn_ref::<js_sys::Object>()
+            .ok_or_else(|| JSError::from(LLMRouterError::Validation {
+                message: "Invalid message format".to_string(),
+            }))?;
+
+        let request_id = js_sys::Reflect::get(message_obj, &"requestId".into())
+            .ok()
+            .and_then(|v| v.as_string())
+            .unwrap_or_else(generate_uuid);
+        js_sys::Reflect::set(message_obj, &"requestId".into(), &request_id.clone().into()).unwrap();
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.borrow_mut().insert(request_id.clone(), tx);
+
+        let index = self.least_loaded_index();
+        let post_result = {
+            let workers = self.workers.borrow();
+            let pooled = &workers[index];
+            pooled.in_flight.set(pooled.in_flight.get() + 1);
+            pooled.handle.post_message(message)
+        };
+
+        if post_result.is_err() {
+            self.workers.borrow()[index].in_flight.set(
+                self.workers.borrow()[index].in_flight.get().saturating_sub(1),
+            );
+            self.pending.borrow_mut().remove(&request_id);
+            return Err(JSError::from(LLMRouterError::JavaScript {
+                message: "Failed to post message to pool worker".to_string(),
+            }));
+        }
+
+        rx.await.map_err(|_| JSError::from(LLMRouterError::Other {
+            message: "Pool worker was terminated before it replied".to_string(),
+        }))
+    }
+
+    /// Route a streaming inference `message` to the least-loaded worker.
+    /// `on_event` is invoked with every `stream_chunk`/`stream_complete`/
+    /// `stream_error`/`stream_cancelled` message carrying the returned
+    /// `requestId`. Returns as soon as the message is dispatched — chunks
+    /// arrive asynchronously through `on_event`, not the return value,
+    /// mirroring how [`LLMRouterWorker::process_stream_inference`] also
+    /// reports completion out-of-band.
+    #[wasm_bindgen(js_name = "processStreamInference")]
+    pub fn process_stream_inference(
+        &self,
+        message: &JsValue,
+        on_event: &js_sys::Function,
+    ) -> Result<String, JSError> {
+        let message_obj = message.dyn_ref::<js_sys::Object>()
+            .ok_or_else(|| JSError::from(LLMRouterError::Validation {
+                message: "Invalid message format".to_string(),
+            }))?;
+
+        let request_id = js_sys::Reflect::get(message_obj, &"requestId".into())
+            .ok()
+            .and_then(|v| v.as_string())
+            .unwrap_or_else(generate_uuid);
+        js_sys::Reflect::set(message_obj, &"requestId".into(), &request_id.clone().into()).unwrap();
+
+        self.stream_callbacks.borrow_mut().insert(request_id.clone(), on_event.clone());
+
+        let index = self.least_loaded_index();
+        let post_result = {
+            let workers = self.workers.borrow();
+            let pooled = &workers[index];
+            pooled.in_flight.set(pooled.in_flight.get() + 1);
+            pooled.handle.post_message(message)
+        };
+
+        if post_result.is_err() {
+            self.workers.borrow()[index].in_flight.set(
+                self.workers.borrow()[index].in_flight.get().saturating_sub(1),
+            );
+            self.stream_callbacks.borrow_mut().remove(&request_id);
+            return Err(JSError::from(LLMRouterError::JavaScript {
+                message: "Failed to post stream inference message to pool worker".to_string(),
+            }));
+        }
+
+        Ok(request_id)
+    }
+
+    /// Terminate every worker in the pool. Any `processMessage` call still
+    /// awaiting a reply resolves to an error, and any `processStreamInference`
+    /// callback simply stops being invoked.
+    pub fn terminate(&self) {
+        for pooled in self.workers.borrow().iter() {
+            pooled.handle.terminate();
+        }
+        self.workers.borrow_mut().clear();
+        self.pending.borrow_mut().clear();
+        self.stream_callbacks.borrow_mut().clear();
+    }
+}
+
 #[wasm_bindgen(typescript_custom_section)]
 const TS_WORKER_APPEND: &'static str = r#"
+/** `type`/`payload` mirror the Rust-side `WorkerRequest` enum in
+ * `protocol.rs` (`health_check`, `get_status`, `list_models`, `load_model`,
+ * `inference`, `quick_inference`, `set_session_id`, `clear_session`,
+ * `abort`) — add a variant there and its shape here in the same change. */
 export interface WorkerMessage {
     type: string;
     requestId?: string;
     payload?: any;
     timestamp?: number;
+    /** Set by `createEntangledPort` to route this message's replies to a
+     * dedicated `MessageChannel` port instead of the shared `onmessage`
+     * handler. */
+    port?: MessagePort;
 }
 
 export interface WorkerResponse {
@@ -356,6 +1039,7 @@ export interface WorkerResponse {
     data?: any;
     error?: any;
     timestamp: number;
+    workerId: string;
 }
 
 export interface StreamChunk {
@@ -364,5 +1048,40 @@ export interface StreamChunk {
     token: string;
     isComplete: boolean;
     error?: string;
+    workerId: string;
+    /** Present when the request set `binaryTokens: true` — `token`'s UTF-8
+     * bytes, transferred rather than copied. */
+    tokenBytes?: ArrayBuffer;
+}
+
+export interface StreamCancelledMessage {
+    requestId: string;
+    type: 'stream_cancelled';
+    workerId: string;
+}
+
+export interface AbortMessage {
+    type: 'abort';
+    requestId: string;
+}
+
+export interface AbortResponse {
+    requestId: string;
+    type: 'abort_response';
+    success: boolean;
+    data: { cancelled: boolean };
+    timestamp: number;
+    workerId: string;
+}
+
+/** One frame of a message too large for a single `postMessage`, reassembled
+ * transparently by the worker's `onmessage` handler; never seen directly by
+ * application code on either side. */
+export interface WorkerChunkFrame {
+    type: 'worker_chunk';
+    messageId: number;
+    index: number;
+    total: number;
+    bytes: Uint8Array;
 }
 "#;
\ No newline at end of file
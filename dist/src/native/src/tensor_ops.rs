@@ -3,8 +3,101 @@
 use crate::{error::{NativeError, Result}, perf::Timer};
 use napi_derive::napi;
 use candle_core::{Tensor, Device, DType, Shape};
+use gemm::{gemm, Parallelism};
+use half::f16;
 use rayon::prelude::*;
 
+/// Shared implementation behind `TensorOps::quiet_softmax` and
+/// `TensorOps::attention`'s `quiet` option: softmax with 1 added to the
+/// max-shifted denominator, `exp(x_i - m) / (1 + sum_j exp(x_j - m))`.
+fn quiet_softmax(tensor: &Tensor, dim: usize) -> candle_core::Result<Tensor> {
+    let max = tensor.max_keepdim(dim)?;
+    let exp = tensor.broadcast_sub(&max)?.exp()?;
+    let denom = exp.sum_keepdim(dim)?.affine(1.0, 1.0)?;
+    exp.broadcast_div(&denom)
+}
+
+/// Additive mask for causal `flash_attention`: `0.0` where key position
+/// `k_start + j <= i`, `-inf` otherwise, so it can be added to a block's
+/// scores before the running softmax update.
+fn causal_additive_mask(
+    seq_q: usize,
+    k_start: usize,
+    k_len: usize,
+    device: &Device,
+) -> candle_core::Result<Tensor> {
+    let mut data = vec![0f32; seq_q * k_len];
+    for i in 0..seq_q {
+        for j in 0..k_len {
+            if k_start + j > i {
+                data[i * k_len + j] = f32::NEG_INFINITY;
+            }
+        }
+    }
+    Tensor::from_vec(data, (seq_q, k_len), device)
+}
+
+/// Tiled, memory-efficient attention: never materializes the full `Q·Kᵀ`
+/// scores matrix, instead streaming over key/value blocks and maintaining a
+/// running row-max, row-sum, and output accumulator (the "online softmax"
+/// trick), so peak memory is `O(block_size·d)` instead of `O(seq_q·seq_k)`.
+/// Produces results numerically equivalent to the dense softmax path.
+fn flash_attention_impl(
+    query: &Tensor,
+    key: &Tensor,
+    value: &Tensor,
+    scale: f64,
+    causal: bool,
+    block_size: usize,
+) -> candle_core::Result<Tensor> {
+    let (seq_q, _) = query.dims2()?;
+    let (seq_k, _) = key.dims2()?;
+    let d_v = value.dim(1)?;
+    let device = query.device();
+    let dtype = query.dtype();
+
+    let mut acc = Tensor::zeros((seq_q, d_v), dtype, device)?;
+    let mut row_max = Tensor::full(f32::NEG_INFINITY, (seq_q, 1), device)?;
+    let mut row_sum = Tensor::zeros((seq_q, 1), dtype, device)?;
+
+    let mut k_start = 0usize;
+    while k_start < seq_k {
+        let k_len = block_size.min(seq_k - k_start);
+
+        // A block that starts after the last query row is entirely in the
+        // future for every row, so it can be skipped outright.
+        if causal && k_start > seq_q.saturating_sub(1) {
+            k_start += k_len;
+            continue;
+        }
+
+        let key_block = key.narrow(0, k_start, k_len)?;
+        let value_block = value.narrow(0, k_start, k_len)?;
+
+        let mut block_scores = (query.matmul(&key_block.t()?)? * scale)?;
+        if causal {
+            let mask = causal_additive_mask(seq_q, k_start, k_len, device)?;
+            block_scores = block_scores.broadcast_add(&mask)?;
+        }
+
+        let block_max = block_scores.max_keepdim(1)?;
+        let new_max = row_max.maximum(&block_max)?;
+        let correction = row_max.sub(&new_max)?.exp()?;
+        let block_exp = block_scores.broadcast_sub(&new_max)?.exp()?;
+        let block_row_sum = block_exp.sum_keepdim(1)?;
+
+        row_sum = row_sum.mul(&correction)?.add(&block_row_sum)?;
+        let acc_scaled = acc.broadcast_mul(&correction)?;
+        let block_out = block_exp.matmul(&value_block)?;
+        acc = (&acc_scaled + &block_out)?;
+        row_max = new_max;
+
+        k_start += k_len;
+    }
+
+    acc.broadcast_div(&row_sum)
+}
+
 /// Tensor operation utilities
 #[napi]
 pub struct TensorOps;
@@ -23,6 +116,38 @@ impl TensorOps {
         Ok(TensorWrapper { tensor })
     }
 
+    /// Create a new tensor from dtype-tagged data, preserving native
+    /// precision (f32/f16/i64/u32) across the NAPI boundary instead of
+    /// always going through `Vec<f32>`.
+    #[napi(factory)]
+    pub fn from_data_typed(data: TensorData, shape: Vec<u32>) -> napi::Result<TensorWrapper> {
+        let shape: Vec<usize> = shape.iter().map(|&x| x as usize).collect();
+        let device = Device::Cpu;
+
+        let tensor = match data.dtype.as_str() {
+            "f32" => {
+                let values = data.f32_values.ok_or_else(|| NativeError::invalid_input("Missing f32_values for dtype \"f32\""))?;
+                Tensor::from_vec(values, &shape, &device)
+            }
+            "f16" => {
+                let values = data.f16_bits.ok_or_else(|| NativeError::invalid_input("Missing f16_bits for dtype \"f16\""))?;
+                let values: Vec<f16> = values.into_iter().map(f16::from_bits).collect();
+                Tensor::from_vec(values, &shape, &device)
+            }
+            "i64" => {
+                let values = data.i64_values.ok_or_else(|| NativeError::invalid_input("Missing i64_values for dtype \"i64\""))?;
+                Tensor::from_vec(values, &shape, &device)
+            }
+            "u32" => {
+                let values = data.u32_values.ok_or_else(|| NativeError::invalid_input("Missing u32_values for dtype \"u32\""))?;
+                Tensor::from_vec(values, &shape, &device)
+            }
+            other => return Err(NativeError::invalid_input(format!("Unsupported dtype: {}", other)).into()),
+        }.map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(TensorWrapper { tensor })
+    }
+
     /// Perform matrix multiplication with SIMD optimization
     #[napi]
     pub fn matmul(a: &TensorWrapper, b: &TensorWrapper) -> napi::Result<TensorWrapper> {
@@ -34,6 +159,57 @@ impl TensorOps {
         Ok(TensorWrapper { tensor: result })
     }
 
+    /// Matrix multiplication via the `gemm` crate's blocked/tiled kernel
+    /// instead of candle's default path, for faster large f32 matmuls.
+    /// `num_threads` selects `Parallelism::Rayon(n)`; `None` or `Some(0)`
+    /// lets `gemm` size its own thread pool.
+    #[napi]
+    pub fn matmul_fast(a: &TensorWrapper, b: &TensorWrapper, num_threads: Option<u32>) -> napi::Result<TensorWrapper> {
+        let _timer = Timer::new("tensor_matmul_fast");
+
+        let (m, k) = a.tensor.dims2().map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let (k2, n) = b.tensor.dims2().map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        if k != k2 {
+            return Err(NativeError::invalid_input(format!(
+                "Inner dimensions must match for matmul_fast: {} vs {}", k, k2
+            )).into());
+        }
+
+        let a_data = a.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let b_data = b.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let mut out = vec![0f32; m * n];
+
+        let parallelism = match num_threads {
+            Some(threads) if threads > 0 => Parallelism::Rayon(threads as usize),
+            _ => Parallelism::Rayon(0),
+        };
+
+        // Safety: `out`, `a_data`, `b_data` are contiguous row-major buffers
+        // sized exactly m*n, m*k, and k*n, matching the strides passed below.
+        unsafe {
+            gemm(
+                m, n, k,
+                out.as_mut_ptr(), 1, n as isize,
+                false,
+                a_data.as_ptr(), 1, k as isize,
+                b_data.as_ptr(), 1, n as isize,
+                0.0f32, 1.0f32,
+                false, false, false,
+                parallelism,
+            );
+        }
+
+        let tensor = Tensor::from_vec(out, (m, n), &Device::Cpu)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(TensorWrapper { tensor })
+    }
+
     /// Element-wise addition
     #[napi]
     pub fn add(a: &TensorWrapper, b: &TensorWrapper) -> napi::Result<TensorWrapper> {
@@ -69,6 +245,27 @@ impl TensorOps {
         Ok(TensorWrapper { tensor: result })
     }
 
+    /// Compute "quiet" softmax (aka softmax1): `softmax1(x)_i = exp(x_i - m)
+    /// / (1 + sum_j exp(x_j - m))`, where `m = max_j(x_j)` for numerical
+    /// stability. Unlike `softmax`, a row can sum to less than one, so
+    /// attention rows can attend to "nothing" — this reduces activation
+    /// outliers and improves quantizability of transformer weights.
+    #[napi]
+    pub fn quiet_softmax(tensor: &TensorWrapper, dim: i32) -> napi::Result<TensorWrapper> {
+        let _timer = Timer::new("tensor_quiet_softmax");
+
+        let dim = if dim < 0 {
+            (tensor.tensor.dims().len() as i32 + dim) as usize
+        } else {
+            dim as usize
+        };
+
+        let result = quiet_softmax(&tensor.tensor, dim)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(TensorWrapper { tensor: result })
+    }
+
     /// Compute layer normalization
     #[napi]
     pub fn layer_norm(
@@ -120,6 +317,7 @@ impl TensorOps {
         value: &TensorWrapper,
         mask: Option<&TensorWrapper>,
         scale: Option<f64>,
+        quiet: Option<bool>,
     ) -> napi::Result<TensorWrapper> {
         let _timer = Timer::new("tensor_attention");
         
@@ -148,9 +346,14 @@ impl TensorOps {
             scores
         };
         
-        // Apply softmax
-        let attention_weights = candle_nn::ops::softmax(&scores, scores.dims().len() - 1)
-            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        // Apply softmax (or quiet_softmax, which lets a row attend to
+        // "nothing" instead of being forced to sum to one)
+        let last_dim = scores.dims().len() - 1;
+        let attention_weights = if quiet.unwrap_or(false) {
+            quiet_softmax(&scores, last_dim)
+        } else {
+            candle_nn::ops::softmax(&scores, last_dim)
+        }.map_err(|e| NativeError::tensor_op(e.to_string()))?;
         
         // Apply to values
         let result = attention_weights.matmul(&value.tensor)
@@ -159,6 +362,40 @@ impl TensorOps {
         Ok(TensorWrapper { tensor: result })
     }
 
+    /// Memory-efficient fused attention with online (streaming) softmax.
+    /// Tiles over key/value blocks instead of materializing the full
+    /// `Q·Kᵀ` scores matrix, so memory stays `O(block_size·d)` rather than
+    /// `O(seq_q·seq_k)`. Set `causal` to skip/mask blocks above the
+    /// diagonal for autoregressive decoding, and tune `block_size` to trade
+    /// off peak memory against per-block overhead.
+    #[napi]
+    pub fn flash_attention(
+        query: &TensorWrapper,
+        key: &TensorWrapper,
+        value: &TensorWrapper,
+        scale: Option<f64>,
+        causal: Option<bool>,
+        block_size: Option<u32>,
+    ) -> napi::Result<TensorWrapper> {
+        let _timer = Timer::new("tensor_flash_attention");
+
+        let scale = scale.unwrap_or_else(|| {
+            let d_k = query.tensor.dim(query.tensor.dims().len() - 1).unwrap_or(1) as f64;
+            1.0 / d_k.sqrt()
+        });
+
+        let result = flash_attention_impl(
+            &query.tensor,
+            &key.tensor,
+            &value.tensor,
+            scale,
+            causal.unwrap_or(false),
+            block_size.unwrap_or(128).max(1) as usize,
+        ).map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(TensorWrapper { tensor: result })
+    }
+
     /// Parallel reduction operations
     #[napi]
     pub fn parallel_sum(tensor: &TensorWrapper, dim: Option<i32>) -> napi::Result<TensorWrapper> {
@@ -207,9 +444,136 @@ impl TensorOps {
     pub fn transpose(tensor: &TensorWrapper, dim1: u32, dim2: u32) -> napi::Result<TensorWrapper> {
         let result = tensor.tensor.transpose(dim1 as usize, dim2 as usize)
             .map_err(|e| NativeError::tensor_op(e.to_string()))?;
-        
+
         Ok(TensorWrapper { tensor: result })
     }
+
+    /// Asymmetric per-tensor int8 quantization: `q = clamp(round(x/scale) +
+    /// zero_point, 0, 255)`. Lets callers store weights at ~4x lower memory
+    /// than f32.
+    #[napi]
+    pub fn quantize(tensor: &TensorWrapper, scale: f64, zero_point: i32) -> napi::Result<QuantizedTensor> {
+        let _timer = Timer::new("tensor_quantize");
+
+        let shape = tensor.tensor.dims().to_vec();
+        let data = tensor.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        let quantized: Vec<u8> = data.iter()
+            .map(|&x| quantize_value(x as f64, scale, zero_point))
+            .collect();
+
+        let tensor = Tensor::from_vec(quantized, shape, &Device::Cpu)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(QuantizedTensor { tensor, scale, zero_point })
+    }
+
+    /// Reverse `quantize`: `x = scale·(q - zero_point)`.
+    #[napi]
+    pub fn dequantize(quantized: &QuantizedTensor) -> napi::Result<TensorWrapper> {
+        let _timer = Timer::new("tensor_dequantize");
+
+        let shape = quantized.tensor.dims().to_vec();
+        let data = quantized.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<u8>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        let dequantized: Vec<f32> = data.iter()
+            .map(|&q| (quantized.scale * (q as f64 - quantized.zero_point as f64)) as f32)
+            .collect();
+
+        let tensor = Tensor::from_vec(dequantized, shape, &Device::Cpu)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(TensorWrapper { tensor })
+    }
+
+    /// Quantized matmul: accumulates `(a_q - zero_point_a)·(b_q -
+    /// zero_point_b)` in i32, rescales by `scale_a·scale_b`, and requantizes
+    /// the result against `output_scale`/`output_zero_point`.
+    #[napi]
+    pub fn quantized_matmul(
+        a: &QuantizedTensor,
+        b: &QuantizedTensor,
+        output_scale: f64,
+        output_zero_point: i32,
+    ) -> napi::Result<QuantizedTensor> {
+        let _timer = Timer::new("tensor_quantized_matmul");
+
+        let (m, k) = a.tensor.dims2().map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let (k2, n) = b.tensor.dims2().map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        if k != k2 {
+            return Err(NativeError::invalid_input(format!(
+                "Inner dimensions must match for quantized_matmul: {} vs {}", k, k2
+            )).into());
+        }
+
+        let a_data = a.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<u8>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let b_data = b.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<u8>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        let combined_scale = a.scale * b.scale;
+        let mut out = vec![0u8; m * n];
+
+        for i in 0..m {
+            for j in 0..n {
+                let mut acc: i32 = 0;
+                for p in 0..k {
+                    let av = a_data[i * k + p] as i32 - a.zero_point;
+                    let bv = b_data[p * n + j] as i32 - b.zero_point;
+                    acc += av * bv;
+                }
+                out[i * n + j] = quantize_value(combined_scale * acc as f64, output_scale, output_zero_point);
+            }
+        }
+
+        let tensor = Tensor::from_vec(out, (m, n), &Device::Cpu)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(QuantizedTensor { tensor, scale: output_scale, zero_point: output_zero_point })
+    }
+
+    /// Quantized softmax: dequantizes, runs the stable dense `softmax`, and
+    /// requantizes against `output_scale`/`output_zero_point`.
+    #[napi]
+    pub fn quantized_softmax(
+        quantized: &QuantizedTensor,
+        dim: i32,
+        output_scale: f64,
+        output_zero_point: i32,
+    ) -> napi::Result<QuantizedTensor> {
+        let _timer = Timer::new("tensor_quantized_softmax");
+
+        let dequantized = Self::dequantize(quantized)?;
+        let softmaxed = Self::softmax(&dequantized, dim)?;
+        Self::quantize(&softmaxed, output_scale, output_zero_point)
+    }
+}
+
+/// `round(x/scale) + zero_point`, clamped to the `[0, 255]` int8-as-u8 range.
+fn quantize_value(x: f64, scale: f64, zero_point: i32) -> u8 {
+    let q = (x / scale).round() as i64 + zero_point as i64;
+    q.clamp(0, 255) as u8
+}
+
+/// Tagged tensor data crossing the NAPI boundary in its native dtype:
+/// exactly one of `f32_values`/`f16_bits`/`i64_values`/`u32_values` is set,
+/// matching `dtype` (`"f32"`, `"f16"`, `"i64"`, or `"u32"`). `f16_bits`
+/// holds each half-precision value's raw bit pattern, since NAPI has no
+/// native f16 type.
+#[napi(object)]
+pub struct TensorData {
+    pub dtype: String,
+    pub f32_values: Option<Vec<f32>>,
+    pub f16_bits: Option<Vec<u16>>,
+    pub i64_values: Option<Vec<i64>>,
+    pub u32_values: Option<Vec<u32>>,
 }
 
 /// Wrapper for Candle tensors to work with NAPI
@@ -249,6 +613,49 @@ impl TensorWrapper {
         Ok(result)
     }
 
+    /// Convert tensor to a dtype-tagged flat array, preserving native
+    /// precision instead of lossily converting through f32.
+    #[napi]
+    pub fn to_vec_typed(&self) -> napi::Result<TensorData> {
+        let flat = self.tensor.flatten_all()
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        let data = match flat.dtype() {
+            DType::F32 => TensorData {
+                dtype: "f32".to_string(),
+                f32_values: Some(flat.to_vec1::<f32>().map_err(|e| NativeError::tensor_op(e.to_string()))?),
+                f16_bits: None,
+                i64_values: None,
+                u32_values: None,
+            },
+            DType::F16 => TensorData {
+                dtype: "f16".to_string(),
+                f32_values: None,
+                f16_bits: Some(flat.to_vec1::<f16>().map_err(|e| NativeError::tensor_op(e.to_string()))?
+                    .into_iter().map(|v| v.to_bits()).collect()),
+                i64_values: None,
+                u32_values: None,
+            },
+            DType::I64 => TensorData {
+                dtype: "i64".to_string(),
+                f32_values: None,
+                f16_bits: None,
+                i64_values: Some(flat.to_vec1::<i64>().map_err(|e| NativeError::tensor_op(e.to_string()))?),
+                u32_values: None,
+            },
+            DType::U32 => TensorData {
+                dtype: "u32".to_string(),
+                f32_values: None,
+                f16_bits: None,
+                i64_values: None,
+                u32_values: Some(flat.to_vec1::<u32>().map_err(|e| NativeError::tensor_op(e.to_string()))?),
+            },
+            other => return Err(NativeError::invalid_input(format!("Unsupported dtype for to_vec_typed: {:?}", other)).into()),
+        };
+
+        Ok(data)
+    }
+
     /// Get a scalar value (for 0-dimensional tensors)
     #[napi]
     pub fn to_scalar(&self) -> napi::Result<f32> {
@@ -296,78 +703,404 @@ impl TensorWrapper {
 
         let result = self.tensor.to_dtype(target_dtype)
             .map_err(|e| NativeError::tensor_op(e.to_string()))?;
-        
+
         Ok(TensorWrapper { tensor: result })
     }
 }
 
+/// Asymmetric per-tensor int8 quantized data: a `DType::U8` tensor of codes
+/// in `[0, 255]`, plus the `scale`/`zero_point` needed to dequantize it.
+#[napi]
+pub struct QuantizedTensor {
+    pub(crate) tensor: Tensor,
+    pub(crate) scale: f64,
+    pub(crate) zero_point: i32,
+}
+
+#[napi]
+impl QuantizedTensor {
+    /// Get tensor shape
+    #[napi]
+    pub fn shape(&self) -> Vec<u32> {
+        self.tensor.dims().iter().map(|&x| x as u32).collect()
+    }
+
+    /// Get the quantization scale
+    #[napi]
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Get the quantization zero point
+    #[napi]
+    pub fn zero_point(&self) -> i32 {
+        self.zero_point
+    }
+
+    /// Get the raw quantized codes (each in `[0, 255]`)
+    #[napi]
+    pub fn codes(&self) -> napi::Result<Vec<u8>> {
+        let result = self.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<u8>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(result)
+    }
+}
+
 /// SIMD-optimized operations
 pub mod simd {
     use super::*;
     use wide::f32x8;
-    
+
     /// SIMD vector addition
     #[cfg(target_arch = "x86_64")]
     pub fn add_vectors_simd(a: &[f32], b: &[f32]) -> Vec<f32> {
         if !is_x86_feature_detected!("avx") || a.len() != b.len() {
             return a.iter().zip(b.iter()).map(|(x, y)| x + y).collect();
         }
-        
+
         let mut result = Vec::with_capacity(a.len());
         let chunks_a = a.chunks_exact(8);
         let chunks_b = b.chunks_exact(8);
         let remainder_a = chunks_a.remainder();
         let remainder_b = chunks_b.remainder();
-        
+
         for (chunk_a, chunk_b) in chunks_a.zip(chunks_b) {
             let vec_a = f32x8::from_array(*chunk_a.try_into().unwrap());
             let vec_b = f32x8::from_array(*chunk_b.try_into().unwrap());
             let sum = vec_a + vec_b;
             result.extend_from_slice(&sum.to_array());
         }
-        
+
         // Handle remainder
         result.extend(remainder_a.iter().zip(remainder_b.iter()).map(|(x, y)| x + y));
         result
     }
-    
-    /// Fallback for non-x86 architectures
-    #[cfg(not(target_arch = "x86_64"))]
+
+    /// NEON-backed vector addition for aarch64 (Apple Silicon, ARM servers)
+    #[cfg(target_arch = "aarch64")]
+    pub fn add_vectors_simd(a: &[f32], b: &[f32]) -> Vec<f32> {
+        if !std::arch::is_aarch64_feature_detected!("neon") || a.len() != b.len() {
+            return a.iter().zip(b.iter()).map(|(x, y)| x + y).collect();
+        }
+
+        use std::arch::aarch64::{vaddq_f32, vld1q_f32, vst1q_f32};
+
+        let mut result = vec![0f32; a.len()];
+        let chunks = a.len() / 4;
+
+        for i in 0..chunks {
+            unsafe {
+                let vec_a = vld1q_f32(a[i * 4..].as_ptr());
+                let vec_b = vld1q_f32(b[i * 4..].as_ptr());
+                let sum = vaddq_f32(vec_a, vec_b);
+                vst1q_f32(result[i * 4..].as_mut_ptr(), sum);
+            }
+        }
+
+        for i in (chunks * 4)..a.len() {
+            result[i] = a[i] + b[i];
+        }
+
+        result
+    }
+
+    /// Fallback for architectures without a dedicated SIMD path above
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
     pub fn add_vectors_simd(a: &[f32], b: &[f32]) -> Vec<f32> {
         a.iter().zip(b.iter()).map(|(x, y)| x + y).collect()
     }
-    
+
     /// SIMD dot product
     #[cfg(target_arch = "x86_64")]
     pub fn dot_product_simd(a: &[f32], b: &[f32]) -> f32 {
         if !is_x86_feature_detected!("avx") || a.len() != b.len() {
             return a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
         }
-        
+
         let mut sum = f32x8::ZERO;
         let chunks_a = a.chunks_exact(8);
         let chunks_b = b.chunks_exact(8);
         let remainder_a = chunks_a.remainder();
         let remainder_b = chunks_b.remainder();
-        
+
         for (chunk_a, chunk_b) in chunks_a.zip(chunks_b) {
             let vec_a = f32x8::from_array(*chunk_a.try_into().unwrap());
             let vec_b = f32x8::from_array(*chunk_b.try_into().unwrap());
             sum += vec_a * vec_b;
         }
-        
+
         let mut result = sum.reduce_add();
-        
+
         // Handle remainder
         result += remainder_a.iter().zip(remainder_b.iter()).map(|(x, y)| x * y).sum::<f32>();
         result
     }
-    
-    /// Fallback for non-x86 architectures
-    #[cfg(not(target_arch = "x86_64"))]
+
+    /// NEON-backed dot product for aarch64
+    #[cfg(target_arch = "aarch64")]
+    pub fn dot_product_simd(a: &[f32], b: &[f32]) -> f32 {
+        if !std::arch::is_aarch64_feature_detected!("neon") || a.len() != b.len() {
+            return a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+        }
+
+        use std::arch::aarch64::{vaddvq_f32, vfmaq_f32, vld1q_f32, vmovq_n_f32};
+
+        let chunks = a.len() / 4;
+        let mut acc = unsafe { vmovq_n_f32(0.0) };
+
+        for i in 0..chunks {
+            unsafe {
+                let vec_a = vld1q_f32(a[i * 4..].as_ptr());
+                let vec_b = vld1q_f32(b[i * 4..].as_ptr());
+                acc = vfmaq_f32(acc, vec_a, vec_b);
+            }
+        }
+
+        let mut result = unsafe { vaddvq_f32(acc) };
+        result += a[(chunks * 4)..].iter().zip(b[(chunks * 4)..].iter()).map(|(x, y)| x * y).sum::<f32>();
+        result
+    }
+
+    /// Fallback for architectures without a dedicated SIMD path above
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
     pub fn dot_product_simd(a: &[f32], b: &[f32]) -> f32 {
         a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
     }
+
+    /// SIMD element-wise multiplication
+    #[cfg(target_arch = "x86_64")]
+    pub fn mul_vectors_simd(a: &[f32], b: &[f32]) -> Vec<f32> {
+        if !is_x86_feature_detected!("avx") || a.len() != b.len() {
+            return a.iter().zip(b.iter()).map(|(x, y)| x * y).collect();
+        }
+
+        let mut result = Vec::with_capacity(a.len());
+        let chunks_a = a.chunks_exact(8);
+        let chunks_b = b.chunks_exact(8);
+        let remainder_a = chunks_a.remainder();
+        let remainder_b = chunks_b.remainder();
+
+        for (chunk_a, chunk_b) in chunks_a.zip(chunks_b) {
+            let vec_a = f32x8::from_array(*chunk_a.try_into().unwrap());
+            let vec_b = f32x8::from_array(*chunk_b.try_into().unwrap());
+            let product = vec_a * vec_b;
+            result.extend_from_slice(&product.to_array());
+        }
+
+        result.extend(remainder_a.iter().zip(remainder_b.iter()).map(|(x, y)| x * y));
+        result
+    }
+
+    /// NEON-backed element-wise multiplication for aarch64
+    #[cfg(target_arch = "aarch64")]
+    pub fn mul_vectors_simd(a: &[f32], b: &[f32]) -> Vec<f32> {
+        if !std::arch::is_aarch64_feature_detected!("neon") || a.len() != b.len() {
+            return a.iter().zip(b.iter()).map(|(x, y)| x * y).collect();
+        }
+
+        use std::arch::aarch64::{vld1q_f32, vmulq_f32, vst1q_f32};
+
+        let mut result = vec![0f32; a.len()];
+        let chunks = a.len() / 4;
+
+        for i in 0..chunks {
+            unsafe {
+                let vec_a = vld1q_f32(a[i * 4..].as_ptr());
+                let vec_b = vld1q_f32(b[i * 4..].as_ptr());
+                let product = vmulq_f32(vec_a, vec_b);
+                vst1q_f32(result[i * 4..].as_mut_ptr(), product);
+            }
+        }
+
+        for i in (chunks * 4)..a.len() {
+            result[i] = a[i] * b[i];
+        }
+
+        result
+    }
+
+    /// Fallback for architectures without a dedicated SIMD path above
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub fn mul_vectors_simd(a: &[f32], b: &[f32]) -> Vec<f32> {
+        a.iter().zip(b.iter()).map(|(x, y)| x * y).collect()
+    }
+
+    /// SIMD ReLU: max with zero lanes
+    #[cfg(target_arch = "x86_64")]
+    pub fn relu_simd(a: &[f32]) -> Vec<f32> {
+        if !is_x86_feature_detected!("avx") {
+            return a.iter().map(|&x| x.max(0.0)).collect();
+        }
+
+        let mut result = Vec::with_capacity(a.len());
+        let chunks = a.chunks_exact(8);
+        let remainder = chunks.remainder();
+        let zero = f32x8::ZERO;
+
+        for chunk in chunks {
+            let vec = f32x8::from_array(*chunk.try_into().unwrap());
+            let relu = vec.max(zero);
+            result.extend_from_slice(&relu.to_array());
+        }
+
+        result.extend(remainder.iter().map(|&x| x.max(0.0)));
+        result
+    }
+
+    /// NEON-backed ReLU for aarch64
+    #[cfg(target_arch = "aarch64")]
+    pub fn relu_simd(a: &[f32]) -> Vec<f32> {
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            return a.iter().map(|&x| x.max(0.0)).collect();
+        }
+
+        use std::arch::aarch64::{vld1q_f32, vmaxq_f32, vmovq_n_f32, vst1q_f32};
+
+        let mut result = vec![0f32; a.len()];
+        let chunks = a.len() / 4;
+        let zero = unsafe { vmovq_n_f32(0.0) };
+
+        for i in 0..chunks {
+            unsafe {
+                let vec = vld1q_f32(a[i * 4..].as_ptr());
+                let relu = vmaxq_f32(vec, zero);
+                vst1q_f32(result[i * 4..].as_mut_ptr(), relu);
+            }
+        }
+
+        for i in (chunks * 4)..a.len() {
+            result[i] = a[i].max(0.0);
+        }
+
+        result
+    }
+
+    /// Fallback for architectures without a dedicated SIMD path above
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub fn relu_simd(a: &[f32]) -> Vec<f32> {
+        a.iter().map(|&x| x.max(0.0)).collect()
+    }
+
+    /// Sum of `exp(x_i - max)` over a row, the reduction at the heart of a
+    /// stable softmax; the function subtracts `max` itself, so passing the
+    /// row's maximum keeps every exponentiated value non-positive.
+    #[cfg(target_arch = "x86_64")]
+    pub fn sum_exp_simd(a: &[f32], max: f32) -> f32 {
+        // `exp` has no portable SIMD intrinsic in the `wide` crate, so the
+        // shift-and-sum still runs per element on x86_64.
+        a.iter().map(|&x| (x - max).exp()).sum()
+    }
+
+    /// NEON path for `sum_exp_simd`
+    #[cfg(target_arch = "aarch64")]
+    pub fn sum_exp_simd(a: &[f32], max: f32) -> f32 {
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            return a.iter().map(|&x| (x - max).exp()).sum();
+        }
+
+        use std::arch::aarch64::{vdupq_n_f32, vgetq_lane_f32, vld1q_f32, vsubq_f32};
+
+        let chunks = a.len() / 4;
+        let max_vec = unsafe { vdupq_n_f32(max) };
+        let mut sum = 0f32;
+
+        for i in 0..chunks {
+            unsafe {
+                let vec = vld1q_f32(a[i * 4..].as_ptr());
+                let shifted = vsubq_f32(vec, max_vec);
+                sum += vgetq_lane_f32::<0>(shifted).exp()
+                    + vgetq_lane_f32::<1>(shifted).exp()
+                    + vgetq_lane_f32::<2>(shifted).exp()
+                    + vgetq_lane_f32::<3>(shifted).exp();
+            }
+        }
+
+        sum + a[(chunks * 4)..].iter().map(|&x| (x - max).exp()).sum::<f32>()
+    }
+
+    /// Fallback for architectures without a dedicated SIMD path above
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub fn sum_exp_simd(a: &[f32], max: f32) -> f32 {
+        a.iter().map(|&x| (x - max).exp()).sum()
+    }
+}
+
+/// Loss functions for lightweight training loops on top of `TensorWrapper`.
+pub mod losses {
+    use super::*;
+
+    /// `x - m - ln(sum exp(x - m))`, `m` the per-`dim` max, for numerically
+    /// stable log-probabilities.
+    fn log_softmax(tensor: &Tensor, dim: usize) -> candle_core::Result<Tensor> {
+        let max = tensor.max_keepdim(dim)?;
+        let shifted = tensor.broadcast_sub(&max)?;
+        let log_sum_exp = shifted.exp()?.sum_keepdim(dim)?.log()?;
+        shifted.broadcast_sub(&log_sum_exp)
+    }
+
+    /// `-(target_probs * log_softmax(logits)).mean()`.
+    #[napi]
+    pub fn cross_entropy_with_logits(
+        logits: &TensorWrapper,
+        target_probs: &TensorWrapper,
+        dim: i32,
+    ) -> napi::Result<f64> {
+        let dim = if dim < 0 {
+            (logits.tensor.dims().len() as i32 + dim) as usize
+        } else {
+            dim as usize
+        };
+
+        let log_probs = log_softmax(&logits.tensor, dim)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let weighted = (&target_probs.tensor * &log_probs)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let mean = weighted.mean_all()
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?
+            .to_scalar::<f32>()
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        Ok(-(mean as f64))
+    }
+
+    /// Quadratic for `|pred - target| <= delta`, linear beyond.
+    #[napi]
+    pub fn huber_loss(pred: &TensorWrapper, target: &TensorWrapper, delta: f64) -> napi::Result<f64> {
+        let pred_data = pred.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+        let target_data = target.tensor.flatten_all()
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?;
+
+        if pred_data.len() != target_data.len() {
+            return Err(NativeError::invalid_input(
+                "huber_loss: pred and target must have the same element count"
+            ).into());
+        }
+
+        let sum: f64 = pred_data.iter().zip(target_data.iter())
+            .map(|(&p, &t)| {
+                let diff = (p - t) as f64;
+                let abs_diff = diff.abs();
+                if abs_diff <= delta {
+                    0.5 * diff * diff
+                } else {
+                    delta * (abs_diff - 0.5 * delta)
+                }
+            })
+            .sum();
+
+        Ok(sum / pred_data.len() as f64)
+    }
+
+    /// `huber_loss(pred, target, delta) / delta`
+    #[napi]
+    pub fn smooth_l1_loss(pred: &TensorWrapper, target: &TensorWrapper, delta: f64) -> napi::Result<f64> {
+        Ok(huber_loss(pred, target, delta)? / delta)
+    }
 }
 
 /// Benchmark tensor operations
@@ -396,6 +1129,14 @@ pub async fn benchmark_tensor_ops(
                 let reshaped_b = TensorOps::reshape(&tensor_b, vec![sqrt_size, sqrt_size])?;
                 TensorOps::matmul(&reshaped_a, &reshaped_b)?
             }
+            "matmul_fast" => {
+                // Reshape for matrix multiplication, same as "matmul" but via
+                // the gemm-backed kernel so the two are comparable.
+                let sqrt_size = (size as f64).sqrt() as u32;
+                let reshaped_a = TensorOps::reshape(&tensor_a, vec![sqrt_size, sqrt_size])?;
+                let reshaped_b = TensorOps::reshape(&tensor_b, vec![sqrt_size, sqrt_size])?;
+                TensorOps::matmul_fast(&reshaped_a, &reshaped_b, None)?
+            }
             "softmax" => TensorOps::softmax(&tensor_a, -1)?,
             _ => return Err(NativeError::invalid_input(format!("Unknown operation: {}", operation)).into()),
         };
@@ -436,6 +1177,111 @@ mod tests {
         assert_eq!(result_data, vec![6.0, 8.0, 10.0, 12.0]);
     }
     
+    #[test]
+    fn test_quiet_softmax_sums_below_one() {
+        let data = vec![1.0, 2.0, 3.0, 4.0];
+        let tensor = TensorOps::from_data(data, vec![4]).unwrap();
+
+        let result = TensorOps::quiet_softmax(&tensor, -1).unwrap();
+        let result_data = result.to_vec().unwrap();
+
+        let sum: f32 = result_data.iter().sum();
+        assert!(sum < 1.0);
+        assert!(sum > 0.0);
+    }
+
+    #[test]
+    fn test_flash_attention_matches_dense_attention() {
+        let query = TensorOps::from_data(vec![1.0, 0.0, 0.0, 1.0], vec![2, 2]).unwrap();
+        let key = TensorOps::from_data(vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0], vec![3, 2]).unwrap();
+        let value = TensorOps::from_data(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![3, 2]).unwrap();
+
+        let dense = TensorOps::attention(&query, &key, &value, None, None, None).unwrap();
+        let flash = TensorOps::flash_attention(&query, &key, &value, None, None, Some(1)).unwrap();
+
+        let dense_data = dense.to_vec().unwrap();
+        let flash_data = flash.to_vec().unwrap();
+
+        for (a, b) in dense_data.iter().zip(flash_data.iter()) {
+            assert!((a - b).abs() < 1e-5, "dense={} flash={}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_quantize_dequantize_round_trip() {
+        let data = vec![0.0, 0.5, 1.0, -0.5];
+        let tensor = TensorOps::from_data(data.clone(), vec![4]).unwrap();
+
+        let quantized = TensorOps::quantize(&tensor, 0.01, 128).unwrap();
+        let dequantized = TensorOps::dequantize(&quantized).unwrap();
+        let result = dequantized.to_vec().unwrap();
+
+        for (original, recovered) in data.iter().zip(result.iter()) {
+            assert!((original - recovered).abs() < 0.01, "original={} recovered={}", original, recovered);
+        }
+    }
+
+    #[test]
+    fn test_quantized_matmul_matches_dense() {
+        let a = TensorOps::from_data(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        let b = TensorOps::from_data(vec![5.0, 6.0, 7.0, 8.0], vec![2, 2]).unwrap();
+
+        let dense = TensorOps::matmul(&a, &b).unwrap().to_vec().unwrap();
+
+        let qa = TensorOps::quantize(&a, 0.05, 0).unwrap();
+        let qb = TensorOps::quantize(&b, 0.05, 0).unwrap();
+        let q_result = TensorOps::quantized_matmul(&qa, &qb, 0.5, 128).unwrap();
+        let dequantized = TensorOps::dequantize(&q_result).unwrap().to_vec().unwrap();
+
+        for (d, q) in dense.iter().zip(dequantized.iter()) {
+            assert!((d - q).abs() < 1.0, "dense={} quantized={}", d, q);
+        }
+    }
+
+    #[test]
+    fn test_huber_loss_quadratic_and_linear_regions() {
+        let pred = TensorOps::from_data(vec![0.0, 0.0], vec![2]).unwrap();
+        let target = TensorOps::from_data(vec![0.5, 2.0], vec![2]).unwrap();
+
+        let loss = losses::huber_loss(&pred, &target, 1.0).unwrap();
+        // row 0: |d|=0.5 <= delta -> 0.5*0.5^2 = 0.125
+        // row 1: |d|=2.0 > delta -> 1.0*(2.0 - 0.5) = 1.5
+        assert!((loss - (0.125 + 1.5) / 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_cross_entropy_with_logits_matches_one_hot_target() {
+        let logits = TensorOps::from_data(vec![2.0, 0.0, 0.0], vec![1, 3]).unwrap();
+        let target = TensorOps::from_data(vec![1.0, 0.0, 0.0], vec![1, 3]).unwrap();
+
+        let loss = losses::cross_entropy_with_logits(&logits, &target, -1).unwrap();
+        assert!(loss > 0.0);
+        assert!(loss < 0.2);
+    }
+
+    #[test]
+    fn test_matmul_fast_matches_default_matmul() {
+        let a = TensorOps::from_data(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        let b = TensorOps::from_data(vec![5.0, 6.0, 7.0, 8.0], vec![2, 2]).unwrap();
+
+        let expected = TensorOps::matmul(&a, &b).unwrap().to_vec().unwrap();
+        let actual = TensorOps::matmul_fast(&a, &b, None).unwrap().to_vec().unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_to_vec_typed_and_from_data_typed_round_trip() {
+        let tensor = TensorOps::from_data(vec![1.0, 2.0, 3.0, 4.0], vec![4]).unwrap();
+        let typed = tensor.to_vec_typed().unwrap();
+
+        assert_eq!(typed.dtype, "f32");
+        assert_eq!(typed.f32_values, Some(vec![1.0, 2.0, 3.0, 4.0]));
+
+        let round_tripped = TensorOps::from_data_typed(typed, vec![4]).unwrap();
+        assert_eq!(round_tripped.to_vec().unwrap(), vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
     #[test]
     fn test_simd_operations() {
         let a = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
@@ -447,4 +1293,17 @@ mod tests {
         let dot_result = simd::dot_product_simd(&a, &b);
         assert_eq!(dot_result, 120.0);
     }
+
+    #[test]
+    fn test_simd_mul_relu_sum_exp() {
+        let a = vec![1.0, -2.0, 3.0, -4.0, 5.0, -6.0, 7.0, -8.0];
+        let b = vec![2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0];
+
+        assert_eq!(simd::mul_vectors_simd(&a, &b), vec![2.0, -4.0, 6.0, -8.0, 10.0, -12.0, 14.0, -16.0]);
+        assert_eq!(simd::relu_simd(&a), vec![1.0, 0.0, 3.0, 0.0, 5.0, 0.0, 7.0, 0.0]);
+
+        let sum = simd::sum_exp_simd(&a, 7.0);
+        let expected: f32 = a.iter().map(|&x| (x - 7.0).exp()).sum();
+        assert!((sum - expected).abs() < 1e-5);
+    }
 }
\ No newline at end of file
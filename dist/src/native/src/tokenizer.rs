@@ -4,10 +4,13 @@ use crate::{error::{NativeError, Result}, perf::Timer};
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 use tokenizers::{Tokenizer, AddedToken, Encoding};
-use tokio::sync::RwLock;
-use dashmap::DashMap;
+use tokio::sync::{Mutex, RwLock};
+use lru::LruCache;
+use minijinja::{context, Environment};
+use serde_json::Value as JsonValue;
 
 /// Configuration for tokenizer
 #[napi(object)]
@@ -29,6 +32,16 @@ pub struct TokenizerConfig {
     pub add_special_tokens: Option<bool>,
     /// Whether to enable caching
     pub enable_cache: Option<bool>,
+    /// Maximum number of entries retained in the tokenization cache before the
+    /// least-recently-used entry is evicted
+    pub max_cache_entries: Option<u32>,
+    /// Approximate maximum cache size in bytes; once exceeded, entries are
+    /// evicted least-recently-used first until the cache fits again
+    pub max_cache_bytes: Option<u64>,
+    /// Collapse runs of whitespace in `encode` input and `decode` output
+    /// before/after tokenization, using a scalar per-character pass
+    /// (`simd::normalize_whitespace` is not vectorized)
+    pub normalize_whitespace: Option<bool>,
 }
 
 impl Default for TokenizerConfig {
@@ -42,6 +55,9 @@ impl Default for TokenizerConfig {
             eos_token: Some("<eos>".to_string()),
             add_special_tokens: Some(true),
             enable_cache: Some(true),
+            max_cache_entries: Some(1000),
+            max_cache_bytes: None,
+            normalize_whitespace: Some(false),
         }
     }
 }
@@ -62,13 +78,31 @@ pub struct TokenizationResult {
     pub offsets: Vec<Vec<u32>>,
 }
 
+/// A single message in a chat conversation, for `apply_chat_template`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    /// e.g. "system", "user", "assistant"
+    pub role: String,
+    pub content: String,
+}
+
+/// A chat template rendered into a prompt and its tokenization
+#[napi(object)]
+pub struct ChatTemplateResult {
+    /// The rendered prompt text
+    pub text: String,
+    pub tokenization: TokenizationResult,
+}
+
 /// Fast tokenizer with caching and SIMD optimizations
 #[napi]
 pub struct FastTokenizer {
     tokenizer: Arc<RwLock<Tokenizer>>,
     config: TokenizerConfig,
-    cache: Arc<DashMap<String, TokenizationResult>>,
+    cache: Arc<Mutex<LruCache<String, TokenizationResult>>>,
     stats: Arc<RwLock<TokenizerStats>>,
+    chat_template: Option<String>,
 }
 
 #[derive(Debug, Default)]
@@ -77,6 +111,7 @@ struct TokenizerStats {
     cache_misses: u64,
     total_tokens: u64,
     total_time_ms: f64,
+    evictions: u64,
 }
 
 #[napi]
@@ -94,12 +129,17 @@ impl FastTokenizer {
         let _timer = Timer::new("tokenizer_init");
         
         let tokenizer = Self::load_tokenizer(&config.model).await?;
-        
+        let chat_template = Self::load_chat_template(&config.model);
+
+        let cache_capacity = NonZeroUsize::new(config.max_cache_entries.unwrap_or(1000) as usize)
+            .unwrap_or_else(|| NonZeroUsize::new(1).unwrap());
+
         Ok(FastTokenizer {
             tokenizer: Arc::new(RwLock::new(tokenizer)),
             config,
-            cache: Arc::new(DashMap::new()),
+            cache: Arc::new(Mutex::new(LruCache::new(cache_capacity))),
             stats: Arc::new(RwLock::new(TokenizerStats::default())),
+            chat_template,
         })
     }
 
@@ -125,26 +165,55 @@ impl FastTokenizer {
         Ok(tokenizer)
     }
 
+    /// Load the `chat_template` field out of a sibling `tokenizer_config.json`
+    /// for a local model path, if one exists. Returns `None` for a bare
+    /// HuggingFace Hub repo id (fetched via `Tokenizer::from_pretrained`
+    /// above, which only pulls `tokenizer.json`) or when no template is set.
+    fn load_chat_template(model: &str) -> Option<String> {
+        let path = std::path::Path::new(model);
+        let config_path = if path.is_file() {
+            path.parent()?.join("tokenizer_config.json")
+        } else {
+            path.join("tokenizer_config.json")
+        };
+
+        let contents = std::fs::read_to_string(config_path).ok()?;
+        let config: JsonValue = serde_json::from_str(&contents).ok()?;
+        config
+            .get("chat_template")?
+            .as_str()
+            .map(|template| template.to_string())
+    }
+
     /// Encode text to tokens
     #[napi]
     pub async fn encode(&self, text: String) -> napi::Result<TokenizationResult> {
         let _timer = Timer::new("tokenize_encode");
-        
+
+        let text = if self.config.normalize_whitespace.unwrap_or(false) {
+            simd::normalize_whitespace(&text)
+        } else {
+            text
+        };
+
         // Check cache first if enabled
         if self.config.enable_cache.unwrap_or(true) {
-            if let Some(cached) = self.cache.get(&text) {
+            let mut cache = self.cache.lock().await;
+            if let Some(cached) = cache.get(&text) {
+                let cached = cached.clone();
+                drop(cache);
                 let mut stats = self.stats.write().await;
                 stats.cache_hits += 1;
-                return Ok(cached.clone());
+                return Ok(cached);
             }
         }
 
         // Perform tokenization
         let result = self.encode_impl(&text).await?;
-        
+
         // Update cache
         if self.config.enable_cache.unwrap_or(true) {
-            self.cache.insert(text, result.clone());
+            self.insert_cache_entry(text, result.clone()).await;
         }
 
         // Update stats
@@ -155,6 +224,55 @@ impl FastTokenizer {
         Ok(result)
     }
 
+    /// Insert a tokenization result into the LRU cache, evicting the
+    /// least-recently-used entry when `max_cache_entries` or
+    /// `max_cache_bytes` is exceeded.
+    async fn insert_cache_entry(&self, text: String, result: TokenizationResult) {
+        let mut cache = self.cache.lock().await;
+        let mut evictions = 0u64;
+
+        // `push` also returns the old entry when `text` is already cached
+        // (e.g. a duplicate within one batch); that is a replacement, not an eviction.
+        let replacing = cache.contains(&text);
+        if cache.push(text, result).is_some() && !replacing {
+            evictions += 1;
+        }
+
+        if let Some(max_bytes) = self.config.max_cache_bytes {
+            while Self::cache_bytes_locked(&cache) > max_bytes {
+                if cache.pop_lru().is_none() {
+                    break;
+                }
+                evictions += 1;
+            }
+        }
+
+        drop(cache);
+
+        if evictions > 0 {
+            let mut stats = self.stats.write().await;
+            stats.evictions += evictions;
+        }
+    }
+
+    /// Approximate size in bytes of a single cached tokenization result
+    fn result_size_bytes(result: &TokenizationResult) -> u64 {
+        let ids = (result.ids.len() * 4) as u64;
+        let tokens: u64 = result.tokens.iter().map(|t| t.len() as u64).sum();
+        let attention_mask = (result.attention_mask.len() * 4) as u64;
+        let special_tokens_mask = (result.special_tokens_mask.len() * 4) as u64;
+        let offsets = (result.offsets.len() * 8) as u64;
+        ids + tokens + attention_mask + special_tokens_mask + offsets
+    }
+
+    /// Approximate total size in bytes of every entry currently in `cache`
+    fn cache_bytes_locked(cache: &LruCache<String, TokenizationResult>) -> u64 {
+        cache
+            .iter()
+            .map(|(key, result)| key.len() as u64 + Self::result_size_bytes(result))
+            .sum()
+    }
+
     /// Internal encoding implementation
     async fn encode_impl(&self, text: &str) -> napi::Result<TokenizationResult> {
         let tokenizer = self.tokenizer.read().await;
@@ -173,24 +291,22 @@ impl FastTokenizer {
         .await
         .map_err(|e| NativeError::internal(format!("Encoding task failed: {}", e)))??;
 
-        // Convert to result format
-        let ids = encoding.get_ids().to_vec();
-        let tokens = encoding.get_tokens().to_vec();
-        let attention_mask = encoding.get_attention_mask().to_vec();
-        let special_tokens_mask = encoding.get_special_tokens_mask().to_vec();
-        let offsets = encoding
-            .get_offsets()
-            .iter()
-            .map(|(start, end)| vec![*start as u32, *end as u32])
-            .collect();
+        Ok(Self::encoding_to_result(&encoding))
+    }
 
-        Ok(TokenizationResult {
-            ids,
-            tokens,
-            attention_mask,
-            special_tokens_mask,
-            offsets,
-        })
+    /// Convert a `tokenizers::Encoding` into our NAPI-facing result type
+    fn encoding_to_result(encoding: &Encoding) -> TokenizationResult {
+        TokenizationResult {
+            ids: encoding.get_ids().to_vec(),
+            tokens: encoding.get_tokens().to_vec(),
+            attention_mask: encoding.get_attention_mask().to_vec(),
+            special_tokens_mask: encoding.get_special_tokens_mask().to_vec(),
+            offsets: encoding
+                .get_offsets()
+                .iter()
+                .map(|(start, end)| vec![*start as u32, *end as u32])
+                .collect(),
+        }
     }
 
     /// Decode token IDs to text
@@ -213,67 +329,235 @@ impl FastTokenizer {
         .await
         .map_err(|e| NativeError::internal(format!("Decoding task failed: {}", e)))??;
 
-        Ok(text)
+        Ok(if self.config.normalize_whitespace.unwrap_or(false) {
+            simd::normalize_whitespace(&text)
+        } else {
+            text
+        })
     }
 
     /// Batch encode multiple texts
     #[napi]
     pub async fn encode_batch(&self, texts: Vec<String>) -> napi::Result<Vec<TokenizationResult>> {
         let _timer = Timer::new("tokenize_encode_batch");
-        
-        // Use rayon for parallel processing
-        let results = tokio::task::spawn_blocking({
-            let texts = texts.clone();
-            let tokenizer = self.tokenizer.clone();
-            let config = self.config.clone();
-            
-            move || -> Result<Vec<TokenizationResult>> {
-                use rayon::prelude::*;
-                
-                texts
-                    .par_iter()
-                    .map(|text| {
-                        let rt = tokio::runtime::Handle::current();
-                        rt.block_on(async {
-                            let tokenizer = tokenizer.read().await;
-                            let add_special_tokens = config.add_special_tokens.unwrap_or(true);
-                            
-                            let encoding = tokenizer
-                                .encode(text, add_special_tokens)
-                                .map_err(|e| NativeError::tokenizer(e.to_string()))?;
-
-                            let ids = encoding.get_ids().to_vec();
-                            let tokens = encoding.get_tokens().to_vec();
-                            let attention_mask = encoding.get_attention_mask().to_vec();
-                            let special_tokens_mask = encoding.get_special_tokens_mask().to_vec();
-                            let offsets = encoding
-                                .get_offsets()
-                                .iter()
-                                .map(|(start, end)| vec![*start as u32, *end as u32])
-                                .collect();
-
-                            Ok(TokenizationResult {
-                                ids,
-                                tokens,
-                                attention_mask,
-                                special_tokens_mask,
-                                offsets,
-                            })
-                        })
-                    })
-                    .collect()
+
+        let use_cache = self.config.enable_cache.unwrap_or(true);
+        let mut results: Vec<Option<TokenizationResult>> = vec![None; texts.len()];
+        let mut pending: Vec<(usize, String)> = Vec::new();
+        let mut cache_hits = 0u64;
+
+        if use_cache {
+            let mut cache = self.cache.lock().await;
+            for (index, text) in texts.into_iter().enumerate() {
+                if let Some(cached) = cache.get(&text) {
+                    results[index] = Some(cached.clone());
+                    cache_hits += 1;
+                } else {
+                    pending.push((index, text));
+                }
             }
-        })
-        .await
-        .map_err(|e| NativeError::internal(format!("Batch encoding task failed: {}", e)))??;
+        } else {
+            pending = texts.into_iter().enumerate().collect();
+        }
+
+        if !pending.is_empty() {
+            let tokenizer = self.tokenizer.read().await;
+            let add_special_tokens = self.config.add_special_tokens.unwrap_or(true);
+            let pending_texts: Vec<String> = pending.iter().map(|(_, text)| text.clone()).collect();
+
+            // The `tokenizers` crate already parallelizes batch encoding
+            // internally, so a single blocking call beats re-entering the
+            // async runtime once per item.
+            let encodings = tokio::task::spawn_blocking({
+                let tokenizer_clone = tokenizer.clone();
+                move || -> Result<Vec<Encoding>> {
+                    tokenizer_clone
+                        .encode_batch(pending_texts, add_special_tokens)
+                        .map_err(|e| NativeError::tokenizer(e.to_string()))
+                }
+            })
+            .await
+            .map_err(|e| NativeError::internal(format!("Batch encoding task failed: {}", e)))??;
+
+            for ((index, text), encoding) in pending.into_iter().zip(encodings.into_iter()) {
+                let result = Self::encoding_to_result(&encoding);
+                if use_cache {
+                    self.insert_cache_entry(text, result.clone()).await;
+                }
+                results[index] = Some(result);
+            }
+        }
+
+        let results: Vec<TokenizationResult> = results.into_iter().map(|r| r.unwrap()).collect();
 
         // Update stats
         let mut stats = self.stats.write().await;
+        stats.cache_hits += cache_hits;
+        stats.cache_misses += results.len() as u64 - cache_hits;
         stats.total_tokens += results.iter().map(|r| r.ids.len() as u64).sum::<u64>();
 
         Ok(results)
     }
 
+    /// Split `text` into overlapping windows of `window` tokens advancing by
+    /// `stride` (so `window - stride` tokens overlap between adjacent
+    /// windows), each returned as its own `TokenizationResult`. Lets callers
+    /// feed documents longer than a model's context window through
+    /// retrieval/embedding pipelines without truncating data away.
+    ///
+    /// BOS/EOS tokens are re-applied to every window when
+    /// `add_special_tokens` is set, and `offsets` stay relative to the
+    /// original `text` so a window can be mapped back to source characters.
+    #[napi]
+    pub async fn encode_windows(
+        &self,
+        text: String,
+        window: u32,
+        stride: u32,
+    ) -> napi::Result<Vec<TokenizationResult>> {
+        let _timer = Timer::new("tokenize_encode_windows");
+
+        if stride == 0 {
+            return Err(NativeError::invalid_input(
+                "stride must be greater than zero to make progress through the text",
+            )
+            .into());
+        }
+
+        let tokenizer = self.tokenizer.read().await;
+        let add_special_tokens = self.config.add_special_tokens.unwrap_or(true);
+
+        // Tokenize the content once without special tokens so windows can be
+        // sliced from a clean stream, with BOS/EOS re-applied per window below.
+        let encoding = tokio::task::spawn_blocking({
+            let tokenizer_clone = tokenizer.clone();
+            move || -> Result<Encoding> {
+                tokenizer_clone
+                    .encode(text, false)
+                    .map_err(|e| NativeError::tokenizer(e.to_string()))
+            }
+        })
+        .await
+        .map_err(|e| NativeError::internal(format!("Window encoding task failed: {}", e)))??;
+
+        let ids = encoding.get_ids();
+        let tokens = encoding.get_tokens();
+        let offsets = encoding.get_offsets();
+        let total = ids.len();
+
+        let bos = add_special_tokens
+            .then(|| Self::special_token(&tokenizer, self.config.bos_token.as_deref()))
+            .flatten();
+        let eos = add_special_tokens
+            .then(|| Self::special_token(&tokenizer, self.config.eos_token.as_deref()))
+            .flatten();
+
+        let window_len = window as usize;
+        let stride_len = stride as usize;
+
+        let mut results = Vec::new();
+        let mut start = 0usize;
+
+        loop {
+            let end = (start + window_len).min(total);
+
+            let mut window_ids: Vec<u32> = ids[start..end].to_vec();
+            let mut window_tokens: Vec<String> = tokens[start..end].to_vec();
+            let mut window_offsets: Vec<Vec<u32>> = offsets[start..end]
+                .iter()
+                .map(|(s, e)| vec![*s as u32, *e as u32])
+                .collect();
+            let mut special_tokens_mask = vec![0u32; window_ids.len()];
+
+            if let Some((id, token)) = &bos {
+                window_ids.insert(0, *id);
+                window_tokens.insert(0, token.clone());
+                window_offsets.insert(0, vec![0, 0]);
+                special_tokens_mask.insert(0, 1);
+            }
+            if let Some((id, token)) = &eos {
+                window_ids.push(*id);
+                window_tokens.push(token.clone());
+                window_offsets.push(vec![0, 0]);
+                special_tokens_mask.push(1);
+            }
+
+            let attention_mask = vec![1u32; window_ids.len()];
+
+            results.push(TokenizationResult {
+                ids: window_ids,
+                tokens: window_tokens,
+                attention_mask,
+                special_tokens_mask,
+                offsets: window_offsets,
+            });
+
+            if end == total {
+                break;
+            }
+            start += stride_len;
+        }
+
+        Ok(results)
+    }
+
+    /// Resolve a configured special token's ID and string form against the
+    /// vocabulary, if the tokenizer knows about it
+    fn special_token(tokenizer: &Tokenizer, token: Option<&str>) -> Option<(u32, String)> {
+        let token = token?;
+        let id = tokenizer.token_to_id(token)?;
+        Some((id, token.to_string()))
+    }
+
+    /// Render `messages` into a single prompt using the model's chat
+    /// template (loaded from the `chat_template` field of a sibling
+    /// `tokenizer_config.json` at construction time) and encode it.
+    #[napi]
+    pub async fn apply_chat_template(
+        &self,
+        messages: Vec<ChatMessage>,
+        add_generation_prompt: Option<bool>,
+    ) -> napi::Result<ChatTemplateResult> {
+        let template = self.chat_template.as_deref().ok_or_else(|| {
+            NativeError::invalid_input(
+                "No chat_template found in tokenizer_config.json for this model",
+            )
+        })?;
+
+        let mut env = Environment::new();
+        env.add_template("chat", template)
+            .map_err(|e| NativeError::tokenizer(format!("Invalid chat template: {}", e)))?;
+
+        let text = env
+            .get_template("chat")
+            .and_then(|tmpl| {
+                tmpl.render(context! {
+                    messages => messages,
+                    bos_token => self.config.bos_token.clone().unwrap_or_default(),
+                    eos_token => self.config.eos_token.clone().unwrap_or_default(),
+                    add_generation_prompt => add_generation_prompt.unwrap_or(false),
+                })
+            })
+            .map_err(|e| NativeError::tokenizer(format!("Failed to render chat template: {}", e)))?;
+
+        let tokenization = self.encode_impl(&text).await?;
+
+        Ok(ChatTemplateResult { text, tokenization })
+    }
+
+    /// Register custom control tokens (e.g. `<|im_start|>`) in the vocabulary,
+    /// returning the number of tokens actually added.
+    #[napi]
+    pub async fn add_special_tokens_to_vocab(&self, tokens: Vec<String>) -> napi::Result<u32> {
+        let added_tokens: Vec<AddedToken> = tokens
+            .into_iter()
+            .map(|token| AddedToken::from(token, true))
+            .collect();
+
+        let mut tokenizer = self.tokenizer.write().await;
+        Ok(tokenizer.add_special_tokens(&added_tokens) as u32)
+    }
+
     /// Get vocabulary size
     #[napi]
     pub async fn vocab_size(&self) -> napi::Result<u32> {
@@ -285,7 +569,8 @@ impl FastTokenizer {
     #[napi]
     pub async fn get_stats(&self) -> napi::Result<TokenizerStatsResult> {
         let stats = self.stats.read().await;
-        
+        let cache = self.cache.lock().await;
+
         let cache_hit_rate = if stats.cache_hits + stats.cache_misses > 0 {
             stats.cache_hits as f64 / (stats.cache_hits + stats.cache_misses) as f64
         } else {
@@ -297,14 +582,16 @@ impl FastTokenizer {
             cache_misses: stats.cache_misses,
             cache_hit_rate,
             total_tokens: stats.total_tokens,
-            cache_size: self.cache.len() as u64,
+            cache_size: cache.len() as u64,
+            evictions: stats.evictions,
+            cache_bytes: Self::cache_bytes_locked(&cache),
         })
     }
 
     /// Clear tokenizer cache
     #[napi]
-    pub fn clear_cache(&self) -> napi::Result<()> {
-        self.cache.clear();
+    pub async fn clear_cache(&self) -> napi::Result<()> {
+        self.cache.lock().await.clear();
         Ok(())
     }
 
@@ -338,6 +625,68 @@ impl FastTokenizer {
         result
     }
 
+    /// Count tokens in `text` without allocating the full `TokenizationResult`
+    #[napi]
+    pub async fn count_tokens(&self, text: String) -> napi::Result<u32> {
+        let _timer = Timer::new("tokenize_count");
+
+        let tokenizer = self.tokenizer.read().await;
+        let add_special_tokens = self.config.add_special_tokens.unwrap_or(true);
+
+        let count = tokio::task::spawn_blocking({
+            let tokenizer_clone = tokenizer.clone();
+            move || -> Result<usize> {
+                tokenizer_clone
+                    .encode(text, add_special_tokens)
+                    .map(|encoding| encoding.get_ids().len())
+                    .map_err(|e| NativeError::tokenizer(e.to_string()))
+            }
+        })
+        .await
+        .map_err(|e| NativeError::internal(format!("Token counting task failed: {}", e)))??;
+
+        Ok(count as u32)
+    }
+
+    /// Tokens left in `context_window` after accounting for `text`'s prompt tokens
+    #[napi]
+    pub async fn remaining_tokens(&self, text: String, context_window: u32) -> napi::Result<i64> {
+        let used = self.count_tokens(text).await?;
+        Ok(context_window as i64 - used as i64)
+    }
+
+    /// Guard a prompt against a model's context window before dispatching a request.
+    ///
+    /// Returns the tokens left over for the completion -
+    /// `max(0, context_window - prompt_tokens - reserved_completion)` - accounting
+    /// for `add_special_tokens` overhead. Fails with `NativeError::InvalidInput` if
+    /// the prompt alone already exceeds `context_window`, regardless of
+    /// `reserved_completion`.
+    #[napi]
+    pub async fn guard(
+        &self,
+        text: String,
+        context_window: u32,
+        reserved_completion: Option<u32>,
+    ) -> napi::Result<u32> {
+        let prompt_tokens = self.count_tokens(text).await?;
+
+        if prompt_tokens > context_window {
+            return Err(NativeError::invalid_input(format!(
+                "Prompt uses {} tokens, which already exceeds the context window of {}",
+                prompt_tokens, context_window
+            ))
+            .into());
+        }
+
+        let reserved = reserved_completion.unwrap_or(0);
+        let remaining = context_window
+            .saturating_sub(prompt_tokens)
+            .saturating_sub(reserved);
+
+        Ok(remaining)
+    }
+
     /// Pad tokens to specific length
     #[napi]
     pub async fn pad_tokens(&self, mut result: TokenizationResult, target_length: u32, pad_token_id: Option<u32>) -> napi::Result<TokenizationResult> {
@@ -381,39 +730,101 @@ pub struct TokenizerStatsResult {
     pub cache_hit_rate: f64,
     pub total_tokens: u64,
     pub cache_size: u64,
+    /// Entries evicted due to `max_cache_entries`/`max_cache_bytes`
+    pub evictions: u64,
+    /// Approximate total size in bytes of all cached entries
+    pub cache_bytes: u64,
 }
 
 /// SIMD-optimized text preprocessing
+///
+/// `wide::u8x16` is a portable vector type that lowers to SSE2 on x86_64 and
+/// NEON on aarch64, so the byte-scanning helpers (`count_chars_simd`,
+/// `count_whitespace`, `find_byte`) vectorize on both without an
+/// architecture-specific code path; `normalize_whitespace` is a scalar
+/// per-character pass. `is_x86_feature_detected!` only guards
+/// against the (rare) x86_64 target built without SSE2.
 pub mod simd {
     use wide::u8x16;
-    
-    /// Fast character counting using SIMD
-    #[cfg(target_arch = "x86_64")]
+
+    const LANES: usize = 16;
+
+    /// Count occurrences of `target` in `text`
     pub fn count_chars_simd(text: &[u8], target: u8) -> usize {
+        #[cfg(target_arch = "x86_64")]
         if !is_x86_feature_detected!("sse2") {
             return text.iter().filter(|&&c| c == target).count();
         }
-        
+
         let target_vec = u8x16::splat(target);
         let mut count = 0;
-        let chunks = text.chunks_exact(16);
+        let chunks = text.chunks_exact(LANES);
         let remainder = chunks.remainder();
-        
+
         for chunk in chunks {
             let chunk_vec = u8x16::from_array(*chunk.try_into().unwrap());
             let mask = chunk_vec.cmp_eq(target_vec);
             count += mask.move_mask().count_ones() as usize;
         }
-        
+
         // Handle remainder
         count += remainder.iter().filter(|&&c| c == target).count();
         count
     }
-    
-    /// Fallback for non-x86 architectures
-    #[cfg(not(target_arch = "x86_64"))]
-    pub fn count_chars_simd(text: &[u8], target: u8) -> usize {
-        text.iter().filter(|&&c| c == target).count()
+
+    /// Count whitespace bytes (space, tab, newline, carriage return) in `text`
+    pub fn count_whitespace(text: &[u8]) -> usize {
+        count_chars_simd(text, b' ')
+            + count_chars_simd(text, b'\t')
+            + count_chars_simd(text, b'\n')
+            + count_chars_simd(text, b'\r')
+    }
+
+    /// First index of `target` in `text`, for fast pre-splitting ahead of
+    /// tokenization. Returns `None` if `target` doesn't occur.
+    pub fn find_byte(text: &[u8], target: u8) -> Option<usize> {
+        let target_vec = u8x16::splat(target);
+        let chunks = text.chunks_exact(LANES);
+        let remainder_start = chunks.len() * LANES;
+        let remainder = chunks.remainder();
+
+        for (chunk_index, chunk) in chunks.enumerate() {
+            let chunk_vec = u8x16::from_array(*chunk.try_into().unwrap());
+            let mask = chunk_vec.cmp_eq(target_vec).move_mask();
+            if mask != 0 {
+                return Some(chunk_index * LANES + mask.trailing_zeros() as usize);
+            }
+        }
+
+        remainder
+            .iter()
+            .position(|&c| c == target)
+            .map(|i| remainder_start + i)
+    }
+
+    /// Collapse runs of whitespace into a single space and trim the ends,
+    /// so input can be cheaply cleaned before tokenization on any target.
+    pub fn normalize_whitespace(text: &str) -> String {
+        let mut normalized = String::with_capacity(text.len());
+        let mut last_was_space = true; // trims leading whitespace
+
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                if !last_was_space {
+                    normalized.push(' ');
+                }
+                last_was_space = true;
+            } else {
+                normalized.push(ch);
+                last_was_space = false;
+            }
+        }
+
+        if normalized.ends_with(' ') {
+            normalized.pop();
+        }
+
+        normalized
     }
 }
 
@@ -455,10 +866,163 @@ mod tests {
         }
     }
     
+    #[tokio::test]
+    async fn test_apply_chat_template_renders_and_encodes() {
+        let mut tokenizer = FastTokenizer::new_default().await.unwrap();
+        tokenizer.chat_template = Some(
+            "{% for m in messages %}{{ m.role }}: {{ m.content }}\n{% endfor %}\
+             {% if add_generation_prompt %}assistant:{% endif %}"
+                .to_string(),
+        );
+
+        let result = tokenizer
+            .apply_chat_template(
+                vec![ChatMessage {
+                    role: "user".to_string(),
+                    content: "Hi".to_string(),
+                }],
+                Some(true),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.text.contains("user: Hi"));
+        assert!(result.text.ends_with("assistant:"));
+        assert!(!result.tokenization.ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_chat_template_errors_without_template() {
+        let tokenizer = FastTokenizer::new_default().await.unwrap();
+        let err = tokenizer
+            .apply_chat_template(
+                vec![ChatMessage {
+                    role: "user".to_string(),
+                    content: "Hi".to_string(),
+                }],
+                None,
+            )
+            .await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_add_special_tokens_to_vocab() {
+        let tokenizer = FastTokenizer::new_default().await.unwrap();
+        let added = tokenizer
+            .add_special_tokens_to_vocab(vec!["<|im_start|>".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(added, 1);
+        assert!(tokenizer
+            .token_to_id("<|im_start|>".to_string())
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_count_tokens_matches_encode() {
+        let tokenizer = FastTokenizer::new_default().await.unwrap();
+        let text = "Hello, world!".to_string();
+
+        let count = tokenizer.count_tokens(text.clone()).await.unwrap();
+        let result = tokenizer.encode(text).await.unwrap();
+
+        assert_eq!(count as usize, result.ids.len());
+    }
+
+    #[tokio::test]
+    async fn test_guard_within_and_over_budget() {
+        let tokenizer = FastTokenizer::new_default().await.unwrap();
+        let text = "Hello, world!".to_string();
+        let prompt_tokens = tokenizer.count_tokens(text.clone()).await.unwrap();
+
+        // Comfortably within the window, no reserved completion.
+        let remaining = tokenizer.guard(text.clone(), prompt_tokens + 100, None).await.unwrap();
+        assert_eq!(remaining, 100);
+
+        // Reserving more than is left clamps to zero rather than going negative.
+        let remaining = tokenizer
+            .guard(text.clone(), prompt_tokens + 10, Some(50))
+            .await
+            .unwrap();
+        assert_eq!(remaining, 0);
+
+        // Prompt alone already exceeds the window.
+        let err = tokenizer.guard(text, prompt_tokens - 1, None).await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_encode_windows_overlap_and_rejects_zero_stride() {
+        let tokenizer = FastTokenizer::new_default().await.unwrap();
+        let text = "one two three four five six".to_string();
+
+        let windows = tokenizer.encode_windows(text.clone(), 4, 2).await.unwrap();
+        assert!(windows.len() > 1);
+        for window in &windows {
+            assert!(window.ids.len() <= 4 + 2); // 4 content tokens + optional BOS/EOS
+        }
+
+        let err = tokenizer.encode_windows(text, 4, 0).await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_encode_batch_populates_cache() {
+        let tokenizer = FastTokenizer::new_default().await.unwrap();
+        let texts = vec!["Hello, world!".to_string(), "How are you?".to_string()];
+
+        tokenizer.encode_batch(texts.clone()).await.unwrap();
+        let stats = tokenizer.get_stats().await.unwrap();
+        assert_eq!(stats.cache_misses, 2);
+
+        // Second batch should hit the cache populated by the first.
+        tokenizer.encode_batch(texts).await.unwrap();
+        let stats = tokenizer.get_stats().await.unwrap();
+        assert_eq!(stats.cache_hits, 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_lru_eviction() {
+        let mut config = TokenizerConfig::default();
+        config.max_cache_entries = Some(2);
+        let tokenizer = FastTokenizer::new(config).await.unwrap();
+
+        tokenizer.encode("one".to_string()).await.unwrap();
+        tokenizer.encode("two".to_string()).await.unwrap();
+        tokenizer.encode("three".to_string()).await.unwrap();
+
+        let stats = tokenizer.get_stats().await.unwrap();
+        assert_eq!(stats.cache_size, 2);
+        assert_eq!(stats.evictions, 1);
+        assert!(stats.cache_bytes > 0);
+    }
+
     #[test]
     fn test_simd_char_counting() {
         let text = b"Hello, world! Hello, Rust!";
         let count = simd::count_chars_simd(text, b'l');
         assert_eq!(count, 4);
     }
+
+    #[test]
+    fn test_simd_count_whitespace() {
+        let text = b"a b\tc\nd";
+        assert_eq!(simd::count_whitespace(text), 3);
+    }
+
+    #[test]
+    fn test_simd_find_byte() {
+        let text = b"Hello, world! Hello, Rust! Hello again, this text is longer than sixteen bytes.";
+        assert_eq!(simd::find_byte(text, b'!'), Some(12));
+        assert_eq!(simd::find_byte(text, b'?'), None);
+    }
+
+    #[test]
+    fn test_simd_normalize_whitespace() {
+        let normalized = simd::normalize_whitespace("  Hello   \n\tworld  ");
+        assert_eq!(normalized, "Hello world");
+    }
 }
\ No newline at end of file
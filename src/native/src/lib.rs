@@ -47,10 +47,36 @@ pub struct SystemInfo {
     pub cpu_threads: u32,
     pub simd_support: bool,
     pub memory_allocator: String,
+    pub cpu_model: String,
+    pub physical_cores: u32,
+    pub logical_cores: u32,
+    pub cpu_load_per_core: Vec<f64>,
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    pub used_memory_bytes: u64,
+    pub total_swap_bytes: u64,
+    pub used_swap_bytes: u64,
 }
 
 #[napi]
 pub fn get_system_info() -> SystemInfo {
+    use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+
+    let mut system = System::new_with_specifics(
+        RefreshKind::new()
+            .with_memory(MemoryRefreshKind::everything())
+            .with_cpu(CpuRefreshKind::everything()),
+    );
+    system.refresh_memory();
+    system.refresh_cpu();
+
+    let cpu_model = system
+        .cpus()
+        .first()
+        .map(|cpu| cpu.brand().to_string())
+        .unwrap_or_default();
+    let cpu_load_per_core = system.cpus().iter().map(|cpu| cpu.cpu_usage() as f64).collect();
+
     SystemInfo {
         version: env!("CARGO_PKG_VERSION").to_string(),
         cpu_threads: rayon::current_num_threads() as u32,
@@ -60,6 +86,15 @@ pub fn get_system_info() -> SystemInfo {
         } else {
             "system".to_string()
         },
+        cpu_model,
+        physical_cores: System::physical_core_count().unwrap_or(0) as u32,
+        logical_cores: system.cpus().len() as u32,
+        cpu_load_per_core,
+        total_memory_bytes: system.total_memory(),
+        available_memory_bytes: system.available_memory(),
+        used_memory_bytes: system.used_memory(),
+        total_swap_bytes: system.total_swap(),
+        used_swap_bytes: system.used_swap(),
     }
 }
 
@@ -0,0 +1,1224 @@
+//! Memory management utilities for the native module
+
+use crate::error::{NativeError, Result};
+use napi_derive::napi;
+use std::alloc::{GlobalAlloc, Layout, System as SystemAlloc};
+#[cfg(feature = "thread-safe")]
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+#[cfg(feature = "thread-safe")]
+use std::sync::Arc;
+use std::sync::{Mutex, OnceLock};
+#[cfg(feature = "thread-safe")]
+use std::sync::RwLock;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, Pid, ProcessRefreshKind, RefreshKind, System};
+
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Hard allocation cap set via [`MemoryManager::set_memory_limit`]. `u64::MAX`
+/// means "no limit" (the default).
+static MEMORY_LIMIT_BYTES: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// Soft warning threshold set via [`MemoryManager::set_soft_memory_limit`].
+/// `u64::MAX` means "no soft limit".
+static SOFT_LIMIT_BYTES: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// Whether `ALLOCATED_BYTES` is currently at or above `SOFT_LIMIT_BYTES`,
+/// polled by [`is_over_soft_limit`] and also driving the callback hook below.
+static SOFT_LIMIT_EXCEEDED: AtomicBool = AtomicBool::new(false);
+
+/// Rust-side hook fired the moment `ALLOCATED_BYTES` crosses `SOFT_LIMIT_BYTES`
+/// going up. Set with [`set_soft_limit_callback`]; JS callers instead poll
+/// [`is_over_soft_limit`], since invoking an arbitrary JS callback from an
+/// allocator hook — which can run on any thread, at any time — isn't safe.
+static SOFT_LIMIT_CALLBACK: Mutex<Option<Box<dyn Fn(u64) + Send + Sync>>> = Mutex::new(None);
+
+/// `GlobalAlloc` wrapper that keeps `ALLOCATED_BYTES`/`PEAK_BYTES` in sync
+/// with every real allocation instead of relying on manual
+/// `track_allocation`/`track_deallocation` call sites, and turns
+/// `MemoryManager::set_memory_limit` into an actual guard rail: allocations
+/// that would push usage past the limit fail (return a null pointer) rather
+/// than silently overcommitting.
+pub struct TrackingAlloc<A> {
+    inner: A,
+}
+
+impl<A> TrackingAlloc<A> {
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+impl<A> TrackingAlloc<A> {
+    /// `true` if allocating `additional_bytes` more would exceed the hard limit
+    fn would_exceed_limit(additional_bytes: u64) -> bool {
+        let limit = MEMORY_LIMIT_BYTES.load(Ordering::Relaxed);
+        limit != u64::MAX && ALLOCATED_BYTES.load(Ordering::Relaxed) + additional_bytes > limit
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if Self::would_exceed_limit(layout.size() as u64) {
+            return std::ptr::null_mut();
+        }
+
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            track_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        track_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if new_size > layout.size() && Self::would_exceed_limit((new_size - layout.size()) as u64) {
+            return std::ptr::null_mut();
+        }
+
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                track_allocation(new_size - layout.size());
+            } else {
+                track_deallocation(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[cfg(not(feature = "jemalloc"))]
+#[global_allocator]
+static GLOBAL_ALLOCATOR: TrackingAlloc<SystemAlloc> = TrackingAlloc::new(SystemAlloc);
+
+#[cfg(feature = "jemalloc")]
+#[global_allocator]
+static GLOBAL_ALLOCATOR: TrackingAlloc<jemallocator::Jemalloc> =
+    TrackingAlloc::new(jemallocator::Jemalloc);
+
+/// Register a Rust-side callback fired the moment live allocations cross the
+/// soft limit set via `set_soft_memory_limit`. The callback must not allocate
+/// — it runs with the tracking allocator's internal lock held.
+pub fn set_soft_limit_callback(callback: impl Fn(u64) + Send + Sync + 'static) {
+    if let Ok(mut guard) = SOFT_LIMIT_CALLBACK.lock() {
+        *guard = Some(Box::new(callback));
+    }
+}
+
+/// Re-evaluate the soft limit after `allocated` changes, firing the callback
+/// hook on the rising edge (not on every allocation once already over).
+fn check_soft_limit(allocated: u64) {
+    let soft = SOFT_LIMIT_BYTES.load(Ordering::Relaxed);
+    if soft == u64::MAX {
+        return;
+    }
+
+    if allocated >= soft {
+        if !SOFT_LIMIT_EXCEEDED.swap(true, Ordering::Relaxed) {
+            if let Ok(guard) = SOFT_LIMIT_CALLBACK.lock() {
+                if let Some(callback) = guard.as_ref() {
+                    callback(allocated);
+                }
+            }
+        }
+    } else {
+        SOFT_LIMIT_EXCEEDED.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Single shared `sysinfo::System`, so repeated N-API calls refresh just the
+/// counters they need instead of re-probing the whole system every time.
+static SYSTEM: OnceLock<Mutex<System>> = OnceLock::new();
+
+fn system() -> &'static Mutex<System> {
+    SYSTEM.get_or_init(|| {
+        Mutex::new(System::new_with_specifics(
+            RefreshKind::new()
+                .with_memory(MemoryRefreshKind::everything())
+                .with_cpu(CpuRefreshKind::everything()),
+        ))
+    })
+}
+
+/// Memory statistics
+#[napi(object)]
+pub struct MemoryInfo {
+    pub allocated_bytes: u64,
+    pub peak_bytes: u64,
+    pub available_bytes: u64,
+    pub system_total: u64,
+    pub fragmentation_ratio: f64,
+}
+
+/// Memory management utilities
+#[napi]
+pub struct MemoryManager;
+
+#[napi]
+impl MemoryManager {
+    /// Get current memory information
+    #[napi]
+    pub fn get_memory_info() -> napi::Result<MemoryInfo> {
+        let allocated = ALLOCATED_BYTES.load(Ordering::Relaxed);
+        let peak = PEAK_BYTES.load(Ordering::Relaxed);
+
+        #[cfg(feature = "jemalloc")]
+        {
+            let jemalloc_allocated = get_jemalloc_stat("stats.allocated").unwrap_or(0);
+            let jemalloc_resident = get_jemalloc_stat("stats.resident").unwrap_or(0);
+
+            let fragmentation = if jemalloc_allocated > 0 {
+                jemalloc_resident as f64 / jemalloc_allocated as f64
+            } else {
+                1.0
+            };
+
+            Ok(MemoryInfo {
+                allocated_bytes: jemalloc_allocated,
+                peak_bytes: peak.max(jemalloc_allocated),
+                available_bytes: get_available_memory(),
+                system_total: get_system_memory(),
+                fragmentation_ratio: fragmentation,
+            })
+        }
+
+        #[cfg(not(feature = "jemalloc"))]
+        {
+            Ok(MemoryInfo {
+                allocated_bytes: allocated,
+                peak_bytes: peak,
+                available_bytes: get_available_memory(),
+                system_total: get_system_memory(),
+                fragmentation_ratio: 1.0,
+            })
+        }
+    }
+
+    /// Force garbage collection (if applicable)
+    #[napi]
+    pub fn force_gc() -> napi::Result<()> {
+        #[cfg(feature = "jemalloc")]
+        {
+            unsafe {
+                jemalloc_sys::mallctl(
+                    b"thread.tcache.flush\0".as_ptr() as *const i8,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    0,
+                );
+
+                jemalloc_sys::mallctl(
+                    b"arenas.purge\0".as_ptr() as *const i8,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    0,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Toggle jemalloc heap profiling via the `prof.active` mallctl, so
+    /// samples are only collected (and [`MemoryManager::dump_heap_profile`]
+    /// has something to dump) while actively diagnosing a leak. No-op
+    /// outside the `jemalloc` feature. The jemalloc binary must itself have
+    /// been built with profiling support (`MALLOC_CONF=prof:true` or
+    /// equivalent) for this to take effect.
+    #[napi]
+    pub fn enable_heap_profiling(active: bool) -> napi::Result<()> {
+        #[cfg(feature = "jemalloc")]
+        {
+            let mut value = active;
+            let result = unsafe {
+                jemalloc_sys::mallctl(
+                    b"prof.active\0".as_ptr() as *const i8,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    &mut value as *mut bool as *mut std::ffi::c_void,
+                    std::mem::size_of::<bool>(),
+                )
+            };
+
+            if result != 0 {
+                return Err(NativeError::memory(format!(
+                    "Failed to set prof.active to {}: jemalloc may not have been built with \
+                     profiling support (MALLOC_CONF=prof:true)",
+                    active
+                ))
+                .into());
+            }
+        }
+
+        #[cfg(not(feature = "jemalloc"))]
+        {
+            let _ = active;
+        }
+
+        Ok(())
+    }
+
+    /// Write a jemalloc heap profile to `path` via the `prof.dump` mallctl,
+    /// returning `path` back so callers can hand it straight to `jeprof`/
+    /// `pprof` without threading the string through twice. Requires
+    /// [`MemoryManager::enable_heap_profiling`] to have been turned on first.
+    #[napi]
+    pub fn dump_heap_profile(path: String) -> napi::Result<String> {
+        #[cfg(feature = "jemalloc")]
+        {
+            use std::ffi::CString;
+
+            let path_c = CString::new(path.as_str())
+                .map_err(|e| NativeError::memory(format!("Invalid profile path: {}", e)))?;
+            let mut path_ptr = path_c.as_ptr();
+
+            let result = unsafe {
+                jemalloc_sys::mallctl(
+                    b"prof.dump\0".as_ptr() as *const i8,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    &mut path_ptr as *mut *const i8 as *mut std::ffi::c_void,
+                    std::mem::size_of::<*const i8>(),
+                )
+            };
+
+            if result != 0 {
+                return Err(NativeError::memory(format!(
+                    "Failed to dump heap profile to {}: call enable_heap_profiling(true) first",
+                    path
+                ))
+                .into());
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Advance jemalloc's `epoch` mallctl so the next `stats.*` read (and
+    /// therefore [`MemoryManager::get_memory_breakdown`]) reflects a fresh
+    /// snapshot instead of counters cached from before the last
+    /// allocation/deallocation. No-op outside the `jemalloc` feature.
+    #[napi]
+    pub fn refresh_arena_stats() -> napi::Result<()> {
+        #[cfg(feature = "jemalloc")]
+        {
+            let mut epoch: u64 = 1;
+            let result = unsafe {
+                jemalloc_sys::mallctl(
+                    b"epoch\0".as_ptr() as *const i8,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    &mut epoch as *mut u64 as *mut std::ffi::c_void,
+                    std::mem::size_of::<u64>(),
+                )
+            };
+
+            if result != 0 {
+                return Err(NativeError::memory("Failed to advance jemalloc epoch").into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the hard memory limit; allocations that would push live usage past
+    /// it fail instead of succeeding, via `TrackingAlloc`.
+    #[napi]
+    pub fn set_memory_limit(limit_bytes: u64) -> napi::Result<()> {
+        tracing::info!("Memory limit set to {} bytes", limit_bytes);
+        MEMORY_LIMIT_BYTES.store(limit_bytes, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// `true` if live allocations are currently at or above the hard limit set
+    /// by `set_memory_limit`.
+    #[napi]
+    pub fn is_over_limit() -> bool {
+        let limit = MEMORY_LIMIT_BYTES.load(Ordering::Relaxed);
+        limit != u64::MAX && ALLOCATED_BYTES.load(Ordering::Relaxed) >= limit
+    }
+
+    /// Set a soft warning threshold, below the hard limit, that fires
+    /// `set_soft_limit_callback`'s hook and flips `is_over_soft_limit` once
+    /// crossed.
+    #[napi]
+    pub fn set_soft_memory_limit(limit_bytes: u64) -> napi::Result<()> {
+        SOFT_LIMIT_BYTES.store(limit_bytes, Ordering::Relaxed);
+        check_soft_limit(ALLOCATED_BYTES.load(Ordering::Relaxed));
+        Ok(())
+    }
+
+    /// `true` if live allocations are currently at or above the soft limit set
+    /// by `set_soft_memory_limit`.
+    #[napi]
+    pub fn is_over_soft_limit() -> bool {
+        SOFT_LIMIT_EXCEEDED.load(Ordering::Relaxed)
+    }
+
+    /// Get memory usage by category
+    #[napi]
+    pub fn get_memory_breakdown() -> napi::Result<MemoryBreakdown> {
+        #[cfg(feature = "jemalloc")]
+        {
+            let allocated = get_jemalloc_stat("stats.allocated").unwrap_or(0);
+            let active = get_jemalloc_stat("stats.active").unwrap_or(0);
+            let mapped = get_jemalloc_stat("stats.mapped").unwrap_or(0);
+            let resident = get_jemalloc_stat("stats.resident").unwrap_or(0);
+            let metadata = get_jemalloc_stat("stats.metadata").unwrap_or(0);
+
+            Ok(MemoryBreakdown {
+                active_bytes: active,
+                allocated_bytes: allocated,
+                mapped_bytes: mapped,
+                resident_bytes: resident,
+                metadata_bytes: metadata,
+                retained_bytes: mapped.saturating_sub(resident),
+            })
+        }
+
+        #[cfg(not(feature = "jemalloc"))]
+        {
+            let (committed, resident) = global_arena_bytes();
+            Ok(MemoryBreakdown {
+                active_bytes: resident as u64,
+                allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+                mapped_bytes: committed as u64,
+                resident_bytes: resident as u64,
+                metadata_bytes: 0,
+                retained_bytes: (committed.saturating_sub(resident)) as u64,
+            })
+        }
+    }
+}
+
+/// Detailed memory breakdown
+#[napi(object)]
+pub struct MemoryBreakdown {
+    pub active_bytes: u64,
+    pub allocated_bytes: u64,
+    pub mapped_bytes: u64,
+    pub resident_bytes: u64,
+    pub metadata_bytes: u64,
+    pub retained_bytes: u64,
+}
+
+/// Per-process resource usage, refreshed from the shared `System` instance
+#[napi(object)]
+pub struct ProcessStats {
+    pub rss_bytes: u64,
+    pub virtual_bytes: u64,
+    pub cpu_percent: f64,
+}
+
+/// Get this process's RSS, virtual memory size, and CPU usage percentage
+#[napi]
+pub fn get_process_stats() -> napi::Result<ProcessStats> {
+    let pid = Pid::from_u32(std::process::id());
+    let mut system = system().lock().map_err(|_| NativeError::memory("System mutex poisoned"))?;
+
+    system.refresh_process_specifics(pid, ProcessRefreshKind::everything());
+
+    let process = system
+        .process(pid)
+        .ok_or_else(|| NativeError::memory("Failed to read this process's stats"))?;
+
+    Ok(ProcessStats {
+        rss_bytes: process.memory(),
+        virtual_bytes: process.virtual_memory(),
+        cpu_percent: process.cpu_usage() as f64,
+    })
+}
+
+/// Track memory allocation
+pub fn track_allocation(size: usize) {
+    let new_allocated = ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+
+    // Update peak if necessary
+    let mut current_peak = PEAK_BYTES.load(Ordering::Relaxed);
+    while new_allocated > current_peak {
+        match PEAK_BYTES.compare_exchange_weak(
+            current_peak,
+            new_allocated,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => break,
+            Err(actual) => current_peak = actual,
+        }
+    }
+
+    check_soft_limit(new_allocated);
+}
+
+/// Track memory deallocation
+pub fn track_deallocation(size: usize) {
+    let new_allocated = ALLOCATED_BYTES.fetch_sub(size as u64, Ordering::Relaxed) - size as u64;
+    check_soft_limit(new_allocated);
+}
+
+/// Get jemalloc statistics
+#[cfg(feature = "jemalloc")]
+pub fn get_jemalloc_stat(name: &str) -> Result<u64> {
+    use std::ffi::CString;
+    use std::mem;
+
+    let name_c = CString::new(name)
+        .map_err(|e| NativeError::memory(format!("Invalid stat name: {}", e)))?;
+
+    let mut value: u64 = 0;
+    let mut value_len = mem::size_of::<u64>();
+
+    let result = unsafe {
+        jemalloc_sys::mallctl(
+            name_c.as_ptr(),
+            &mut value as *mut u64 as *mut std::ffi::c_void,
+            &mut value_len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+
+    if result == 0 {
+        Ok(value)
+    } else {
+        Err(NativeError::memory(format!("Failed to get jemalloc stat: {}", name)))
+    }
+}
+
+/// Get available system memory in bytes, via `sysinfo` (works the same on
+/// Linux/macOS/Windows instead of hand-parsing `/proc/meminfo`)
+pub fn get_available_memory() -> u64 {
+    let mut system = match system().lock() {
+        Ok(system) => system,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    system.refresh_memory_specifics(MemoryRefreshKind::everything());
+    system.available_memory()
+}
+
+/// Get total system memory in bytes, via `sysinfo`
+pub fn get_system_memory() -> u64 {
+    let mut system = match system().lock() {
+        Ok(system) => system,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    system.refresh_memory_specifics(MemoryRefreshKind::everything());
+    system.total_memory()
+}
+
+/// Memory pool for efficient allocation
+pub struct MemoryPool {
+    blocks: Vec<Vec<u8>>,
+    block_size: usize,
+    used_blocks: Vec<bool>,
+}
+
+impl MemoryPool {
+    /// Create a new memory pool
+    pub fn new(block_size: usize, initial_blocks: usize) -> Self {
+        let mut blocks = Vec::with_capacity(initial_blocks);
+        let mut used_blocks = Vec::with_capacity(initial_blocks);
+
+        for _ in 0..initial_blocks {
+            blocks.push(vec![0u8; block_size]);
+            used_blocks.push(false);
+        }
+
+        // No manual track_allocation call here: `vec![0u8; block_size]` above
+        // already went through `TrackingAlloc`, which accounts for it.
+
+        MemoryPool {
+            blocks,
+            block_size,
+            used_blocks,
+        }
+    }
+
+    /// Allocate a block from the pool
+    pub fn allocate(&mut self) -> Option<usize> {
+        for (index, &used) in self.used_blocks.iter().enumerate() {
+            if !used {
+                self.used_blocks[index] = true;
+                return Some(index);
+            }
+        }
+
+        // No free blocks, allocate a new one
+        let index = self.blocks.len();
+        self.blocks.push(vec![0u8; self.block_size]);
+        self.used_blocks.push(true);
+
+        Some(index)
+    }
+
+    /// Deallocate a block back to the pool
+    pub fn deallocate(&mut self, index: usize) {
+        if index < self.used_blocks.len() {
+            self.used_blocks[index] = false;
+        }
+    }
+
+    /// Get block data
+    pub fn get_block(&self, index: usize) -> Option<&[u8]> {
+        self.blocks.get(index).map(|v| v.as_slice())
+    }
+
+    /// Get mutable block data
+    pub fn get_block_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+        self.blocks.get_mut(index).map(|v| v.as_mut_slice())
+    }
+}
+
+// No Drop impl needed: dropping `blocks` frees each `Vec<u8>` through the
+// normal allocator path, which `TrackingAlloc` already accounts for.
+
+/// Thread-safe counterpart to [`MemoryPool`], enabled via the `thread-safe`
+/// feature, for sharing one pool across rayon worker threads. [`MemoryPool`]
+/// itself stays `&mut self`-only and feature-free so single-threaded callers
+/// pay no locking cost.
+#[cfg(feature = "thread-safe")]
+#[derive(Clone)]
+pub struct ConcurrentMemoryPool {
+    inner: Arc<ConcurrentPoolInner>,
+}
+
+#[cfg(feature = "thread-safe")]
+struct ConcurrentPoolInner {
+    state: RwLock<ConcurrentPoolState>,
+    block_size: usize,
+}
+
+#[cfg(feature = "thread-safe")]
+struct ConcurrentPoolState {
+    // `Box<[u8]>` rather than `Vec<u8>` so a block's heap address is stable
+    // across `blocks.push(..)` growing the outer `Vec` — `PoolBlock` holds a
+    // raw pointer into this storage past the point where the write lock here
+    // is released.
+    blocks: Vec<Box<[u8]>>,
+    used: Vec<bool>,
+}
+
+#[cfg(feature = "thread-safe")]
+impl ConcurrentMemoryPool {
+    /// Create a new thread-safe memory pool
+    pub fn new(block_size: usize, initial_blocks: usize) -> Self {
+        let mut blocks = Vec::with_capacity(initial_blocks);
+        let mut used = Vec::with_capacity(initial_blocks);
+        for _ in 0..initial_blocks {
+            blocks.push(vec![0u8; block_size].into_boxed_slice());
+            used.push(false);
+        }
+
+        Self {
+            inner: Arc::new(ConcurrentPoolInner {
+                state: RwLock::new(ConcurrentPoolState { blocks, used }),
+                block_size,
+            }),
+        }
+    }
+
+    /// Claim a free block (growing the pool if none is free) and return an
+    /// RAII handle that returns it to the pool when dropped.
+    pub fn allocate(&self) -> PoolBlock {
+        let mut state = self.inner.state.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let index = state
+            .used
+            .iter()
+            .position(|&used| !used)
+            .unwrap_or_else(|| {
+                state.blocks.push(vec![0u8; self.inner.block_size].into_boxed_slice());
+                state.used.push(false);
+                state.blocks.len() - 1
+            });
+        state.used[index] = true;
+
+        let block = &mut state.blocks[index];
+        let ptr = NonNull::new(block.as_mut_ptr()).expect("pool blocks are never zero-sized");
+        let len = block.len();
+        drop(state);
+
+        PoolBlock {
+            pool: self.inner.clone(),
+            index,
+            ptr,
+            len,
+        }
+    }
+
+    /// Read a free block's current contents by index without claiming it.
+    /// Returns `None` for an out-of-range index *or* one currently claimed by
+    /// a live [`PoolBlock`] — that block's bytes are only safe to read
+    /// through the handle itself, since the handle writes to them outside
+    /// this lock.
+    pub fn get_block(&self, index: usize) -> Option<Vec<u8>> {
+        let state = self.inner.state.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *state.used.get(index)? {
+            return None;
+        }
+        state.blocks.get(index).map(|block| block.to_vec())
+    }
+}
+
+/// RAII handle to a block claimed from a [`ConcurrentMemoryPool`]. Derefs to
+/// the block's bytes and returns the block to its pool's free list on drop,
+/// so a panicking worker can't leak it.
+#[cfg(feature = "thread-safe")]
+pub struct PoolBlock {
+    pool: Arc<ConcurrentPoolInner>,
+    index: usize,
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+#[cfg(feature = "thread-safe")]
+impl std::ops::Deref for PoolBlock {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr`/`len` point at this block's boxed storage, which
+        // `ConcurrentPoolState` never moves or frees while `used[index]` is
+        // `true` — the only state this handle's `Drop` impl ever sets back to
+        // `false`, so no other handle to the same index can exist concurrently.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(feature = "thread-safe")]
+impl std::ops::DerefMut for PoolBlock {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: see `Deref` above.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(feature = "thread-safe")]
+impl Drop for PoolBlock {
+    fn drop(&mut self) {
+        let mut state = self
+            .pool
+            .state
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(used) = state.used.get_mut(self.index) {
+            *used = false;
+        }
+    }
+}
+
+// SAFETY: a `PoolBlock` has exclusive access to its block (see `Deref`'s
+// safety comment), so it's sound to move the handle to another thread.
+#[cfg(feature = "thread-safe")]
+unsafe impl Send for PoolBlock {}
+
+/// Power-of-two size classes (256B .. 4MB) that `ArenaAllocator` buckets
+/// allocations into.
+const ARENA_SIZE_CLASSES: &[usize] = &[
+    256, 512, 1024, 2048, 4096, 8192, 16384, 32768, 65536, 131072, 262144, 524288, 1_048_576,
+    2_097_152, 4_194_304,
+];
+
+/// Chunk size requested from the OS per size class, grown via `mremap` in
+/// place when a chunk fills up rather than reallocating.
+const ARENA_CHUNK_BYTES: usize = 1 << 20; // 1MB
+
+/// mmap-backed bump allocation, used on Linux where `mremap`/`madvise` are
+/// available. A chunk's address never moves once handed-out pointers exist
+/// in it: `grow_in_place` asks the kernel to extend the mapping at its
+/// current address (no `MREMAP_MAYMOVE`) and simply fails if it can't, so a
+/// full chunk is retained and a fresh one started rather than risking
+/// invalidating pointers already carved out of it.
+#[cfg(target_os = "linux")]
+mod arena_chunk {
+    use std::ptr::NonNull;
+
+    pub(super) struct Chunk {
+        ptr: NonNull<u8>,
+        pub(super) len: usize,
+        pub(super) cursor: usize,
+        pub(super) trimmed: bool,
+    }
+
+    impl Chunk {
+        pub(super) fn new(len: usize) -> Option<Self> {
+            let raw = unsafe {
+                libc::mmap(
+                    std::ptr::null_mut(),
+                    len,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                    -1,
+                    0,
+                )
+            };
+            if raw == libc::MAP_FAILED {
+                return None;
+            }
+            Some(Self {
+                ptr: NonNull::new(raw as *mut u8)?,
+                len,
+                cursor: 0,
+                trimmed: false,
+            })
+        }
+
+        pub(super) fn grow_in_place(&mut self, new_len: usize) -> bool {
+            let raw = unsafe {
+                libc::mremap(self.ptr.as_ptr() as *mut libc::c_void, self.len, new_len, 0)
+            };
+            if raw == libc::MAP_FAILED {
+                return false;
+            }
+            debug_assert_eq!(raw as *mut u8, self.ptr.as_ptr());
+            self.len = new_len;
+            self.trimmed = false;
+            true
+        }
+
+        pub(super) fn bump(&mut self, size: usize) -> Option<NonNull<u8>> {
+            if self.cursor + size > self.len {
+                return None;
+            }
+            let ptr = unsafe { self.ptr.as_ptr().add(self.cursor) };
+            self.cursor += size;
+            self.trimmed = false;
+            NonNull::new(ptr)
+        }
+
+        /// `madvise(MADV_DONTNEED)` this chunk, releasing its physical pages
+        /// back to the OS while leaving the address mapping reserved.
+        pub(super) fn trim(&mut self) {
+            unsafe {
+                libc::madvise(
+                    self.ptr.as_ptr() as *mut libc::c_void,
+                    self.len,
+                    libc::MADV_DONTNEED,
+                );
+            }
+            self.trimmed = true;
+        }
+    }
+
+    impl Drop for Chunk {
+        fn drop(&mut self) {
+            unsafe {
+                libc::munmap(self.ptr.as_ptr() as *mut libc::c_void, self.len);
+            }
+        }
+    }
+}
+
+/// Plain heap-backed fallback chunk for platforms without `mmap`/`mremap`
+/// (no trimming — every byte is always "resident").
+#[cfg(not(target_os = "linux"))]
+mod arena_chunk {
+    use std::ptr::NonNull;
+
+    pub(super) struct Chunk {
+        storage: Box<[u8]>,
+        pub(super) len: usize,
+        pub(super) cursor: usize,
+        pub(super) trimmed: bool,
+    }
+
+    impl Chunk {
+        pub(super) fn new(len: usize) -> Option<Self> {
+            Some(Self {
+                storage: vec![0u8; len].into_boxed_slice(),
+                len,
+                cursor: 0,
+                trimmed: false,
+            })
+        }
+
+        pub(super) fn grow_in_place(&mut self, _new_len: usize) -> bool {
+            false
+        }
+
+        pub(super) fn bump(&mut self, size: usize) -> Option<NonNull<u8>> {
+            if self.cursor + size > self.len {
+                return None;
+            }
+            let ptr = unsafe { self.storage.as_mut_ptr().add(self.cursor) };
+            self.cursor += size;
+            NonNull::new(ptr)
+        }
+
+        /// No-op: there is no `madvise`-equivalent way to decommit part of a
+        /// `Box<[u8]>` portably, so non-Linux builds simply retain every byte
+        /// they've ever committed until the chunk itself is dropped.
+        pub(super) fn trim(&mut self) {}
+    }
+}
+
+/// One size class's chunks and free list
+struct SizeClassPool {
+    class_size: usize,
+    chunks: Vec<arena_chunk::Chunk>,
+    free_list: Vec<std::ptr::NonNull<u8>>,
+    /// Allocations handed out minus allocations returned; `trim()` only
+    /// touches chunks once this reaches zero, since a chunk can't be proven
+    /// fully free (and therefore safe to `madvise(MADV_DONTNEED)`) otherwise.
+    live: usize,
+}
+
+impl SizeClassPool {
+    fn new(class_size: usize) -> Self {
+        Self {
+            class_size,
+            chunks: Vec::new(),
+            free_list: Vec::new(),
+            live: 0,
+        }
+    }
+
+    fn allocate(&mut self) -> Option<std::ptr::NonNull<u8>> {
+        if let Some(ptr) = self.free_list.pop() {
+            self.live += 1;
+            return Some(ptr);
+        }
+
+        let ptr = if let Some(chunk) = self.chunks.last_mut() {
+            if let Some(ptr) = chunk.bump(self.class_size) {
+                Some(ptr)
+            } else {
+                // Current chunk is full; try to extend it in place before
+                // falling back to a brand new chunk.
+                let grown_len = chunk.len + ARENA_CHUNK_BYTES.max(self.class_size);
+                if chunk.grow_in_place(grown_len) {
+                    chunk.bump(self.class_size)
+                } else {
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let ptr = ptr.or_else(|| {
+            let chunk_len = ARENA_CHUNK_BYTES.max(self.class_size);
+            let mut chunk = arena_chunk::Chunk::new(chunk_len)?;
+            let ptr = chunk.bump(self.class_size);
+            self.chunks.push(chunk);
+            ptr
+        });
+
+        if ptr.is_some() {
+            self.live += 1;
+        }
+        ptr
+    }
+
+    fn deallocate(&mut self, ptr: std::ptr::NonNull<u8>) {
+        self.free_list.push(ptr);
+        self.live = self.live.saturating_sub(1);
+    }
+
+    /// `madvise(MADV_DONTNEED)` every chunk, but only once every allocation
+    /// this pool has handed out has been returned — trimming a chunk that
+    /// still has live data in it would discard that data.
+    fn trim(&mut self) {
+        if self.live > 0 {
+            return;
+        }
+        for chunk in &mut self.chunks {
+            chunk.trim();
+        }
+    }
+
+    fn committed_bytes(&self) -> usize {
+        self.chunks.iter().map(|c| c.len).sum()
+    }
+
+    fn resident_bytes(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| !c.trimmed)
+            .map(|c| c.len)
+            .sum()
+    }
+}
+
+/// Arena allocator for hot paths (tokenizer/tensor buffers) that would
+/// otherwise churn the global allocator with many similarly-sized,
+/// short-lived allocations. Buckets requests into power-of-two size classes,
+/// each backed by mmap'd chunks grown via `mremap` rather than copied, and
+/// frees just push back onto that class's free list instead of unmapping —
+/// call `trim()` periodically to actually give idle pages back to the OS.
+pub struct ArenaAllocator {
+    pools: Vec<SizeClassPool>,
+}
+
+impl ArenaAllocator {
+    pub fn new() -> Self {
+        Self {
+            pools: ARENA_SIZE_CLASSES.iter().map(|&size| SizeClassPool::new(size)).collect(),
+        }
+    }
+
+    fn class_index_for(&self, size: usize) -> Option<usize> {
+        ARENA_SIZE_CLASSES.iter().position(|&class_size| class_size >= size)
+    }
+
+    /// Allocate at least `size` bytes, rounded up to the nearest size class.
+    /// Returns `None` if `size` exceeds the largest class or the OS is out
+    /// of memory.
+    ///
+    /// On Linux, chunks are `mmap`'d directly and never pass through the
+    /// global allocator, so `ALLOCATED_BYTES` is updated here explicitly. On
+    /// other platforms a chunk's backing `Box<[u8]>` already goes through
+    /// `TrackingAlloc`, so tracking it again here would double-count it.
+    pub fn allocate(&mut self, size: usize) -> Option<std::ptr::NonNull<u8>> {
+        let index = self.class_index_for(size)?;
+        let ptr = self.pools[index].allocate()?;
+        #[cfg(target_os = "linux")]
+        track_allocation(ARENA_SIZE_CLASSES[index]);
+        Some(ptr)
+    }
+
+    /// Return a pointer previously returned by `allocate(size)` to its size
+    /// class's free list (`size` must match the original request). See
+    /// [`ArenaAllocator::allocate`] for why tracking is Linux-only.
+    pub fn deallocate(&mut self, ptr: std::ptr::NonNull<u8>, size: usize) {
+        if let Some(index) = self.class_index_for(size) {
+            self.pools[index].deallocate(ptr);
+            #[cfg(target_os = "linux")]
+            track_deallocation(ARENA_SIZE_CLASSES[index]);
+        }
+    }
+
+    /// Give fully-idle chunks' physical pages back to the OS
+    /// (`madvise(MADV_DONTNEED)`); the address space stays reserved.
+    pub fn trim(&mut self) {
+        for pool in &mut self.pools {
+            pool.trim();
+        }
+    }
+
+    /// Total bytes reserved from the OS across all size classes
+    pub fn committed_bytes(&self) -> usize {
+        self.pools.iter().map(|p| p.committed_bytes()).sum()
+    }
+
+    /// Bytes still physically backed (not yet `madvise`d away)
+    pub fn resident_bytes(&self) -> usize {
+        self.pools.iter().map(|p| p.resident_bytes()).sum()
+    }
+}
+
+impl Default for ArenaAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// `Chunk`'s raw pointers never escape `ArenaAllocator` across a real thread
+// boundary without going through the allocator's own locking, so the arena as
+// a whole is safe to share behind a `Mutex`.
+unsafe impl Send for ArenaAllocator {}
+
+static GLOBAL_ARENA: OnceLock<Mutex<ArenaAllocator>> = OnceLock::new();
+
+fn global_arena() -> &'static Mutex<ArenaAllocator> {
+    GLOBAL_ARENA.get_or_init(|| Mutex::new(ArenaAllocator::new()))
+}
+
+/// `(committed_bytes, resident_bytes)` for the shared arena, used by
+/// `get_memory_breakdown()` to report real `retained_bytes` without jemalloc.
+fn global_arena_bytes() -> (usize, usize) {
+    match global_arena().lock() {
+        Ok(arena) => (arena.committed_bytes(), arena.resident_bytes()),
+        Err(poisoned) => {
+            let arena = poisoned.into_inner();
+            (arena.committed_bytes(), arena.resident_bytes())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_tracking() {
+        let initial = ALLOCATED_BYTES.load(Ordering::Relaxed);
+
+        track_allocation(1024);
+        assert_eq!(ALLOCATED_BYTES.load(Ordering::Relaxed), initial + 1024);
+
+        track_deallocation(512);
+        assert_eq!(ALLOCATED_BYTES.load(Ordering::Relaxed), initial + 512);
+    }
+
+    #[test]
+    fn test_memory_pool() {
+        let mut pool = MemoryPool::new(1024, 2);
+
+        let block1 = pool.allocate().unwrap();
+        let block2 = pool.allocate().unwrap();
+
+        assert_eq!(block1, 0);
+        assert_eq!(block2, 1);
+
+        pool.deallocate(block1);
+        let block3 = pool.allocate().unwrap();
+        assert_eq!(block3, 0); // Reused block1
+    }
+
+    #[test]
+    fn test_memory_info() {
+        let info = MemoryManager::get_memory_info().unwrap();
+        assert!(info.system_total > 0);
+        assert!(info.peak_bytes >= info.allocated_bytes);
+    }
+
+    #[test]
+    fn test_get_process_stats() {
+        let stats = get_process_stats().unwrap();
+        assert!(stats.rss_bytes > 0);
+    }
+
+    #[cfg(not(feature = "jemalloc"))]
+    #[test]
+    fn test_heap_profiling_is_noop_without_jemalloc() {
+        MemoryManager::enable_heap_profiling(true).unwrap();
+        let path = MemoryManager::dump_heap_profile("/tmp/profile.heap".to_string()).unwrap();
+        assert_eq!(path, "/tmp/profile.heap");
+        MemoryManager::refresh_arena_stats().unwrap();
+    }
+
+    #[test]
+    fn test_soft_limit_fires_callback_once_on_rising_edge() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        let fired = Arc::new(AtomicUsize::new(0));
+        let fired_clone = fired.clone();
+        set_soft_limit_callback(move |_| {
+            fired_clone.fetch_add(1, Ordering::Relaxed);
+        });
+
+        let baseline = ALLOCATED_BYTES.load(Ordering::Relaxed);
+        SOFT_LIMIT_BYTES.store(baseline + 1024, Ordering::Relaxed);
+        SOFT_LIMIT_EXCEEDED.store(false, Ordering::Relaxed);
+
+        check_soft_limit(baseline + 2048);
+        assert!(is_over_soft_limit_for_test());
+        assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+        // Staying above the threshold shouldn't re-fire the callback.
+        check_soft_limit(baseline + 4096);
+        assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+        // Dropping back below clears the flag for the next rising edge.
+        check_soft_limit(baseline);
+        assert!(!is_over_soft_limit_for_test());
+
+        SOFT_LIMIT_BYTES.store(u64::MAX, Ordering::Relaxed);
+    }
+
+    fn is_over_soft_limit_for_test() -> bool {
+        SOFT_LIMIT_EXCEEDED.load(Ordering::Relaxed)
+    }
+
+    #[test]
+    fn test_arena_allocator_reuses_freed_slot() {
+        let mut arena = ArenaAllocator::new();
+
+        let a = arena.allocate(300).unwrap();
+        let b = arena.allocate(300).unwrap();
+        assert_ne!(a, b);
+
+        arena.deallocate(a, 300);
+        let c = arena.allocate(300).unwrap();
+        assert_eq!(a, c); // Reused the freed slot rather than bumping further
+    }
+
+    #[test]
+    fn test_arena_allocator_rejects_oversized_request() {
+        let mut arena = ArenaAllocator::new();
+        assert!(arena.allocate(ARENA_SIZE_CLASSES.last().copied().unwrap() + 1).is_none());
+    }
+
+    #[test]
+    fn test_arena_allocator_reports_committed_and_resident_bytes() {
+        let mut arena = ArenaAllocator::new();
+        assert_eq!(arena.committed_bytes(), 0);
+
+        arena.allocate(1024).unwrap();
+        assert!(arena.committed_bytes() >= 1024);
+        assert_eq!(arena.committed_bytes(), arena.resident_bytes());
+    }
+
+    #[test]
+    fn test_arena_allocator_trim_does_not_crash() {
+        let mut arena = ArenaAllocator::new();
+        let ptr = arena.allocate(512).unwrap();
+        arena.deallocate(ptr, 512);
+        arena.trim();
+    }
+
+    #[test]
+    fn test_global_arena_bytes_reflects_allocations() {
+        let (before_committed, _) = global_arena_bytes();
+
+        let ptr = global_arena().lock().unwrap().allocate(4096).unwrap();
+        let (after_committed, after_resident) = global_arena_bytes();
+        assert!(after_committed >= before_committed);
+        assert!(after_resident > 0);
+
+        global_arena().lock().unwrap().deallocate(ptr, 4096);
+    }
+
+    #[cfg(feature = "thread-safe")]
+    #[test]
+    fn test_concurrent_memory_pool_allocate_deallocate_on_drop() {
+        let pool = ConcurrentMemoryPool::new(64, 1);
+
+        let block_a = pool.allocate();
+        assert_eq!(block_a.len(), 64);
+        let block_b = pool.allocate(); // Grows the pool since block_a is held
+        assert_ne!(block_a.index, block_b.index);
+
+        drop(block_a);
+        let block_c = pool.allocate();
+        assert_eq!(block_c.index, 0); // Reused the freed slot
+
+        let mut block_d = block_c;
+        block_d[0] = 42;
+        let index = block_d.index;
+        // `get_block` can't safely read a block that's still checked out (see
+        // its doc comment), so drop the handle first.
+        drop(block_d);
+        assert_eq!(pool.get_block(index).unwrap()[0], 42);
+    }
+
+    #[cfg(feature = "thread-safe")]
+    #[test]
+    fn test_concurrent_memory_pool_shared_across_threads() {
+        use std::thread;
+
+        let pool = ConcurrentMemoryPool::new(32, 4);
+        let handles: Vec<_> = (0..4u8)
+            .map(|i| {
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    let mut block = pool.allocate();
+                    block[0] = i;
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -21,6 +21,93 @@ pub enum QuantizationType {
     INT4,
     /// Dynamic quantization
     Dynamic,
+    /// GGML/GGUF Q4_0: 32-element blocks, 4-bit codes, one FP16 scale per block
+    Q4_0,
+    /// GGML/GGUF Q4_1: 32-element blocks, 4-bit codes, FP16 scale + FP16 min per block
+    Q4_1,
+    /// GGML/GGUF Q5_0: 32-element blocks, 5-bit codes, one FP16 scale per block
+    Q5_0,
+    /// GGML/GGUF Q5_1: 32-element blocks, 5-bit codes, FP16 scale + FP16 min per block
+    Q5_1,
+    /// GGML/GGUF Q8_0: 32-element blocks, 8-bit codes, one FP16 scale per block
+    Q8_0,
+    /// GGML/GGUF Q6_K: 256-element super-blocks, 6-bit codes, one FP16 scale per block
+    Q6K,
+    /// GPTQ: second-order, calibration-driven 4-bit quantization with
+    /// per-group scale/zero-point and sequential error compensation.
+    /// Requires calling `QuantizedModel::calibrate` before quantizing.
+    GPTQ,
+}
+
+impl QuantizationType {
+    /// Number of elements per block, for the GGML k-quant block formats.
+    /// `None` for the legacy whole-tensor formats.
+    fn block_size(self) -> Option<usize> {
+        match self {
+            QuantizationType::Q6K => Some(256),
+            QuantizationType::Q4_0
+            | QuantizationType::Q4_1
+            | QuantizationType::Q5_0
+            | QuantizationType::Q5_1
+            | QuantizationType::Q8_0 => Some(32),
+            _ => None,
+        }
+    }
+
+    /// Bits used per packed weight code, for the GGML k-quant block formats.
+    fn bits_per_weight(self) -> Option<usize> {
+        match self {
+            QuantizationType::Q4_0 | QuantizationType::Q4_1 => Some(4),
+            QuantizationType::Q5_0 | QuantizationType::Q5_1 => Some(5),
+            QuantizationType::Q8_0 => Some(8),
+            QuantizationType::Q6K => Some(6),
+            _ => None,
+        }
+    }
+
+    /// `true` for the `_1` variants, which store a per-block min alongside
+    /// the scale so the block can be quantized asymmetrically.
+    fn has_min(self) -> bool {
+        matches!(self, QuantizationType::Q4_1 | QuantizationType::Q5_1)
+    }
+
+    fn is_block_format(self) -> bool {
+        self.block_size().is_some()
+    }
+}
+
+/// Mixed-precision tensor-selection policy applied on top of
+/// `QuantizationConfig::quantization_type`.
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuantizationMode {
+    /// Quantize every tensor to `QuantizationConfig::quantization_type`
+    Uniform,
+    /// llama.cpp-style mixed precision: `output.weight` is quantized as
+    /// `Q6K` regardless of `quantization_type`, every other tensor uses
+    /// `quantization_type` as normal
+    Llama,
+}
+
+/// Rounding policy applied when snapping scaled weights onto the integer
+/// quantization grid.
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundingMode {
+    /// Round half away from zero (candle's `Tensor::round`). The default;
+    /// matches the behavior before `round_type` was configurable.
+    NearestAwayFromZero,
+    /// Round half to even (banker's rounding). Removes the systematic
+    /// upward bias `NearestAwayFromZero` has on tie values.
+    NearestEven,
+    /// Truncate toward zero.
+    TowardZero,
+}
+
+impl Default for RoundingMode {
+    fn default() -> Self {
+        Self::NearestAwayFromZero
+    }
 }
 
 /// Quantization configuration
@@ -39,6 +126,18 @@ pub struct QuantizationConfig {
     pub per_channel: Option<bool>,
     /// Quantization range clipping
     pub clip_range: Option<Vec<f32>>,
+    /// Mixed-precision tensor-selection policy; defaults to `Uniform`
+    pub mode: Option<QuantizationMode>,
+    /// Columns per quantization group for `GPTQ` (one scale/zero-point per
+    /// group of columns). Defaults to 128.
+    pub group_size: Option<u32>,
+    /// Hessian dampening fraction for `GPTQ`, as a fraction of `mean(diag(H))`
+    /// added to the diagonal before inversion, to keep it well-conditioned.
+    /// Defaults to 0.01 (1%), matching common GPTQ implementations.
+    pub gptq_dampening: Option<f32>,
+    /// Rounding policy used when snapping scaled `INT8`/`INT4` weights onto
+    /// the quantization grid. Defaults to `NearestAwayFromZero`.
+    pub round_type: Option<RoundingMode>,
 }
 
 impl Default for QuantizationConfig {
@@ -50,6 +149,10 @@ impl Default for QuantizationConfig {
             symmetric: Some(true),
             per_channel: Some(false),
             clip_range: None,
+            mode: Some(QuantizationMode::Uniform),
+            group_size: Some(128),
+            gptq_dampening: Some(0.01),
+            round_type: Some(RoundingMode::NearestAwayFromZero),
         }
     }
 }
@@ -62,16 +165,301 @@ pub struct QuantizedModel {
     original_size: u64,
     quantized_size: u64,
     device: Device,
+    /// Calibration activations staged by `calibrate()` for the next
+    /// `GPTQ`-typed `quantize_tensor` call; consumed and cleared by it.
+    pending_calibration: Option<Vec<Vec<f32>>>,
 }
 
 /// Quantized tensor with metadata
 #[derive(Debug, Clone)]
 pub struct QuantizedTensor {
-    pub data: Tensor,
+    pub data: QuantizedData,
     pub scale: Option<Tensor>,
     pub zero_point: Option<Tensor>,
     pub original_dtype: DType,
     pub quantized_dtype: DType,
+    /// Original tensor shape for `INT4`'s packed-nibble `Dense` representation.
+    /// The packed tensor's own length is `ceil(n/2)` bytes, which can't tell
+    /// whether the final byte's high nibble is real or zero-padding, so the
+    /// true shape/element count is recorded here instead.
+    pub packed_shape: Option<Vec<usize>>,
+    /// Reduced channel axis for per-channel `INT8`/`INT4` quantization
+    /// (`config.per_channel`): `scale`/`zero_point` keep this axis at size 1
+    /// so they broadcast against the tensor during dequant. `None` for a
+    /// single per-tensor scalar scale/zero-point.
+    pub channel_axis: Option<usize>,
+}
+
+/// Backing storage for a `QuantizedTensor`: either a real candle `Tensor`
+/// (for the legacy whole-tensor formats) or tightly packed GGML-style
+/// blocks (for the k-quant formats).
+#[derive(Debug, Clone)]
+pub enum QuantizedData {
+    Dense(Tensor),
+    Block(BlockQuantized),
+}
+
+/// A tensor packed as fixed-size GGML/GGUF blocks: each block stores an
+/// FP16 scale (and, for the `_1` formats, an FP16 min) alongside codes
+/// packed tightly at `format.bits_per_weight()` bits each.
+#[derive(Debug, Clone)]
+pub struct BlockQuantized {
+    pub format: QuantizationType,
+    pub block_size: usize,
+    pub num_elements: usize,
+    pub shape: Vec<usize>,
+    pub packed: Vec<u8>,
+    pub scales: Vec<f16>,
+    pub mins: Option<Vec<f16>>,
+}
+
+impl BlockQuantized {
+    /// True size on disk/in memory: packed codes plus per-block FP16 metadata.
+    fn size_in_bytes(&self) -> usize {
+        let scale_bytes = self.scales.len() * std::mem::size_of::<f16>();
+        let min_bytes = self.mins.as_ref().map_or(0, |m| m.len() * std::mem::size_of::<f16>());
+        self.packed.len() + scale_bytes + min_bytes
+    }
+}
+
+/// Resolve a `device_type` selector ("cpu", "cuda", or "metal") to a candle
+/// `Device`, matching the string-based device selection used elsewhere in
+/// the native module (e.g. `TensorWrapper::to_device`).
+fn parse_device(device_type: &str) -> napi::Result<Device> {
+    match device_type {
+        "cpu" => Ok(Device::Cpu),
+        #[cfg(feature = "gpu")]
+        "cuda" => Ok(Device::Cuda(candle_core::CudaDevice::new(0)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?)),
+        #[cfg(feature = "metal")]
+        "metal" => Ok(Device::Metal(candle_core::MetalDevice::new(0)
+            .map_err(|e| NativeError::tensor_op(e.to_string()))?)),
+        _ => Err(NativeError::invalid_input(format!("Unsupported device: {}", device_type)).into()),
+    }
+}
+
+/// Round half to even (banker's rounding): ties round to the nearest even
+/// integer instead of always away from zero, removing the systematic upward
+/// bias `f32::round` has on values that sit exactly on a grid midpoint.
+fn round_half_even(x: f32) -> f32 {
+    let floor = x.floor();
+    let diff = x - floor;
+    if diff < 0.5 {
+        floor
+    } else if diff > 0.5 {
+        floor + 1.0
+    } else if (floor as i64) % 2 == 0 {
+        floor
+    } else {
+        floor + 1.0
+    }
+}
+
+/// Pack `bits`-wide unsigned codes tightly into bytes, LSB-first.
+fn pack_bits(codes: &[u8], bits: usize) -> Vec<u8> {
+    let mut packed = Vec::with_capacity((codes.len() * bits).div_ceil(8));
+    let mut buffer: u32 = 0;
+    let mut buffered_bits = 0;
+
+    for &code in codes {
+        buffer |= (code as u32) << buffered_bits;
+        buffered_bits += bits;
+        while buffered_bits >= 8 {
+            packed.push((buffer & 0xFF) as u8);
+            buffer >>= 8;
+            buffered_bits -= 8;
+        }
+    }
+    if buffered_bits > 0 {
+        packed.push((buffer & 0xFF) as u8);
+    }
+    packed
+}
+
+/// Reverse of `pack_bits`: unpack `count` codes of `bits` width each.
+fn unpack_bits(packed: &[u8], bits: usize, count: usize) -> Vec<u8> {
+    let mask = (1u32 << bits) - 1;
+    let mut codes = Vec::with_capacity(count);
+    let mut buffer: u32 = 0;
+    let mut buffered_bits = 0;
+    let mut bytes = packed.iter();
+
+    for _ in 0..count {
+        while buffered_bits < bits {
+            buffer |= (*bytes.next().unwrap_or(&0) as u32) << buffered_bits;
+            buffered_bits += 8;
+        }
+        codes.push((buffer & mask) as u8);
+        buffer >>= bits;
+        buffered_bits -= bits;
+    }
+    codes
+}
+
+/// Dequantize widened integer codes to f32 via `(code - zero_point) * scale`,
+/// using AVX2 (`simd_x86`) or NEON (`simd_arm`) intrinsics — the cfgs
+/// `build.rs` emits for release builds on their respective targets — with a
+/// runtime feature check and a scalar fallback/tail for whatever's left.
+fn dequantize_codes(codes: &[i32], scale: f32, zero_point: f32) -> Vec<f32> {
+    #[cfg(simd_x86)]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            return unsafe { dequantize_codes_avx2(codes, scale, zero_point) };
+        }
+    }
+    #[cfg(simd_arm)]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return unsafe { dequantize_codes_neon(codes, scale, zero_point) };
+        }
+    }
+    dequantize_codes_scalar(codes, scale, zero_point)
+}
+
+fn dequantize_codes_scalar(codes: &[i32], scale: f32, zero_point: f32) -> Vec<f32> {
+    codes.iter().map(|&code| (code as f32 - zero_point) * scale).collect()
+}
+
+#[cfg(simd_x86)]
+#[target_feature(enable = "avx2")]
+unsafe fn dequantize_codes_avx2(codes: &[i32], scale: f32, zero_point: f32) -> Vec<f32> {
+    use std::arch::x86_64::*;
+
+    const LANES: usize = 8;
+    let mut out = vec![0.0_f32; codes.len()];
+    let scale_v = _mm256_set1_ps(scale);
+    let zero_point_v = _mm256_set1_ps(zero_point);
+
+    let chunks = codes.len() / LANES;
+    for chunk in 0..chunks {
+        let base = chunk * LANES;
+        let code_i32 = _mm256_loadu_si256(codes.as_ptr().add(base) as *const __m256i);
+        let code_f32 = _mm256_cvtepi32_ps(code_i32);
+        let result = _mm256_mul_ps(_mm256_sub_ps(code_f32, zero_point_v), scale_v);
+        _mm256_storeu_ps(out.as_mut_ptr().add(base), result);
+    }
+
+    for i in (chunks * LANES)..codes.len() {
+        out[i] = (codes[i] as f32 - zero_point) * scale;
+    }
+    out
+}
+
+#[cfg(simd_arm)]
+unsafe fn dequantize_codes_neon(codes: &[i32], scale: f32, zero_point: f32) -> Vec<f32> {
+    use std::arch::aarch64::*;
+
+    const LANES: usize = 4;
+    let mut out = vec![0.0_f32; codes.len()];
+    let scale_v = vdupq_n_f32(scale);
+    let zero_point_v = vdupq_n_f32(zero_point);
+
+    let chunks = codes.len() / LANES;
+    for chunk in 0..chunks {
+        let base = chunk * LANES;
+        let code_i32 = vld1q_s32(codes.as_ptr().add(base));
+        let code_f32 = vcvtq_f32_s32(code_i32);
+        let result = vmulq_f32(vsubq_f32(code_f32, zero_point_v), scale_v);
+        vst1q_f32(out.as_mut_ptr().add(base), result);
+    }
+
+    for i in (chunks * LANES)..codes.len() {
+        out[i] = (codes[i] as f32 - zero_point) * scale;
+    }
+    out
+}
+
+/// Build the GPTQ layer Hessian `H = 2·X·Xᵀ/n + λI` from calibration
+/// activations (one sample per row, `in_features` columns), with `λ` the
+/// dampening fraction of `mean(diag(H))`.
+fn build_hessian(activations: &[Vec<f32>], in_features: usize, dampening: f32) -> Result<Vec<Vec<f32>>> {
+    for row in activations {
+        if row.len() != in_features {
+            return Err(NativeError::quantization(format!(
+                "GPTQ calibration row has {} columns, expected {in_features} (the weight's in_features)",
+                row.len(),
+            )));
+        }
+    }
+
+    let mut hessian = vec![vec![0.0_f32; in_features]; in_features];
+    for sample in activations {
+        for i in 0..in_features {
+            let xi = sample[i];
+            for j in i..in_features {
+                hessian[i][j] += xi * sample[j];
+            }
+        }
+    }
+
+    let scale = 2.0 / activations.len() as f32;
+    for i in 0..in_features {
+        for j in i..in_features {
+            let value = hessian[i][j] * scale;
+            hessian[i][j] = value;
+            hessian[j][i] = value;
+        }
+    }
+
+    let mean_diag: f32 = (0..in_features).map(|i| hessian[i][i]).sum::<f32>() / in_features as f32;
+    let lambda = dampening * mean_diag;
+    for i in 0..in_features {
+        hessian[i][i] += lambda;
+    }
+
+    Ok(hessian)
+}
+
+/// Invert a symmetric positive-definite matrix via its Cholesky factor
+/// `H = L·Lᵀ`: solve for `L`, invert it by forward substitution, then
+/// recombine as `H⁻¹ = L⁻ᵀ·L⁻¹`.
+fn invert_via_cholesky(hessian: &[Vec<f32>]) -> Result<Vec<Vec<f32>>> {
+    let n = hessian.len();
+    let mut l = vec![vec![0.0_f32; n]; n];
+
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = hessian[i][j];
+            for k in 0..j {
+                sum -= l[i][k] * l[j][k];
+            }
+            if i == j {
+                if sum <= 0.0 {
+                    return Err(NativeError::quantization(
+                        "GPTQ Hessian is not positive-definite; increase gptq_dampening",
+                    ));
+                }
+                l[i][j] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+
+    let mut l_inv = vec![vec![0.0_f32; n]; n];
+    for col in 0..n {
+        l_inv[col][col] = 1.0 / l[col][col];
+        for row in (col + 1)..n {
+            let mut sum = 0.0;
+            for k in col..row {
+                sum -= l[row][k] * l_inv[k][col];
+            }
+            l_inv[row][col] = sum / l[row][row];
+        }
+    }
+
+    let mut hessian_inv = vec![vec![0.0_f32; n]; n];
+    for i in 0..n {
+        for j in 0..n {
+            let mut sum = 0.0;
+            for k in 0..n {
+                sum += l_inv[k][i] * l_inv[k][j];
+            }
+            hessian_inv[i][j] = sum;
+        }
+    }
+
+    Ok(hessian_inv)
 }
 
 /// Quantization statistics
@@ -86,20 +474,60 @@ pub struct QuantizationStats {
 
 #[napi]
 impl QuantizedModel {
-    /// Create a new quantized model
+    /// Create a new quantized model. `device_type` selects where
+    /// `quantize_tensor`/`dequantize_tensor` run their tensor math
+    /// ("cpu" (default), "cuda", or "metal"); see `to_device` to relocate an
+    /// already-quantized model.
     #[napi(constructor)]
-    pub fn new(config: QuantizationConfig) -> napi::Result<QuantizedModel> {
-        let device = Device::Cpu; // Default to CPU, can be extended for GPU
-        
+    pub fn new(config: QuantizationConfig, device_type: Option<String>) -> napi::Result<QuantizedModel> {
+        let device = parse_device(device_type.as_deref().unwrap_or("cpu"))?;
+
         Ok(QuantizedModel {
             weights: HashMap::new(),
             config,
             original_size: 0,
             quantized_size: 0,
             device,
+            pending_calibration: None,
         })
     }
 
+    /// Move every stored `QuantizedTensor` — data, scale, and zero-point —
+    /// plus all future `quantize_tensor`/`dequantize_tensor` math, onto
+    /// `device_type` ("cpu", "cuda", or "metal"). Lets a model quantized on
+    /// one device (e.g. staged on CPU) be relocated to GPU in one call
+    /// instead of round-tripping each tensor through host memory.
+    #[napi]
+    pub fn to_device(&mut self, device_type: String) -> napi::Result<()> {
+        let device = parse_device(&device_type)?;
+
+        for tensor in self.weights.values_mut() {
+            if let QuantizedData::Dense(data) = &tensor.data {
+                tensor.data = QuantizedData::Dense(data.to_device(&device).map_err(NativeError::from)?);
+            }
+            if let Some(scale) = &tensor.scale {
+                tensor.scale = Some(scale.to_device(&device).map_err(NativeError::from)?);
+            }
+            if let Some(zero_point) = &tensor.zero_point {
+                tensor.zero_point = Some(zero_point.to_device(&device).map_err(NativeError::from)?);
+            }
+        }
+
+        self.device = device;
+        Ok(())
+    }
+
+    /// Stage calibration activations for the next `quantize_tensor` call on
+    /// a `GPTQ`-typed tensor. `layer_inputs` is the layer's calibration
+    /// input batch, one sample per row, width matching the weight's
+    /// `in_features`. Consumed (and cleared) by that call, so call this
+    /// again before quantizing each GPTQ layer.
+    #[napi]
+    pub fn calibrate(&mut self, layer_inputs: Vec<Vec<f32>>) -> napi::Result<()> {
+        self.pending_calibration = Some(layer_inputs);
+        Ok(())
+    }
+
     /// Quantize a tensor according to the configuration
     #[napi]
     pub async fn quantize_tensor(&mut self, name: String, data: Vec<f32>, shape: Vec<u32>) -> napi::Result<()> {
@@ -112,19 +540,30 @@ impl QuantizedModel {
         let tensor = Tensor::from_vec(data, &shape, &self.device)
             .map_err(|e| NativeError::tensor_op(e.to_string()))?;
         
-        // Perform quantization based on type
-        let quantized = match self.config.quantization_type {
-            QuantizationType::None => self.quantize_none(tensor)?,
-            QuantizationType::FP16 => self.quantize_fp16(tensor)?,
-            QuantizationType::INT8 => self.quantize_int8(tensor)?,
-            QuantizationType::INT4 => self.quantize_int4(tensor)?,
-            QuantizationType::Dynamic => self.quantize_dynamic(tensor)?,
+        // Perform quantization based on type, honoring the mixed-precision
+        // policy (e.g. `Llama` mode forces `output.weight` to Q6_K)
+        let effective_type = self.resolve_quantization_type(&name);
+        let quantized = match effective_type {
+            QuantizationType::None => self.quantize_none(tensor.clone())?,
+            QuantizationType::FP16 => self.quantize_fp16(tensor.clone())?,
+            QuantizationType::INT8 => self.quantize_int8(tensor.clone())?,
+            QuantizationType::INT4 => self.quantize_int4(tensor.clone())?,
+            QuantizationType::Dynamic => self.quantize_dynamic(tensor.clone())?,
+            QuantizationType::GPTQ => {
+                let activations = self.pending_calibration.take()
+                    .ok_or_else(|| NativeError::quantization(
+                        "GPTQ quantization requires calibration activations; call calibrate() first",
+                    ))?;
+                self.quantize_gptq(&tensor, &activations)?
+            }
+            _ if effective_type.is_block_format() => self.quantize_block(&tensor, effective_type)?,
+            _ => unreachable!("all QuantizationType variants are handled above"),
         };
-        
+
         // Calculate sizes
         let original_size = tensor.elem_count() * tensor.dtype().size_in_bytes();
-        let quantized_size = quantized.data.elem_count() * quantized.data.dtype().size_in_bytes();
-        
+        let quantized_size = quantized.size_in_bytes();
+
         self.original_size += original_size as u64;
         self.quantized_size += quantized_size as u64;
         
@@ -178,14 +617,29 @@ impl QuantizedModel {
         self.weights.keys().cloned().collect()
     }
 
+    /// Tensor name this model's `Llama` mixed-precision policy pins to Q6_K,
+    /// mirroring llama.cpp's own convention for the final projection.
+    const LLAMA_MODE_OUTPUT_TENSOR: &'static str = "output.weight";
+
+    /// Resolve the quantization type to actually use for `tensor_name`,
+    /// applying `QuantizationConfig::mode` on top of `quantization_type`.
+    fn resolve_quantization_type(&self, tensor_name: &str) -> QuantizationType {
+        match self.config.mode.unwrap_or(QuantizationMode::Uniform) {
+            QuantizationMode::Llama if tensor_name == Self::LLAMA_MODE_OUTPUT_TENSOR => QuantizationType::Q6K,
+            _ => self.config.quantization_type,
+        }
+    }
+
     /// No quantization (passthrough)
     fn quantize_none(&self, tensor: Tensor) -> Result<QuantizedTensor> {
         Ok(QuantizedTensor {
-            data: tensor.clone(),
+            data: QuantizedData::Dense(tensor.clone()),
             scale: None,
             zero_point: None,
             original_dtype: tensor.dtype(),
             quantized_dtype: tensor.dtype(),
+            packed_shape: None,
+            channel_axis: None,
         })
     }
 
@@ -193,76 +647,344 @@ impl QuantizedModel {
     fn quantize_fp16(&self, tensor: Tensor) -> Result<QuantizedTensor> {
         let fp16_tensor = tensor.to_dtype(DType::F16)
             .map_err(|e| NativeError::quantization(e.to_string()))?;
-        
+
         Ok(QuantizedTensor {
-            data: fp16_tensor,
+            data: QuantizedData::Dense(fp16_tensor),
             scale: None,
             zero_point: None,
             original_dtype: tensor.dtype(),
             quantized_dtype: DType::F16,
+            packed_shape: None,
+            channel_axis: None,
         })
     }
 
     /// INT8 quantization with scale and zero point
     fn quantize_int8(&self, tensor: Tensor) -> Result<QuantizedTensor> {
-        // Calculate min and max values
-        let min_val = tensor.min(tensor.dims().len() - 1)
-            .map_err(|e| NativeError::quantization(e.to_string()))?;
-        let max_val = tensor.max(tensor.dims().len() - 1)
-            .map_err(|e| NativeError::quantization(e.to_string()))?;
-        
-        // Calculate scale and zero point
         let qmin = -128.0_f32;
         let qmax = 127.0_f32;
-        
-        let scale = (&max_val - &min_val)? / (qmax - qmin);
-        let zero_point = qmin - min_val.div(&scale)?;
-        
+        let (scale, zero_point, axis) = self.affine_quant_params(&tensor, qmin, qmax)?;
+
         // Quantize the tensor
-        let quantized = tensor.div(&scale)?
-            .add(&zero_point)?
-            .round()?
+        let scaled = tensor.broadcast_div(&scale)?.broadcast_add(&zero_point)?;
+        let quantized = self.round_with_mode(&scaled)?
             .clamp(qmin as f64, qmax as f64)?
             .to_dtype(DType::I64)?; // Using I64 as INT8 placeholder
-        
+
         Ok(QuantizedTensor {
-            data: quantized,
+            data: QuantizedData::Dense(quantized),
             scale: Some(scale),
             zero_point: Some(zero_point),
             original_dtype: tensor.dtype(),
             quantized_dtype: DType::I64,
+            packed_shape: None,
+            channel_axis: axis,
         })
     }
 
-    /// INT4 quantization (simplified implementation)
+    /// INT4 quantization: quantize to the 4-bit grid, then pack two codes
+    /// per byte so the on-disk/in-memory footprint is half a byte per value
+    /// (8x smaller than f32), not an 8-byte `I64` per value.
     fn quantize_int4(&self, tensor: Tensor) -> Result<QuantizedTensor> {
-        // For INT4, we'll use a similar approach to INT8 but with smaller range
-        let min_val = tensor.min(tensor.dims().len() - 1)
-            .map_err(|e| NativeError::quantization(e.to_string()))?;
-        let max_val = tensor.max(tensor.dims().len() - 1)
-            .map_err(|e| NativeError::quantization(e.to_string()))?;
-        
         let qmin = -8.0_f32;
         let qmax = 7.0_f32;
-        
-        let scale = (&max_val - &min_val)? / (qmax - qmin);
-        let zero_point = qmin - min_val.div(&scale)?;
-        
-        let quantized = tensor.div(&scale)?
-            .add(&zero_point)?
-            .round()?
+        let (scale, zero_point, axis) = self.affine_quant_params(&tensor, qmin, qmax)?;
+
+        let shape = tensor.dims().to_vec();
+        let scaled = tensor.broadcast_div(&scale)?.broadcast_add(&zero_point)?;
+        let codes: Vec<u8> = self.round_with_mode(&scaled)?
             .clamp(qmin as f64, qmax as f64)?
-            .to_dtype(DType::I64)?; // Using I64 as INT4 placeholder
-        
+            .flatten_all()?
+            .to_vec1::<f32>()?
+            .into_iter()
+            .map(|v| (v - qmin) as u8) // [-8, 7] -> [0, 15]
+            .collect();
+        let packed = pack_bits(&codes, 4);
+        let packed_len = packed.len();
+
         Ok(QuantizedTensor {
-            data: quantized,
+            data: QuantizedData::Dense(Tensor::from_vec(packed, packed_len, &self.device)?),
             scale: Some(scale),
             zero_point: Some(zero_point),
             original_dtype: tensor.dtype(),
-            quantized_dtype: DType::I64,
+            quantized_dtype: DType::U8,
+            packed_shape: Some(shape),
+            channel_axis: axis,
+        })
+    }
+
+    /// Compute per-tensor or per-channel affine quantization parameters for
+    /// `tensor`, honoring `config.per_channel` (one scale/zero-point per row,
+    /// reduced over the last axis, vs. a single per-tensor scalar), `config.symmetric`
+    /// (abs-max scaling with a zero-point forced to 0), and `config.clip_range`
+    /// (bounding min/max before computing the scale). `scale`/`zero_point` keep
+    /// the reduced axis at size 1 so they broadcast against `tensor` directly.
+    fn affine_quant_params(&self, tensor: &Tensor, qmin: f32, qmax: f32) -> Result<(Tensor, Tensor, Option<usize>)> {
+        let axis = self.config.per_channel.unwrap_or(false).then(|| tensor.dims().len() - 1);
+
+        let (mut min_val, mut max_val) = match axis {
+            Some(ax) => (tensor.min_keepdim(ax)?, tensor.max_keepdim(ax)?),
+            None => {
+                let flat = tensor.flatten_all()?;
+                (flat.min(0)?, flat.max(0)?)
+            }
+        };
+
+        if let Some(range) = self.config.clip_range.as_ref().filter(|r| r.len() >= 2) {
+            let (clip_min, clip_max) = (range[0] as f64, range[1] as f64);
+            min_val = min_val.clamp(clip_min, clip_max)?;
+            max_val = max_val.clamp(clip_min, clip_max)?;
+        }
+
+        if self.config.symmetric.unwrap_or(true) {
+            let abs_max = min_val.abs()?.maximum(&max_val.abs()?)?;
+            let scale = abs_max.affine((1.0 / qmax) as f64, 0.0)?;
+            let zero_point = scale.zeros_like()?;
+            Ok((scale, zero_point, axis))
+        } else {
+            let scale = (&max_val - &min_val)?.affine((1.0 / (qmax - qmin)) as f64, 0.0)?;
+            let zero_point = min_val.div(&scale)?.affine(-1.0, qmin as f64)?;
+            Ok((scale, zero_point, axis))
+        }
+    }
+
+    /// Round `tensor` onto the integer grid per `config.round_type`.
+    /// `NearestAwayFromZero` uses candle's built-in (and fastest) `round`;
+    /// the other modes need elementwise control candle doesn't expose, so
+    /// they round off the tensor's `Vec<f32>` and rebuild it.
+    fn round_with_mode(&self, tensor: &Tensor) -> Result<Tensor> {
+        match self.config.round_type.unwrap_or_default() {
+            RoundingMode::NearestAwayFromZero => Ok(tensor.round()?),
+            RoundingMode::NearestEven => self.round_elementwise(tensor, round_half_even),
+            RoundingMode::TowardZero => self.round_elementwise(tensor, f32::trunc),
+        }
+    }
+
+    fn round_elementwise(&self, tensor: &Tensor, f: impl Fn(f32) -> f32) -> Result<Tensor> {
+        let shape = tensor.dims().to_vec();
+        let values: Vec<f32> = tensor.flatten_all()?.to_vec1::<f32>()?.into_iter().map(f).collect();
+        Ok(Tensor::from_vec(values, shape.as_slice(), &self.device)?)
+    }
+
+    /// Block-quantize a tensor into fixed-size GGML/GGUF-style blocks: each
+    /// block stores an FP16 scale (and, for `_1` formats, an FP16 min) and
+    /// `bits_per_weight()`-wide codes packed tightly back to back.
+    fn quantize_block(&self, tensor: &Tensor, format: QuantizationType) -> Result<QuantizedTensor> {
+        let block_size = format.block_size()
+            .ok_or_else(|| NativeError::quantization("Not a block quantization format"))?;
+        let bits = format.bits_per_weight().expect("block formats carry a bit width");
+        let qmax = ((1u32 << bits) - 1) as f32;
+
+        let shape = tensor.dims().to_vec();
+        let flat = tensor.to_dtype(DType::F32)
+            .and_then(|t| t.flatten_all())
+            .map_err(|e| NativeError::quantization(e.to_string()))?
+            .to_vec1::<f32>()
+            .map_err(|e| NativeError::quantization(e.to_string()))?;
+
+        let num_blocks = flat.len().div_ceil(block_size);
+        let mut codes = Vec::with_capacity(flat.len());
+        let mut scales = Vec::with_capacity(num_blocks);
+        let mut mins = format.has_min().then(|| Vec::with_capacity(num_blocks));
+
+        for block in flat.chunks(block_size) {
+            if format.has_min() {
+                let min_val = block.iter().cloned().fold(f32::INFINITY, f32::min);
+                let max_val = block.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+                let scale = if max_val > min_val { (max_val - min_val) / qmax } else { 1.0 };
+
+                for &value in block {
+                    let code = ((value - min_val) / scale).round().clamp(0.0, qmax);
+                    codes.push(code as u8);
+                }
+                scales.push(f16::from_f32(scale));
+                mins.as_mut().unwrap().push(f16::from_f32(min_val));
+            } else {
+                let abs_max = block.iter().fold(0.0_f32, |acc, &v| acc.max(v.abs()));
+                let half_range = qmax / 2.0;
+                let scale = if abs_max > 0.0 { abs_max / half_range } else { 1.0 };
+
+                for &value in block {
+                    let code = (value / scale + half_range).round().clamp(0.0, qmax);
+                    codes.push(code as u8);
+                }
+                scales.push(f16::from_f32(scale));
+            }
+        }
+
+        let block = BlockQuantized {
+            format,
+            block_size,
+            num_elements: flat.len(),
+            shape,
+            packed: pack_bits(&codes, bits),
+            scales,
+            mins,
+        };
+
+        Ok(QuantizedTensor {
+            data: QuantizedData::Block(block),
+            scale: None,
+            zero_point: None,
+            original_dtype: tensor.dtype(),
+            quantized_dtype: DType::U8,
+            packed_shape: None,
+            channel_axis: None,
+        })
+    }
+
+    /// Reverse `quantize_block`: unpack the codes and rebuild an FP32 tensor
+    /// of the original shape, one block at a time.
+    fn dequantize_block(&self, block: &BlockQuantized) -> Result<Tensor> {
+        let bits = block.format.bits_per_weight()
+            .ok_or_else(|| NativeError::quantization("Not a block quantization format"))?;
+        let codes = unpack_bits(&block.packed, bits, block.num_elements);
+        let half_range = (((1u32 << bits) - 1) as f32) / 2.0;
+
+        let mut values = Vec::with_capacity(block.num_elements);
+        for (i, &code) in codes.iter().enumerate() {
+            let block_idx = i / block.block_size;
+            let scale = block.scales[block_idx].to_f32();
+            let value = match &block.mins {
+                Some(mins) => code as f32 * scale + mins[block_idx].to_f32(),
+                None => (code as f32 - half_range) * scale,
+            };
+            values.push(value);
+        }
+
+        Tensor::from_vec(values, block.shape.as_slice(), &self.device)
+            .map_err(|e| NativeError::quantization(e.to_string()))
+    }
+
+    /// GPTQ: second-order 4-bit quantization of a 2D (out_features x
+    /// in_features) weight matrix. Builds the Hessian of the layer's
+    /// calibration activations, quantizes columns left-to-right in groups
+    /// of `config.group_size`, and after each column subtracts its
+    /// weighted quantization error from every not-yet-quantized column so
+    /// later columns compensate for earlier rounding.
+    fn quantize_gptq(&self, tensor: &Tensor, activations: &[Vec<f32>]) -> Result<QuantizedTensor> {
+        if activations.is_empty() {
+            return Err(NativeError::quantization(
+                "GPTQ quantization requires calibration activations; call calibrate() first",
+            ));
+        }
+
+        let shape = tensor.dims().to_vec();
+        if shape.len() != 2 {
+            return Err(NativeError::quantization(
+                "GPTQ quantization requires a 2D (out_features x in_features) weight tensor",
+            ));
+        }
+        let (out_features, in_features) = (shape[0], shape[1]);
+
+        let mut w = tensor.to_dtype(DType::F32)
+            .and_then(|t| t.flatten_all())
+            .map_err(|e| NativeError::quantization(e.to_string()))?
+            .to_vec1::<f32>()
+            .map_err(|e| NativeError::quantization(e.to_string()))?;
+
+        let dampening = self.config.gptq_dampening.unwrap_or(0.01);
+        let hessian = build_hessian(activations, in_features, dampening)?;
+        let hinv = invert_via_cholesky(&hessian)?;
+
+        let group_size = (self.config.group_size.unwrap_or(128) as usize).max(1);
+        let qmin = -8.0_f32;
+        let qmax = 7.0_f32;
+        let bits = 4;
+
+        let mut codes = vec![0u8; out_features * in_features];
+        let mut scales = Vec::with_capacity(in_features.div_ceil(group_size));
+        let mut zero_points = Vec::with_capacity(in_features.div_ceil(group_size));
+
+        let mut group_start = 0;
+        while group_start < in_features {
+            let group_end = (group_start + group_size).min(in_features);
+
+            // One scale/zero-point per group, from the group's current
+            // (possibly already error-compensated) values across all rows.
+            let mut min_val = f32::INFINITY;
+            let mut max_val = f32::NEG_INFINITY;
+            for row in 0..out_features {
+                for col in group_start..group_end {
+                    let v = w[row * in_features + col];
+                    min_val = min_val.min(v);
+                    max_val = max_val.max(v);
+                }
+            }
+            let scale = if max_val > min_val { (max_val - min_val) / (qmax - qmin) } else { 1.0 };
+            let zero_point = qmin - min_val / scale;
+            scales.push(f16::from_f32(scale));
+            zero_points.push(f16::from_f32(zero_point));
+
+            for col in group_start..group_end {
+                let hinv_ii = hinv[col][col];
+                for row in 0..out_features {
+                    let idx = row * in_features + col;
+                    let w_val = w[idx];
+                    let code = (w_val / scale + zero_point).round().clamp(qmin, qmax);
+                    codes[idx] = (code - qmin) as u8;
+
+                    let dequantized = (code - zero_point) * scale;
+                    let error = (w_val - dequantized) / hinv_ii;
+
+                    // Compensate every not-yet-quantized column in this row.
+                    for future_col in (col + 1)..in_features {
+                        w[row * in_features + future_col] -= error * hinv[col][future_col];
+                    }
+                }
+            }
+
+            group_start = group_end;
+        }
+
+        let block = BlockQuantized {
+            format: QuantizationType::GPTQ,
+            block_size: group_size,
+            num_elements: out_features * in_features,
+            shape,
+            packed: pack_bits(&codes, bits),
+            scales,
+            mins: Some(zero_points),
+        };
+
+        Ok(QuantizedTensor {
+            data: QuantizedData::Block(block),
+            scale: None,
+            zero_point: None,
+            original_dtype: tensor.dtype(),
+            quantized_dtype: DType::U8,
+            packed_shape: None,
+            channel_axis: None,
         })
     }
 
+    /// Reverse `quantize_gptq`: unlike `dequantize_block`, GPTQ groups run
+    /// along the weight's `in_features` columns and are shared across every
+    /// row, so the group index comes from the column, not the flat offset.
+    fn dequantize_gptq(&self, block: &BlockQuantized) -> Result<Tensor> {
+        let qmin = -8.0_f32;
+        let codes = unpack_bits(&block.packed, 4, block.num_elements);
+        let zero_points = block.mins.as_ref()
+            .ok_or_else(|| NativeError::quantization("GPTQ block is missing its zero points"))?;
+        let in_features = *block.shape.last()
+            .ok_or_else(|| NativeError::quantization("GPTQ block is missing its shape"))?;
+        let out_features = block.num_elements / in_features;
+
+        let mut values = Vec::with_capacity(block.num_elements);
+        for row in 0..out_features {
+            for col in 0..in_features {
+                let group = col / block.block_size;
+                let scale = block.scales[group].to_f32();
+                let zero_point = zero_points[group].to_f32();
+                let code = codes[row * in_features + col] as f32 + qmin;
+                values.push((code - zero_point) * scale);
+            }
+        }
+
+        Tensor::from_vec(values, block.shape.as_slice(), &self.device)
+            .map_err(|e| NativeError::quantization(e.to_string()))
+    }
+
     /// Dynamic quantization (weights only)
     fn quantize_dynamic(&self, tensor: Tensor) -> Result<QuantizedTensor> {
         // Choose quantization type based on tensor properties
@@ -282,29 +1004,106 @@ impl QuantizedModel {
 
     /// Dequantize a tensor back to original precision
     fn dequantize_tensor(&self, quantized: &QuantizedTensor) -> Result<Tensor> {
+        let data = match &quantized.data {
+            QuantizedData::Block(block) if block.format == QuantizationType::GPTQ => {
+                return self.dequantize_gptq(block)
+            }
+            QuantizedData::Block(block) => return self.dequantize_block(block),
+            QuantizedData::Dense(data) => data,
+        };
+
+        if let Some(shape) = &quantized.packed_shape {
+            return self.dequantize_int4_packed(data, shape, quantized);
+        }
+
         match quantized.quantized_dtype {
             DType::F16 => {
                 // Convert FP16 back to FP32
-                quantized.data.to_dtype(DType::F32)
+                data.to_dtype(DType::F32)
                     .map_err(|e| NativeError::quantization(e.to_string()))
             }
             DType::I64 => {
-                // Dequantize INT8/INT4
-                if let (Some(scale), Some(zero_point)) = (&quantized.scale, &quantized.zero_point) {
-                    let dequantized = quantized.data.to_dtype(DType::F32)?
-                        .sub(zero_point)?
-                        .mul(scale)?;
-                    Ok(dequantized)
-                } else {
-                    Err(NativeError::quantization("Missing scale or zero_point for INT quantization"))
+                // Dequantize INT8 (INT4 now takes the packed-nibble path above)
+                let (scale, zero_point) = match (&quantized.scale, &quantized.zero_point) {
+                    (Some(scale), Some(zero_point)) => (scale, zero_point),
+                    _ => return Err(NativeError::quantization("Missing scale or zero_point for INT quantization")),
+                };
+
+                // Fast path: a single scalar scale/zero-point (the common
+                // per-tensor case) goes through the SIMD kernel; anything
+                // broadcast per-row falls back to candle's generic ops.
+                if let (Ok(scale), Ok(zero_point)) = (scale.to_scalar::<f32>(), zero_point.to_scalar::<f32>()) {
+                    let shape = data.dims().to_vec();
+                    let codes: Vec<i32> = data.to_dtype(DType::I64)?
+                        .flatten_all()?
+                        .to_vec1::<i64>()?
+                        .into_iter()
+                        .map(|code| code as i32)
+                        .collect();
+                    let values = dequantize_codes(&codes, scale, zero_point);
+                    return Tensor::from_vec(values, shape.as_slice(), &self.device)
+                        .map_err(|e| NativeError::quantization(e.to_string()));
                 }
+
+                data.to_dtype(DType::F32)?
+                    .broadcast_sub(zero_point)?
+                    .broadcast_mul(scale)
+                    .map_err(|e| NativeError::quantization(e.to_string()))
             }
             _ => {
                 // No quantization or unknown type
-                Ok(quantized.data.clone())
+                Ok(data.clone())
             }
         }
     }
+
+    /// Reverse the INT4 nibble packing in `quantize_int4`: unpack two 4-bit
+    /// codes per byte, sign-extend each back to the `[-8, 7]` grid, and
+    /// apply the stored scale/zero-point. `shape` is the pre-packing shape,
+    /// since the packed tensor's own length (`ceil(n/2)` bytes) can't tell
+    /// real codes from final-byte zero-padding.
+    fn dequantize_int4_packed(&self, packed: &Tensor, shape: &[usize], quantized: &QuantizedTensor) -> Result<Tensor> {
+        let element_count: usize = shape.iter().product();
+        let bytes = packed.to_dtype(DType::U8)
+            .and_then(|t| t.to_vec1::<u8>())
+            .map_err(|e| NativeError::quantization(e.to_string()))?;
+        let codes = unpack_bits(&bytes, 4, element_count);
+
+        let qmin = -8_i32;
+        let widened: Vec<i32> = codes.iter().map(|&code| code as i32 + qmin).collect();
+
+        let (scale, zero_point) = match (&quantized.scale, &quantized.zero_point) {
+            (Some(scale), Some(zero_point)) => (scale, zero_point),
+            _ => return Err(NativeError::quantization("Missing scale or zero_point for INT4 quantization")),
+        };
+
+        // Fast path: a single scalar scale/zero-point goes through the SIMD
+        // kernel; a broadcast per-row scale falls back to candle's generic ops.
+        if let (Ok(scale), Ok(zero_point)) = (scale.to_scalar::<f32>(), zero_point.to_scalar::<f32>()) {
+            let values = dequantize_codes(&widened, scale, zero_point);
+            return Tensor::from_vec(values, shape, &self.device)
+                .map_err(|e| NativeError::quantization(e.to_string()));
+        }
+
+        let values: Vec<f32> = widened.iter().map(|&code| code as f32).collect();
+        let dequantized = Tensor::from_vec(values, shape, &self.device)
+            .map_err(|e| NativeError::quantization(e.to_string()))?;
+        dequantized.broadcast_sub(zero_point)
+            .and_then(|t| t.broadcast_mul(scale))
+            .map_err(|e| NativeError::quantization(e.to_string()))
+    }
+}
+
+impl QuantizedTensor {
+    /// True size in bytes: a real tensor's byte footprint for the
+    /// whole-tensor formats, or packed codes plus FP16 metadata for the
+    /// block formats.
+    fn size_in_bytes(&self) -> usize {
+        match &self.data {
+            QuantizedData::Dense(tensor) => tensor.elem_count() * tensor.dtype().size_in_bytes(),
+            QuantizedData::Block(block) => block.size_in_bytes(),
+        }
+    }
 }
 
 /// Utility functions for quantization
@@ -355,6 +1154,23 @@ pub mod utils {
             QuantizationType::INT8 => elem_count * 1,
             QuantizationType::INT4 => elem_count / 2, // Packed
             QuantizationType::Dynamic => elem_count * 2, // Assume FP16 average
+            QuantizationType::Q4_0 | QuantizationType::Q4_1 | QuantizationType::Q5_0
+            | QuantizationType::Q5_1 | QuantizationType::Q8_0 | QuantizationType::Q6K => {
+                let block_size = target_qtype.block_size().unwrap();
+                let bits = target_qtype.bits_per_weight().unwrap();
+                let blocks = elem_count.div_ceil(block_size);
+                let packed_bytes = (elem_count * bits).div_ceil(8);
+                let metadata_bytes = blocks * 2 + if target_qtype.has_min() { blocks * 2 } else { 0 };
+                packed_bytes + metadata_bytes
+            }
+            QuantizationType::GPTQ => {
+                // Shape and group size aren't known here; count one group per
+                // 128 elements, which over-counts `quantize_gptq`'s column
+                // groups (each shared by every row) for multi-row weights.
+                let groups = elem_count.div_ceil(128);
+                let packed_bytes = (elem_count * 4).div_ceil(8);
+                packed_bytes + groups * 4 // FP16 scale + FP16 zero-point per group
+            }
         };
         
         let compression_ratio = original_size as f32 / target_size as f32;
@@ -376,7 +1192,7 @@ pub async fn benchmark_quantization(
     let mut model = QuantizedModel::new(QuantizationConfig {
         quantization_type: qtype,
         ..Default::default()
-    })?;
+    }, None)?;
     
     let start = std::time::Instant::now();
     
@@ -387,7 +1203,35 @@ pub async fn benchmark_quantization(
     
     let duration = start.elapsed();
     let ops_per_sec = iterations as f64 / duration.as_secs_f64();
-    
+
+    Ok(ops_per_sec)
+}
+
+/// Benchmark dequantization throughput (exercises the SIMD dequant kernels)
+#[napi]
+pub async fn benchmark_dequantization(
+    data: Vec<f32>,
+    shape: Vec<u32>,
+    qtype: QuantizationType,
+    iterations: u32,
+) -> napi::Result<f64> {
+    let _timer = Timer::new("dequantization_benchmark");
+
+    let mut model = QuantizedModel::new(QuantizationConfig {
+        quantization_type: qtype,
+        ..Default::default()
+    }, None)?;
+    model.quantize_tensor("tensor".to_string(), data, shape).await?;
+
+    let start = std::time::Instant::now();
+
+    for _ in 0..iterations {
+        model.get_tensor("tensor".to_string()).await?;
+    }
+
+    let duration = start.elapsed();
+    let ops_per_sec = iterations as f64 / duration.as_secs_f64();
+
     Ok(ops_per_sec)
 }
 
@@ -398,16 +1242,45 @@ mod tests {
     #[tokio::test]
     async fn test_quantized_model_creation() {
         let config = QuantizationConfig::default();
-        let model = QuantizedModel::new(config);
+        let model = QuantizedModel::new(config, None);
         assert!(model.is_ok());
     }
-    
+
+    #[test]
+    fn test_quantized_model_rejects_unknown_device() {
+        let err = QuantizedModel::new(QuantizationConfig::default(), Some("tpu".to_string()));
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_to_device_relocates_stored_tensors_and_survives_round_trip() {
+        // CPU is the only device guaranteed available in this sandbox, so
+        // this exercises to_device's relocation plumbing rather than an
+        // actual cross-device move, but it still covers data/scale/zero_point
+        // all landing on the requested device and staying dequantizable.
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::INT8,
+            ..Default::default()
+        }, None).unwrap();
+
+        let data: Vec<f32> = (0..10).map(|i| i as f32 - 5.0).collect();
+        model.quantize_tensor("test".to_string(), data.clone(), vec![10]).await.unwrap();
+
+        model.to_device("cpu".to_string()).unwrap();
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 1.0, "{original} vs {dequantized}");
+        }
+    }
+
     #[tokio::test]
     async fn test_fp16_quantization() {
         let mut model = QuantizedModel::new(QuantizationConfig {
             quantization_type: QuantizationType::FP16,
             ..Default::default()
-        }).unwrap();
+        }, None).unwrap();
         
         let data = vec![1.0, 2.0, 3.0, 4.0];
         let shape = vec![2, 2];
@@ -424,7 +1297,7 @@ mod tests {
         let mut model = QuantizedModel::new(QuantizationConfig {
             quantization_type: QuantizationType::INT8,
             ..Default::default()
-        }).unwrap();
+        }, None).unwrap();
         
         let data = (0..100).map(|i| i as f32 / 10.0).collect();
         let shape = vec![10, 10];
@@ -438,6 +1311,275 @@ mod tests {
         assert_eq!(retrieved.unwrap().len(), 100);
     }
     
+    #[tokio::test]
+    async fn test_int8_quantization_1d_takes_simd_dequant_fast_path() {
+        // A 1D tensor reduces min/max to a scalar scale/zero-point, which is
+        // what routes dequantize_tensor through the SIMD kernel.
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::INT8,
+            ..Default::default()
+        }, None).unwrap();
+
+        let data: Vec<f32> = (0..32).map(|i| (i as f32 - 16.0) / 4.0).collect();
+        let shape = vec![32];
+
+        model.quantize_tensor("test".to_string(), data.clone(), shape).await.unwrap();
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 1.0, "{original} vs {dequantized}");
+        }
+    }
+
+    #[test]
+    fn test_dequantize_codes_matches_scalar_formula() {
+        let codes = vec![-8, -4, 0, 4, 7];
+        let scale = 0.5;
+        let zero_point = -1.0;
+
+        let values = dequantize_codes(&codes, scale, zero_point);
+        for (&code, &value) in codes.iter().zip(values.iter()) {
+            let expected = (code as f32 - zero_point) * scale;
+            assert!((value - expected).abs() < 1e-6);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_int4_quantization_packs_two_codes_per_byte() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::INT4,
+            ..Default::default()
+        }, None).unwrap();
+
+        // Odd element count exercises the final byte's zero-padded high nibble.
+        let data: Vec<f32> = (0..9).map(|i| (i as f32 - 4.0) / 2.0).collect();
+        let shape = vec![9];
+
+        model.quantize_tensor("test".to_string(), data.clone(), shape).await.unwrap();
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 1.0, "{original} vs {dequantized}");
+        }
+
+        // 9 codes packed 2-per-byte == 5 bytes, versus 9 * 4 = 36 bytes unquantized.
+        let stats = model.get_stats();
+        assert!(stats.compression_ratio > 5.0, "{}", stats.compression_ratio);
+    }
+
+    #[tokio::test]
+    async fn test_int8_quantization_per_channel_round_trips() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::INT8,
+            per_channel: Some(true),
+            ..Default::default()
+        }, None).unwrap();
+
+        // Two rows with very different magnitudes: a shared per-tensor scale
+        // would starve the small row of resolution, per-channel should not.
+        let data: Vec<f32> = vec![
+            -1.0, -0.5, 0.0, 0.5, 1.0,
+            -100.0, -50.0, 0.0, 50.0, 100.0,
+        ];
+        let shape = vec![2, 5];
+
+        model.quantize_tensor("test".to_string(), data.clone(), shape).await.unwrap();
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            let tolerance = (original.abs() * 0.05).max(0.05);
+            assert!((original - dequantized).abs() < tolerance, "{original} vs {dequantized}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_int8_quantization_asymmetric_handles_skewed_range() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::INT8,
+            symmetric: Some(false),
+            ..Default::default()
+        }, None).unwrap();
+
+        // All-positive, skewed range: symmetric (abs-max around zero) would
+        // waste half the code space, asymmetric should use it all.
+        let data: Vec<f32> = (0..20).map(|i| 10.0 + i as f32).collect();
+        let shape = vec![20];
+
+        model.quantize_tensor("test".to_string(), data.clone(), shape).await.unwrap();
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 0.5, "{original} vs {dequantized}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_int8_quantization_clip_range_saturates_outliers() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::INT8,
+            clip_range: Some(vec![-1.0, 1.0]),
+            ..Default::default()
+        }, None).unwrap();
+
+        // A single huge outlier would blow up the scale if it weren't
+        // clipped; with clip_range the in-range values keep their resolution
+        // and the outlier just saturates.
+        let mut data: Vec<f32> = vec![-1.0, -0.5, 0.0, 0.5, 1.0];
+        data.push(1000.0);
+        let shape = vec![data.len()];
+
+        model.quantize_tensor("test".to_string(), data.clone(), shape).await.unwrap();
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().take(5).zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 0.1, "{original} vs {dequantized}");
+        }
+        // Saturated at the clip maximum: code 127 times scale 1/127.
+        assert!((retrieved.last().unwrap() - 1.0).abs() < 0.01, "{}", retrieved.last().unwrap());
+    }
+
+    #[test]
+    fn test_round_half_even_breaks_ties_to_even() {
+        assert_eq!(round_half_even(0.5), 0.0);
+        assert_eq!(round_half_even(1.5), 2.0);
+        assert_eq!(round_half_even(2.5), 2.0);
+        assert_eq!(round_half_even(-0.5), 0.0);
+        assert_eq!(round_half_even(-1.5), -2.0);
+        // Non-tie values round as usual.
+        assert_eq!(round_half_even(1.2), 1.0);
+        assert_eq!(round_half_even(1.8), 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_int8_quantization_nearest_even_round_type_round_trips() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::INT8,
+            round_type: Some(RoundingMode::NearestEven),
+            ..Default::default()
+        }, None).unwrap();
+
+        let data: Vec<f32> = (0..20).map(|i| (i as f32 - 10.0) / 3.0).collect();
+        let shape = vec![data.len()];
+
+        model.quantize_tensor("test".to_string(), data.clone(), shape).await.unwrap();
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 0.5, "{original} vs {dequantized}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_q4_0_quantization_round_trips_within_block_resolution() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::Q4_0,
+            ..Default::default()
+        }, None).unwrap();
+
+        let data: Vec<f32> = (0..64).map(|i| (i as f32 - 32.0) / 4.0).collect();
+        let shape = vec![64];
+
+        let result = model.quantize_tensor("test".to_string(), data.clone(), shape).await;
+        assert!(result.is_ok());
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 1.0, "{original} vs {dequantized}");
+        }
+
+        // Two 32-element Q4_0 blocks: 32 packed bytes + 2 FP16 scales == 36 bytes,
+        // versus 64 * 4 = 256 bytes unquantized.
+        let stats = model.get_stats();
+        assert!(stats.compression_ratio > 7.0, "{}", stats.compression_ratio);
+    }
+
+    #[tokio::test]
+    async fn test_q6k_block_size_is_256_elements() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::Q6K,
+            ..Default::default()
+        }, None).unwrap();
+
+        let data: Vec<f32> = (0..256).map(|i| i as f32 / 10.0).collect();
+        let shape = vec![256];
+
+        model.quantize_tensor("test".to_string(), data, shape).await.unwrap();
+        let retrieved = model.get_tensor("test".to_string()).await;
+        assert_eq!(retrieved.unwrap().len(), 256);
+    }
+
+    #[tokio::test]
+    async fn test_llama_mode_uses_q6k_for_output_weight_only() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::Q4_0,
+            mode: Some(QuantizationMode::Llama),
+            ..Default::default()
+        }, None).unwrap();
+
+        let data = vec![0.5_f32; 32];
+        model.quantize_tensor("output.weight".to_string(), data.clone(), vec![32]).await.unwrap();
+        model.quantize_tensor("layer0.weight".to_string(), data, vec![32]).await.unwrap();
+
+        assert!(matches!(
+            model.weights.get("output.weight").unwrap().data,
+            QuantizedData::Block(BlockQuantized { format: QuantizationType::Q6K, .. })
+        ));
+        assert!(matches!(
+            model.weights.get("layer0.weight").unwrap().data,
+            QuantizedData::Block(BlockQuantized { format: QuantizationType::Q4_0, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_gptq_quantization_requires_calibration() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::GPTQ,
+            ..Default::default()
+        }, None).unwrap();
+
+        let data = vec![0.5_f32; 8];
+        let result = model.quantize_tensor("test".to_string(), data, vec![2, 4]).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gptq_quantization_round_trips_within_group_resolution() {
+        let mut model = QuantizedModel::new(QuantizationConfig {
+            quantization_type: QuantizationType::GPTQ,
+            group_size: Some(4),
+            ..Default::default()
+        }, None).unwrap();
+
+        // 2 out_features x 4 in_features weight, calibrated on 16 activation samples.
+        let data: Vec<f32> = vec![0.1, 0.2, -0.3, 0.4, -0.5, 0.6, 0.7, -0.8];
+        let activations: Vec<Vec<f32>> = (0..16)
+            .map(|i| (0..4).map(|j| ((i * 4 + j) as f32 / 10.0) - 3.2).collect())
+            .collect();
+
+        model.calibrate(activations).unwrap();
+        model.quantize_tensor("test".to_string(), data.clone(), vec![2, 4]).await.unwrap();
+
+        let retrieved = model.get_tensor("test".to_string()).await.unwrap();
+        assert_eq!(retrieved.len(), data.len());
+        for (original, dequantized) in data.iter().zip(retrieved.iter()) {
+            assert!((original - dequantized).abs() < 1.0, "{original} vs {dequantized}");
+        }
+    }
+
+    #[test]
+    fn test_pack_unpack_bits_round_trips() {
+        let codes: Vec<u8> = (0..32).map(|i| i % 16).collect();
+        let packed = pack_bits(&codes, 4);
+        let unpacked = unpack_bits(&packed, 4, codes.len());
+        assert_eq!(codes, unpacked);
+    }
+
     #[test]
     fn test_memory_estimation() {
         let (original, quantized, ratio) = utils::estimate_memory_savings(
@@ -445,7 +1587,7 @@ mod tests {
             QuantizationType::INT8,
             1000,
         );
-        
+
         assert_eq!(original, 4000); // 1000 * 4 bytes
         assert_eq!(quantized, 1000); // 1000 * 1 byte
         assert_eq!(ratio, 4.0);